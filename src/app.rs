@@ -1,25 +1,31 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::misc::config::config;
+use crate::misc::keybindings::{Context, keybindings};
 use crate::misc::remote_load::{self, RemoteLoad};
+use crate::misc::type_ext::UnwrapOrEnqueueError;
 use crate::tui::Pane;
+use crate::tui::key_hints::KeyHints;
 use crate::tui::popups::download_notif::DownloadNotification;
 use crate::tui::popups::sql_query_picker::SqlQueryPicker;
 use crate::tui::table::Table;
 use crate::tui::toast::Toast;
 use crate::tui::{error_popup::ErrorPopup, tabs::Tabs};
 use crate::{
-    handler::message::Message,
+    handler::{command, message::Message},
     tui::{
         component::{Component, FocusState},
         popups::{
-            command_palette::CommandPalette, help_modal::Help, importer::Importer,
+            command_palette::{Command, CommandPalette},
+            help_modal::Help,
+            importer::Importer,
             theme_selector::ThemeSelector,
         },
         schema::schema::Schema,
+        themes::theme::LoadedTheme,
     },
 };
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 use itertools::Itertools;
 use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
 use url::Url;
@@ -31,6 +37,31 @@ pub struct App {
     toast: Option<Toast>,
     dls: Vec<DownloadNotification>,
     running: bool,
+    macros: Macros,
+    /// Whether the leader key was typed, waiting for the key of a mapping.
+    leader: bool,
+    /// Last command line run from the palette, and the last changing the data.
+    last_command: Option<String>,
+    last_data_command: Option<String>,
+}
+
+/// Messages recorded into registers `a-z` with `q` and replayed with `@`.
+/// Messages are recorded rather than keys, so a macro does the same on any tab
+/// whatever popup or mode was open when it was recorded.
+#[derive(Debug, Default)]
+struct Macros {
+    registers: HashMap<char, Vec<Message>>,
+    recording: Option<(char, Vec<Message>)>,
+    /// Whether the next key names the register to record into or replay.
+    pending: Option<MacroKey>,
+    last: Option<char>,
+    replaying: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum MacroKey {
+    Record,
+    Replay,
 }
 
 impl App {
@@ -42,6 +73,10 @@ impl App {
             toast: None,
             running: true,
             dls: Vec::new(),
+            macros: Macros::default(),
+            leader: false,
+            last_command: None,
+            last_data_command: None,
         }
     }
 
@@ -53,8 +88,27 @@ impl App {
         self.overlay = Some(Overlay::ThemeSelector(Default::default()));
     }
 
+    fn select_theme(&mut self, name: &str) {
+        if name.eq_ignore_ascii_case("auto") || LoadedTheme::exists(name) {
+            match config().select_theme(name) {
+                Ok(()) => config().store().unwrap_or_enqueue_error(),
+                Err(err) => self.show_error(err.to_string()),
+            }
+        } else {
+            self.overlay = Some(Overlay::ThemeSelector(ThemeSelector::with_query(name)));
+        }
+    }
+
     fn show_palette(&mut self) {
-        self.overlay = Some(Overlay::CommandPicker(CommandPalette::default()));
+        self.overlay = Some(Overlay::CommandPicker(CommandPalette::new(
+            self.tabs.titles(),
+        )));
+    }
+
+    fn prompt_command(&mut self, text: &str) {
+        self.overlay = Some(Overlay::CommandPicker(
+            CommandPalette::new(self.tabs.titles()).with_text(text),
+        ));
     }
 
     fn show_error(&mut self, message: impl Into<String>) {
@@ -65,6 +119,38 @@ impl App {
         self.toast = Some(Toast::new(message));
     }
 
+    fn remember_command(&mut self, line: &str) {
+        if command::modifies_data(line) {
+            self.last_data_command = Some(line.to_owned());
+        }
+        self.last_command = Some(line.to_owned());
+    }
+
+    fn repeat_command(&mut self, data_only: bool) {
+        let line = if data_only {
+            self.last_data_command.clone()
+        } else {
+            self.last_command.clone()
+        };
+        let Some(line) = line else {
+            self.show_toast("No command to repeat");
+            return;
+        };
+        // commands picked from the palette list are remembered by name
+        match command::parse(&line) {
+            Some(Ok(message)) => message.enqueue(),
+            Some(Err(err)) => self.show_error(err.to_string()),
+            None => match line.parse::<Command>() {
+                Ok(command) => command.execute(),
+                Err(_) => self.show_toast("No command to repeat"),
+            },
+        }
+    }
+
+    fn show_help(&mut self) {
+        self.overlay = Some(Overlay::Help(Help::new()));
+    }
+
     fn show_importer(&mut self) {
         self.overlay = Some(Overlay::Import(Importer::default()))
     }
@@ -87,11 +173,62 @@ impl App {
     }
 
     fn reload_app_config(&mut self) {
-        if let Err(err) = config().reload() {
+        if let Err(err) = config().reload().and_then(|_| keybindings().reload()) {
             self.show_error(err.to_string());
         }
     }
 
+    /// Handles `messages` in turn, along with the messages each sends before the
+    /// next one. Stops at an error or a popup left open, as the rest of the
+    /// macro would go to it.
+    fn replay(&mut self, messages: Vec<Message>) {
+        self.macros.replaying = true;
+        for message in messages {
+            self.update(&message, FocusState::Focused);
+            while let Some(message) = Message::dequeue() {
+                self.update(&message, FocusState::Focused);
+            }
+            if self.overlay.is_some() || !self.tabs.is_browsing() {
+                break;
+            }
+        }
+        self.macros.replaying = false;
+    }
+
+    /// Handles the register key after `q` or `@`; `@@` replays the last replayed
+    /// macro.
+    fn handle_macro_key(&mut self, key: MacroKey, event: KeyEvent) {
+        let register = match (key, event.code) {
+            (MacroKey::Replay, KeyCode::Char('@')) => self.macros.last,
+            (_, KeyCode::Char(register)) if register.is_ascii_lowercase() => Some(register),
+            _ => None,
+        };
+        match (key, register) {
+            (MacroKey::Record, Some(register)) => {
+                self.macros.recording = Some((register, Vec::new()));
+                self.show_toast(format!("Recording macro @{register}"));
+            }
+            (MacroKey::Replay, Some(register)) => match self.macros.registers.get(&register) {
+                Some(messages) => {
+                    self.macros.last = Some(register);
+                    self.replay(messages.clone());
+                }
+                None => self.show_toast(format!("Macro @{register} is empty")),
+            },
+            (_, None) => (),
+        }
+    }
+
+    fn stop_recording(&mut self) {
+        if let Some((register, messages)) = self.macros.recording.take() {
+            self.show_toast(format!(
+                "Macro @{register} recorded ({} actions)",
+                messages.len()
+            ));
+            self.macros.registers.insert(register, messages);
+        }
+    }
+
     fn dismiss_overlay(&mut self) {
         self.overlay = None;
     }
@@ -144,12 +281,70 @@ impl Component for App {
             dl.render(*area, buf, FocusState::NotFocused);
         }
 
+        if self.leader {
+            KeyHints::leader().render(area, buf, FocusState::NotFocused);
+        }
+
         if let Some(toast) = self.toast.as_mut() {
             toast.render(area, buf, FocusState::NotFocused);
         }
     }
 
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if self.overlay.is_some() || self.schema.is_some() {
+            false
+        } else {
+            self.tabs.handle_mouse(event)
+        }
+    }
+
     fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        let context = match (self.overlay.as_ref(), self.schema.as_ref()) {
+            (Some(_), _) => None,
+            (None, Some(_)) => Some(Context::Schema),
+            (None, None) => self.tabs.is_browsing().then_some(Context::Table),
+        };
+        if let Some(key) = self.macros.pending.take() {
+            self.handle_macro_key(key, event);
+            return true;
+        }
+        if std::mem::take(&mut self.leader) {
+            match keybindings().get(Context::Leader, &event) {
+                Some(action) => action.run(),
+                None if event.code == KeyCode::Esc => (),
+                None => self.show_toast("No leader mapping for this key"),
+            }
+            return true;
+        }
+        if context == Some(Context::Table) && keybindings().is_leader(&event) {
+            self.leader = true;
+            return true;
+        }
+        if context == Some(Context::Table) {
+            match (event.code, event.modifiers) {
+                (KeyCode::Char('q'), KeyModifiers::NONE) if self.macros.recording.is_some() => {
+                    self.stop_recording();
+                    return true;
+                }
+                (KeyCode::Char('q'), KeyModifiers::NONE) => {
+                    self.macros.pending = Some(MacroKey::Record);
+                    return true;
+                }
+                (KeyCode::Char('.'), _) => {
+                    self.repeat_command(true);
+                    return true;
+                }
+                (KeyCode::Char('@'), _) => {
+                    self.macros.pending = Some(MacroKey::Replay);
+                    return true;
+                }
+                _ => (),
+            }
+        }
+        if let Some(action) = context.and_then(|context| keybindings().get(context, &event)) {
+            action.run();
+            return true;
+        }
         (if let Some(overlay) = self.overlay.as_mut() {
             overlay.responder().handle(event)
         } else if let Some(schema) = self.schema.as_mut() {
@@ -165,20 +360,35 @@ impl Component for App {
                 self.quit();
                 true
             }
+            KeyCode::F(1) if self.overlay.is_none() => {
+                self.show_help();
+                true
+            }
             _ => false,
         }
     }
 
     fn update(&mut self, action: &Message, _: FocusState) {
+        if let Some((_, messages)) = self.macros.recording.as_mut()
+            && !self.macros.replaying
+            && action.is_replayable()
+        {
+            messages.push(action.clone());
+        }
         match action {
             Message::Quit => self.quit(),
             Message::AppDismissOverlay => self.dismiss_overlay(),
             Message::AppShowError(message) => self.show_error(message),
             Message::AppShowToast(message) => self.show_toast(message),
             Message::AppShowCommandPicker => self.show_palette(),
+            Message::AppPromptCommand(text) => self.prompt_command(text),
+            Message::AppRememberCommand(line) => self.remember_command(line),
+            Message::AppRepeatCommand(data_only) => self.repeat_command(*data_only),
             Message::AppShowThemeSelector => self.show_theme_selector(),
+            Message::AppSelectTheme(name) => self.select_theme(name),
             Message::AppShowSchema => self.show_schema(),
             Message::AppShowImporter => self.show_importer(),
+            Message::AppShowHelp => self.show_help(),
             Message::AppDismissSchema => self.dismiss_schema(),
             Message::AppShowSqlQuery => self.show_sql_query_picker(),
             Message::AppReloadConfig => self.reload_app_config(),
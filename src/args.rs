@@ -1,12 +1,19 @@
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
 use std::num::NonZero;
 
 use crate::io::DataSource;
 
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(version, about, long_about = None)]
 pub struct Args {
-    #[arg(help = "Path(s) to the file(s) to be opened.", required = false)]
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[arg(
+        help = "Path(s) to the file(s) to be opened, or to a workspace (.toml) file.",
+        required = false
+    )]
     pub resources: Vec<DataSource>,
 
     #[arg(long, help = "Paths to be opened and concatenated vertically.",
@@ -17,16 +24,23 @@ pub struct Args {
     #[arg(
         short,
         long,
+        global = true,
         help = "Specifies the input format. By default, the format is selected based on the file extension",
         value_enum
     )]
     pub format: Option<Format>,
 
-    #[arg(long, help = "Sets the key for sqlite (if required)", value_enum)]
+    #[arg(
+        long,
+        global = true,
+        help = "Sets the key for sqlite (if required)",
+        value_enum
+    )]
     pub sqlite_key: Option<String>,
 
     #[arg(
         long,
+        global = true,
         help = "Specifies if the input does not contain a header row.",
         default_value_t = false
     )]
@@ -34,13 +48,15 @@ pub struct Args {
 
     #[arg(
         long,
-        help = "Ignores parsing errors while loading.",
+        global = true,
+        help = "Ignores parsing errors while loading. Malformed CSV rows are left out and listed, with their line numbers and errors, in a <name>_errors tab.",
         default_value_t = false
     )]
     pub ignore_errors: bool,
 
     #[arg(
         long,
+        global = true,
         help = "Specifies the method to infer the schema.",
         required = false,
         value_enum,
@@ -50,6 +66,7 @@ pub struct Args {
 
     #[arg(
         long,
+        global = true,
         help = "Performs additional processing to parse date and datetime columns",
         default_value_t = false
     )]
@@ -57,6 +74,7 @@ pub struct Args {
 
     #[arg(
         long,
+        global = true,
         help = "Character used as the field separator or delimiter while loading DSV files.",
         required = false,
         default_value_t = ','
@@ -65,6 +83,7 @@ pub struct Args {
 
     #[arg(
         long,
+        global = true,
         help = "Character used to quote fields while loading DSV files.",
         required = false,
         default_value_t = '"'
@@ -73,6 +92,7 @@ pub struct Args {
 
     #[arg(
         long,
+        global = true,
         help = "A comma-separated list of widths, which specifies the column widths for FWF files.",
         required = false,
         default_value_t = String::default(),
@@ -81,6 +101,7 @@ pub struct Args {
 
     #[arg(
         long,
+        global = true,
         help = "Specifies the separator length for FWF files.",
         required = false,
         default_value_t = 1_usize
@@ -89,6 +110,7 @@ pub struct Args {
 
     #[arg(
         long,
+        global = true,
         help = "Sets strict column width restrictions for FWF files.",
         required = false,
         default_value_t = false
@@ -97,6 +119,7 @@ pub struct Args {
 
     #[arg(
         long,
+        global = true,
         help = "Truncate ragged lines while reading the file.",
         required = false,
         default_value_t = false
@@ -105,6 +128,7 @@ pub struct Args {
 
     #[arg(
         long,
+        global = true,
         help = "Specifies the types to infer for text-based files.",
         required = false,
         default_value_t = TypeVec(vec![Type::Int, Type::Float]),
@@ -113,14 +137,90 @@ pub struct Args {
 
     #[arg(
         long,
+        global = true,
+        help = "Reads only the first N rows of each table, stopping early for CSV, Parquet, Arrow and JSON Lines files.",
+        required = false
+    )]
+    pub limit: Option<usize>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Keeps N rows of each table drawn at random, while reading CSV files and after loading other formats.",
+        conflicts_with = "limit",
+        required = false
+    )]
+    pub sample: Option<usize>,
+
+    #[arg(
+        long,
+        global = true,
         help = "Disables type inference",
         required = false,
         default_value_t = false
     )]
     pub no_type_inference: bool,
+
+    #[arg(
+        long,
+        env = "TABIEW_THEME",
+        help = "Theme to use: a built-in theme name, a theme file name in ~/.config/tabiew/themes, or a theme file path.",
+        required = false
+    )]
+    pub theme: Option<String>,
+
+    #[arg(
+        long,
+        help = "Keybindings file to use instead of ~/.config/tabiew/keybindings.toml.",
+        required = false
+    )]
+    pub keybindings: Option<std::path::PathBuf>,
+
+    #[arg(
+        long,
+        help = "Runs the SQL query on the loaded tables and writes its result without starting the TUI. Tables are named after their files, and `_` is the first one.",
+        required = false
+    )]
+    pub sql: Option<String>,
+
+    #[arg(
+        long,
+        help = "File the result of --sql is written to, in the format of its extension (csv, tsv, json, jsonl, parquet, arrow, avro, md). Without it, CSV is written to stdout.",
+        requires = "sql",
+        required = false
+    )]
+    pub output: Option<std::path::PathBuf>,
+
+    #[arg(
+        long,
+        help = "Prints the schema and summary statistics of the loaded tables as Markdown, without starting the TUI.",
+        conflicts_with = "sql",
+        default_value_t = false
+    )]
+    pub describe: bool,
 }
 
-#[derive(Debug, Clone, ValueEnum)]
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Converts a file to the format of the output extension, reading it with the same
+    /// options as when it is opened.
+    Convert {
+        #[arg(help = "File to convert, or - for stdin.")]
+        input: DataSource,
+        #[arg(
+            help = "File to write: csv, tsv, json, jsonl, parquet, arrow, avro, or md, by extension."
+        )]
+        output: std::path::PathBuf,
+    },
+    /// Prints the completions of the command line for a shell.
+    Completions {
+        #[arg(help = "Shell to complete for.", value_enum)]
+        shell: clap_complete::Shell,
+    },
+}
+
+#[derive(Debug, Clone, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Format {
     Dsv,
     Csv,
@@ -204,7 +304,8 @@ impl std::str::FromStr for Type {
     }
 }
 
-#[derive(Debug, Clone, Copy, ValueEnum)]
+#[derive(Debug, Clone, Copy, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum InferSchema {
     No,
     Fast,
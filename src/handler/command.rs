@@ -0,0 +1,1293 @@
+use anyhow::anyhow;
+
+use crate::{
+    AppResult,
+    handler::message::Message,
+    misc::transform::{
+        Aggregation, ColumnPattern, FillNull, JoinHow, Keep, Plot, Rank, Rolling, RowTarget,
+        SampleSize, Slice, TextPattern, WindowFunc,
+    },
+};
+
+/// Parses a palette input that carries arguments, e.g. `mutate total = price * qty`,
+/// or a bare keyword such as `dup`.
+///
+/// Returns `None` when the input is not a known command keyword followed by
+/// arguments, letting the palette fall back to its list of commands.
+pub fn parse(input: &str) -> Option<AppResult<Message>> {
+    // bare keywords the fuzzy list would resolve to another command, or miss
+    let bare = match input.trim() {
+        "dup" | "duplicate" => Some(Message::TabsDuplicate),
+        "tabonly" => Some(Message::TabsCloseOthers),
+        "tabclose-right" => Some(Message::TabsCloseRight),
+        _ => None,
+    };
+    if let Some(message) = bare {
+        return Some(Ok(message));
+    }
+    if let Some(args) = input.trim().strip_prefix("%s") {
+        let args = args.trim_start();
+        return (!args.is_empty()).then(|| parse_substitute(args));
+    }
+    let (keyword, args) = input.trim().split_once(char::is_whitespace)?;
+    let args = args.trim();
+    if args.is_empty() {
+        return None;
+    }
+    let parse: fn(&str) -> AppResult<Message> = match keyword {
+        "col" => |args| Ok(Message::PaneGoToColumn(unquote(args).to_owned())),
+        "goto" => parse_goto,
+        "mutate" => parse_mutate,
+        "rename" => parse_rename,
+        "drop" => parse_drop,
+        "hide" => |args| Ok(Message::PaneHideColumns(parse_column_patterns(args)?)),
+        "show" => |args| Ok(Message::PaneUnhideColumns(parse_column_patterns(args)?)),
+        "fill-null" => parse_fill_null,
+        "footer" => |args| {
+            Ok(Message::PaneSetFooter(match args {
+                "off" => None,
+                footer => Some(footer.parse()?),
+            }))
+        },
+        "width" => parse_width,
+        "dedup" => parse_dedup,
+        "drop-nulls" => parse_drop_nulls,
+        "explode" => parse_explode,
+        "unnest" => parse_unnest,
+        "dummies" => parse_dummies,
+        "freq" => parse_freq,
+        "bar" => parse_bar,
+        "plot" => parse_plot,
+        "sample" => parse_sample,
+        "head" => parse_head,
+        "tail" => parse_tail,
+        "slice" => parse_slice,
+        "agg" => parse_agg,
+        "join" => parse_join,
+        "concat" => parse_concat,
+        "diff" => parse_diff,
+        "rename-tab" => |args| Ok(Message::TabsRename(unquote(args).to_owned())),
+        "theme" => |args| Ok(Message::AppSelectTheme(unquote(args).to_owned())),
+        "split" => parse_split,
+        "parse-dates" => parse_parse_dates,
+        "tz" => parse_tz,
+        "replace" => parse_replace,
+        "cut" => parse_cut,
+        "rank" => parse_rank,
+        "pct-rank" => parse_pct_rank,
+        "cumsum" => |args| parse_cumulative(args, WindowFunc::CumSum),
+        "cummin" => |args| parse_cumulative(args, WindowFunc::CumMin),
+        "cummax" => |args| parse_cumulative(args, WindowFunc::CumMax),
+        "rolling" => parse_rolling,
+        _ => return None,
+    };
+    Some(parse(args))
+}
+
+/// Whether `input` is a command transforming the frame of the current tab in
+/// place, rather than moving around, laying out, or opening another tab.
+pub fn modifies_data(input: &str) -> bool {
+    let input = input.trim();
+    let keyword = input.split_whitespace().next().unwrap_or_default();
+    (input.starts_with("%s")
+        || matches!(
+            keyword,
+            "mutate"
+                | "rename"
+                | "drop"
+                | "fill-null"
+                | "dedup"
+                | "drop-nulls"
+                | "explode"
+                | "unnest"
+                | "dummies"
+                | "head"
+                | "tail"
+                | "slice"
+                | "split"
+                | "parse-dates"
+                | "tz"
+                | "replace"
+                | "cut"
+                | "rank"
+                | "pct-rank"
+                | "cumsum"
+                | "cummin"
+                | "cummax"
+                | "rolling"
+        ))
+        && parse(input).is_some_and(|result| result.is_ok())
+}
+
+fn parse_mutate(args: &str) -> AppResult<Message> {
+    let (name, expr) = args
+        .split_once('=')
+        .ok_or(anyhow!("Usage: mutate <column> = <expression>"))?;
+    let name = unquote(name.trim());
+    let expr = expr.trim();
+    if name.is_empty() || expr.is_empty() {
+        Err(anyhow!("Usage: mutate <column> = <expression>"))
+    } else {
+        Ok(Message::PaneMutate(name.to_owned(), expr.to_owned()))
+    }
+}
+
+fn parse_rename(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [old, new] => Ok(
+            if let Some(pattern) = old
+                .strip_prefix('/')
+                .and_then(|old| old.strip_suffix('/'))
+                .filter(|pattern| !pattern.is_empty())
+            {
+                Message::PaneRenameColumns(pattern.to_owned(), new.to_owned())
+            } else {
+                Message::PaneRenameColumn(old.to_owned(), new.to_owned())
+            },
+        ),
+        _ => Err(anyhow!(
+            "Usage: rename <old> <new> or rename /<pattern>/ <replacement>"
+        )),
+    }
+}
+
+fn parse_drop(args: &str) -> AppResult<Message> {
+    Ok(Message::PaneDropColumns(
+        split_args(args)?
+            .iter()
+            .map(|arg| ColumnPattern::from(arg.as_str()))
+            .collect(),
+    ))
+}
+
+fn parse_column_patterns(args: &str) -> AppResult<Vec<ColumnPattern>> {
+    Ok(split_args(args)?
+        .iter()
+        .map(|arg| ColumnPattern::from(arg.as_str()))
+        .collect())
+}
+
+fn parse_width(args: &str) -> AppResult<Message> {
+    let usage = "Usage: width <column> <width|auto>";
+    match split_args(args)?.as_slice() {
+        [name, width] if width == "auto" => Ok(Message::PaneSetColumnWidth(name.to_owned(), None)),
+        [name, width] => Ok(Message::PaneSetColumnWidth(
+            name.to_owned(),
+            Some(
+                width
+                    .parse()
+                    .ok()
+                    .filter(|width| *width > 0)
+                    .ok_or(anyhow!("'{width}' is not a valid column width"))?,
+            ),
+        )),
+        _ => Err(anyhow!(usage)),
+    }
+}
+
+fn parse_fill_null(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [column, fill] => Ok(Message::PaneFillNull(
+            column.to_owned(),
+            FillNull::from(fill.as_str()),
+        )),
+        _ => Err(anyhow!(
+            "Usage: fill-null <column> <value|forward|backward|mean|zero>"
+        )),
+    }
+}
+
+fn parse_dedup(args: &str) -> AppResult<Message> {
+    let mut patterns = Vec::new();
+    let mut keep = Keep::default();
+    for arg in split_args(args)? {
+        match arg.split_once('=') {
+            Some(("keep", "first")) => keep = Keep::First,
+            Some(("keep", "last")) => keep = Keep::Last,
+            Some(("keep", other)) => {
+                return Err(anyhow!("Invalid keep '{other}', use first or last"));
+            }
+            _ => patterns.push(ColumnPattern::from(arg.as_str())),
+        }
+    }
+    Ok(Message::PaneDedup(patterns, keep))
+}
+
+fn parse_drop_nulls(args: &str) -> AppResult<Message> {
+    Ok(Message::PaneDropNulls(
+        split_args(args)?
+            .iter()
+            .map(|arg| ColumnPattern::from(arg.as_str()))
+            .collect(),
+    ))
+}
+
+fn parse_explode(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name] => Ok(Message::PaneExplode(name.to_owned())),
+        _ => Err(anyhow!("Usage: explode <column>")),
+    }
+}
+
+fn parse_unnest(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name] => Ok(Message::PaneUnnest(name.to_owned())),
+        _ => Err(anyhow!("Usage: unnest <column>")),
+    }
+}
+
+fn parse_dummies(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name] => Ok(Message::PaneDummies(name.to_owned())),
+        _ => Err(anyhow!("Usage: dummies <column>")),
+    }
+}
+
+fn parse_bar(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name] => Ok(Message::PaneShowBarPlot(name.to_owned())),
+        _ => Err(anyhow!("Usage: bar <column>")),
+    }
+}
+
+/// Parses `scatter x=<column> y=<column> [color=<column>]`, `hist <column>
+/// [buckets=<n>]`, or `bar <column>`, optionally followed by `where <condition>`.
+fn parse_plot(args: &str) -> AppResult<Message> {
+    let usage = "Usage: plot scatter x=<column> y=<column> [color=<column>] | plot hist <column> \
+                 [buckets=<n>] | plot bar <column>, optionally followed by where <condition>";
+    let (spec, condition) = match args.to_ascii_lowercase().find(" where ") {
+        Some(idx) => {
+            let condition = args[idx + " where ".len()..].trim();
+            if condition.is_empty() {
+                return Err(anyhow!("Missing condition after 'where'"));
+            }
+            (&args[..idx], Some(condition.to_owned()))
+        }
+        None => (args, None),
+    };
+    let args = split_args(spec)?;
+    let (kind, rest) = args.split_first().ok_or(anyhow!(usage))?;
+    let plot = match kind.as_str() {
+        "scatter" => {
+            let (mut x, mut y, mut color) = (None, None, None);
+            for arg in rest {
+                match arg.split_once('=') {
+                    Some(("x", name)) => x = Some(name.to_owned()),
+                    Some(("y", name)) => y = Some(name.to_owned()),
+                    Some(("color", name)) => color = Some(name.to_owned()),
+                    _ => return Err(anyhow!("Invalid scatter argument '{arg}'; {usage}")),
+                }
+            }
+            Plot::Scatter {
+                x: x.ok_or(anyhow!("Missing x=<column>; {usage}"))?,
+                y: y.ok_or(anyhow!("Missing y=<column>; {usage}"))?,
+                color,
+            }
+        }
+        "hist" | "histogram" => match rest {
+            [column] => Plot::Histogram {
+                column: column.to_owned(),
+                buckets: None,
+            },
+            [column, buckets] => Plot::Histogram {
+                column: column.to_owned(),
+                buckets: Some(
+                    buckets
+                        .strip_prefix("buckets=")
+                        .and_then(|buckets| buckets.parse().ok())
+                        .filter(|buckets| *buckets > 0)
+                        .ok_or(anyhow!("'{buckets}' is not a valid buckets=<n>"))?,
+                ),
+            },
+            _ => return Err(anyhow!(usage)),
+        },
+        "bar" => match rest {
+            [column] => Plot::Bar(column.to_owned()),
+            _ => return Err(anyhow!(usage)),
+        },
+        _ => return Err(anyhow!(usage)),
+    };
+    Ok(Message::PanePlot(plot, condition))
+}
+
+fn parse_freq(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name] => Ok(Message::PaneFrequency(name.to_owned())),
+        _ => Err(anyhow!("Usage: freq <column>")),
+    }
+}
+
+/// Parses `<row>` or `<column>:<row>`, where the row is a line number, a
+/// percentage (`50%`), or an offset from the selected row (`+100`, `-100`).
+fn parse_goto(args: &str) -> AppResult<Message> {
+    let usage = "Usage: goto <line|percent%|+offset|-offset> or goto <column>:<row>";
+    let (column, row) = match args.rsplit_once(':') {
+        Some((column, row)) => (Some(unquote(column.trim())), row.trim()),
+        None => (None, args),
+    };
+    let target = if let Some(percent) = row.strip_suffix('%') {
+        RowTarget::Percent(
+            percent
+                .parse()
+                .ok()
+                .filter(|percent| (0.0..=100.0).contains(percent))
+                .ok_or(anyhow!("'{row}' is not a valid percentage"))?,
+        )
+    } else if row.starts_with(['+', '-']) {
+        RowTarget::Offset(
+            row.parse()
+                .map_err(|_| anyhow!("'{row}' is not a valid row offset"))?,
+        )
+    } else if row.is_empty() {
+        return Err(anyhow!(usage));
+    } else {
+        RowTarget::Line(
+            row.parse()
+                .map_err(|_| anyhow!("'{row}' is not a valid line number"))?,
+        )
+    };
+    match column {
+        Some("") => Err(anyhow!(usage)),
+        column => Ok(Message::PaneGoTo(column.map(ToOwned::to_owned), target)),
+    }
+}
+
+fn parse_sample(args: &str) -> AppResult<Message> {
+    let mut size = None;
+    let mut seed = None;
+    for arg in split_args(args)? {
+        if let Some(value) = arg.strip_prefix("seed=") {
+            seed = Some(
+                value
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid seed '{value}'"))?,
+            );
+        } else if size.is_none() {
+            size = Some(arg.parse::<SampleSize>()?);
+        } else {
+            return Err(anyhow!("Usage: sample <n|percent%> [seed=<n>]"));
+        }
+    }
+    Ok(Message::PaneSample(
+        size.ok_or(anyhow!("Usage: sample <n|percent%> [seed=<n>]"))?,
+        seed,
+    ))
+}
+
+fn parse_head(args: &str) -> AppResult<Message> {
+    Ok(Message::PaneSlice(Slice::Head(parse_count(args)?)))
+}
+
+fn parse_tail(args: &str) -> AppResult<Message> {
+    Ok(Message::PaneSlice(Slice::Tail(parse_count(args)?)))
+}
+
+fn parse_slice(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [start, len] => Ok(Message::PaneSlice(Slice::Range(
+            parse_count(start)?,
+            parse_count(len)?,
+        ))),
+        _ => Err(anyhow!("Usage: slice <start> <length>")),
+    }
+}
+
+fn parse_agg(args: &str) -> AppResult<Message> {
+    let mut by = Vec::new();
+    let mut aggs = Vec::new();
+    for arg in split_args(args)? {
+        if let Some(columns) = arg.strip_prefix("by=") {
+            by.extend(
+                columns
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|column| !column.is_empty())
+                    .map(ToOwned::to_owned),
+            );
+        } else {
+            aggs.push(arg.parse::<Aggregation>()?);
+        }
+    }
+    if aggs.is_empty() {
+        Err(anyhow!(
+            "Usage: agg [by=<column,...>] <func(column)>... e.g. agg by=region sum(sales) count()"
+        ))
+    } else {
+        Ok(Message::PaneAggregate(by, aggs))
+    }
+}
+
+fn parse_join(args: &str) -> AppResult<Message> {
+    let usage = "Usage: join <tab> on=<key[:other_key],...> [how=inner|left|right|full]";
+    let mut args = split_args(args)?.into_iter();
+    let tab = args.next().ok_or(anyhow!(usage))?;
+    let mut keys = Vec::new();
+    let mut how = JoinHow::default();
+    for arg in args {
+        match arg.split_once('=') {
+            Some(("on", on)) => keys.extend(on.split(',').filter(|k| !k.is_empty()).map(|key| {
+                key.split_once(':')
+                    .map(|(l, r)| (l.to_owned(), r.to_owned()))
+                    .unwrap_or((key.to_owned(), key.to_owned()))
+            })),
+            Some(("how", value)) => how = value.parse()?,
+            _ => return Err(anyhow!(usage)),
+        }
+    }
+    if keys.is_empty() {
+        Err(anyhow!(usage))
+    } else {
+        Ok(Message::TabsJoin(tab, keys, how))
+    }
+}
+
+fn parse_split(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name, delimiter] => Ok(Message::PaneSplit(
+            name.to_owned(),
+            TextPattern::from(delimiter.as_str()),
+        )),
+        _ => Err(anyhow!(
+            "Usage: split <column> <delimiter> or split <column> /<pattern>/"
+        )),
+    }
+}
+
+fn parse_parse_dates(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name, fmt] => Ok(Message::PaneParseDates(name.to_owned(), fmt.to_owned())),
+        _ => Err(anyhow!(
+            "Usage: parse-dates <column> <format> e.g. parse-dates date \"%d/%m/%Y %H:%M\""
+        )),
+    }
+}
+
+fn parse_tz(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name, from, to] => Ok(Message::PaneConvertTimeZone(
+            name.to_owned(),
+            from.to_owned(),
+            to.to_owned(),
+        )),
+        _ => Err(anyhow!(
+            "Usage: tz <column> <from_tz> <to_tz> e.g. tz timestamp UTC Europe/Berlin"
+        )),
+    }
+}
+
+fn parse_replace(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name, pattern, replacement] => Ok(Message::PaneReplace(
+            name.to_owned(),
+            TextPattern::from(pattern.as_str()),
+            replacement.to_owned(),
+        )),
+        _ => Err(anyhow!(
+            "Usage: replace <column> <text> <replacement> or replace <column> /<pattern>/ <replacement>"
+        )),
+    }
+}
+
+/// Parses the `/<pattern>/<replacement>/ [columns]` part of a vim-style `%s`
+/// substitution. Any punctuation may stand in for `/`, in which case `\/` is
+/// written with that character instead.
+fn parse_substitute(args: &str) -> AppResult<Message> {
+    let usage = "Usage: %s/<pattern>/<replacement>/ [columns]";
+    let delimiter = args
+        .chars()
+        .next()
+        .filter(char::is_ascii_punctuation)
+        .filter(|c| *c != '\\')
+        .ok_or(anyhow!(usage))?;
+    let body = &args[delimiter.len_utf8()..];
+    let mut parts = vec![String::new()];
+    let mut rest = "";
+    let mut chars = body.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        let last = parts.len() == 2;
+        let part = parts.last_mut().expect("parts is never empty");
+        match c {
+            '\\' => match chars.next() {
+                Some((_, next)) if next == delimiter => part.push(next),
+                Some((_, next)) => part.extend(['\\', next]),
+                None => part.push('\\'),
+            },
+            c if c == delimiter && last => {
+                rest = &body[idx + c.len_utf8()..];
+                break;
+            }
+            c if c == delimiter => parts.push(String::new()),
+            c => part.push(c),
+        }
+    }
+    match parts.as_slice() {
+        [pattern, replacement] if !pattern.is_empty() => Ok(Message::PaneSubstitute(
+            pattern.to_owned(),
+            replacement.to_owned(),
+            split_args(rest)?
+                .iter()
+                .map(|arg| ColumnPattern::from(arg.as_str()))
+                .collect(),
+        )),
+        _ => Err(anyhow!(usage)),
+    }
+}
+
+fn parse_cut(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name, bins] => Ok(Message::PaneCut(name.to_owned(), bins.parse()?)),
+        _ => Err(anyhow!(
+            "Usage: cut <column> <count> or cut <column> <edge,edge,...>"
+        )),
+    }
+}
+
+fn parse_rank(args: &str) -> AppResult<Message> {
+    let usage = "Usage: rank <column> [average|min|max|dense|ordinal] [desc]";
+    let mut args = split_args(args)?.into_iter();
+    let name = args.next().ok_or(anyhow!(usage))?;
+    let mut rank = Rank::default();
+    let mut descending = false;
+    for arg in args {
+        match arg.as_str() {
+            "desc" => descending = true,
+            "asc" => descending = false,
+            method => rank = method.parse()?,
+        }
+    }
+    Ok(Message::PaneRank(name, rank, descending))
+}
+
+fn parse_pct_rank(args: &str) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name] => Ok(Message::PaneRank(name.to_owned(), Rank::Percent, false)),
+        [name, order] if order == "desc" => {
+            Ok(Message::PaneRank(name.to_owned(), Rank::Percent, true))
+        }
+        _ => Err(anyhow!("Usage: pct-rank <column> [desc]")),
+    }
+}
+
+fn parse_cumulative(args: &str, func: WindowFunc) -> AppResult<Message> {
+    match split_args(args)?.as_slice() {
+        [name] => Ok(Message::PaneWindow(name.to_owned(), func)),
+        _ => Err(anyhow!("Usage: cumsum|cummin|cummax <column>")),
+    }
+}
+
+fn parse_rolling(args: &str) -> AppResult<Message> {
+    let usage = "Usage: rolling <mean|sum|min|max|std> <column> window=<size>";
+    match split_args(args)?.as_slice() {
+        [func, name, window] => {
+            let size = window
+                .strip_prefix("window=")
+                .and_then(|size| size.parse::<usize>().ok())
+                .filter(|size| *size > 0)
+                .ok_or(anyhow!(usage))?;
+            Ok(Message::PaneWindow(
+                name.to_owned(),
+                WindowFunc::Rolling(func.parse::<Rolling>()?, size),
+            ))
+        }
+        _ => Err(anyhow!(usage)),
+    }
+}
+
+fn parse_concat(args: &str) -> AppResult<Message> {
+    Ok(Message::TabsConcat(split_args(args)?))
+}
+
+fn parse_diff(args: &str) -> AppResult<Message> {
+    let mut tabs = Vec::new();
+    let mut keys = Vec::new();
+    for arg in split_args(args)? {
+        match arg.strip_prefix("key=") {
+            Some(key) => keys.extend(
+                key.split(',')
+                    .map(str::trim)
+                    .filter(|key| !key.is_empty())
+                    .map(ToOwned::to_owned),
+            ),
+            None => tabs.push(arg),
+        }
+    }
+    let mut tabs = tabs.into_iter();
+    match (tabs.next(), tabs.next(), tabs.next()) {
+        (Some(right), None, None) => Ok(Message::TabsDiff(None, right, keys)),
+        (Some(left), Some(right), None) => Ok(Message::TabsDiff(Some(left), right, keys)),
+        _ => Err(anyhow!(
+            "Usage: diff [<tab>] <other tab> [key=<column,...>]"
+        )),
+    }
+}
+
+/// Completes the tab name typed as an argument of commands referencing other
+/// tabs, e.g. `join sal` becomes `join sales `.
+pub fn complete_tab_name(input: &str, tab_names: &[String]) -> Option<String> {
+    let (keyword, args) = input.split_once(char::is_whitespace)?;
+    let (head, partial) = args.rsplit_once(char::is_whitespace).unwrap_or(("", args));
+    let position = split_args(head).ok()?.len();
+    if !matches!(
+        (keyword, position),
+        ("join", 0) | ("concat", _) | ("diff", 0 | 1)
+    ) {
+        return None;
+    }
+    let partial = partial.trim_start_matches(['"', '\'']).to_lowercase();
+    let name = tab_names
+        .iter()
+        .find(|name| name.to_lowercase().starts_with(&partial))
+        .or_else(|| {
+            tab_names
+                .iter()
+                .find(|name| name.to_lowercase().contains(&partial))
+        })?;
+    let name = if name.contains(char::is_whitespace) {
+        format!("\"{name}\"")
+    } else {
+        name.to_owned()
+    };
+    Some(if head.trim().is_empty() {
+        format!("{keyword} {name} ")
+    } else {
+        format!("{keyword} {} {name} ", head.trim())
+    })
+}
+
+fn parse_count(arg: &str) -> AppResult<usize> {
+    arg.parse()
+        .map_err(|_| anyhow!("'{arg}' is not a valid row count"))
+}
+
+/// Splits arguments on whitespace, keeping quoted sections (`"..."` or `'...'`)
+/// together. Backslashes are kept as-is so regular expressions survive.
+fn split_args(args: &str) -> AppResult<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote = None;
+    for c in args.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.get_or_insert_default().push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                current.get_or_insert_default();
+            }
+            None if c.is_whitespace() => tokens.extend(current.take()),
+            None => current.get_or_insert_default().push(c),
+        }
+    }
+    if quote.is_some() {
+        Err(anyhow!("Unterminated quote in '{args}'"))
+    } else {
+        tokens.extend(current);
+        Ok(tokens)
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    ['"', '\'', '`']
+        .into_iter()
+        .find_map(|q| value.strip_prefix(q).and_then(|v| v.strip_suffix(q)))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::transform::{Bins, Footer};
+
+    #[test]
+    fn test_modifies_data() {
+        assert!(modifies_data("mutate total = price * qty"));
+        assert!(modifies_data("%s/a/b/"));
+        assert!(!modifies_data("goto 10"));
+        assert!(!modifies_data("width price 12"));
+        assert!(!modifies_data("theme nord"));
+        assert!(!modifies_data("mutate"));
+        assert!(modifies_data("head 10"));
+        assert!(!modifies_data("freq city"));
+        assert!(!modifies_data("sample 10"));
+        assert!(!modifies_data("agg sum(price) by=city"));
+    }
+
+    #[test]
+    fn test_parse_unknown_or_bare() {
+        assert!(parse("").is_none());
+        assert!(parse("mutate").is_none());
+        assert!(parse("mutate   ").is_none());
+        assert!(parse("unknown a = b").is_none());
+    }
+
+    #[test]
+    fn test_parse_duplicate() {
+        assert!(matches!(parse("dup"), Some(Ok(Message::TabsDuplicate))));
+        assert!(matches!(
+            parse(" duplicate "),
+            Some(Ok(Message::TabsDuplicate))
+        ));
+        assert!(matches!(
+            parse("dedup a").unwrap().unwrap(),
+            Message::PaneDedup(..)
+        ));
+    }
+
+    #[test]
+    fn test_parse_tab_close() {
+        assert!(matches!(
+            parse("tabonly"),
+            Some(Ok(Message::TabsCloseOthers))
+        ));
+        assert!(matches!(
+            parse("tabclose-right"),
+            Some(Ok(Message::TabsCloseRight))
+        ));
+        assert!(parse("tabclose").is_none());
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        let msg = parse("theme 'Tokyo Night'").unwrap().unwrap();
+        assert!(matches!(msg, Message::AppSelectTheme(name) if name == "Tokyo Night"));
+    }
+
+    #[test]
+    fn test_parse_col() {
+        let msg = parse("col  unit price ").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneGoToColumn(name) if name == "unit price"));
+    }
+
+    #[test]
+    fn test_parse_mutate() {
+        let msg = parse("mutate total = price * qty").unwrap().unwrap();
+        assert!(
+            matches!(msg, Message::PaneMutate(name, expr) if name == "total" && expr == "price * qty")
+        );
+    }
+
+    #[test]
+    fn test_parse_mutate_quoted_name() {
+        let msg = parse("mutate \"unit price\" = price / qty")
+            .unwrap()
+            .unwrap();
+        assert!(matches!(msg, Message::PaneMutate(name, _) if name == "unit price"));
+    }
+
+    #[test]
+    fn test_parse_substitute() {
+        let msg = parse("%s/NYC/New York/ city").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneSubstitute(pat, rep, cols)
+                if pat == "NYC" && rep == "New York" && cols == [ColumnPattern::from("city")]
+        ));
+
+        let msg = parse(r"%s#(\d+)/(\d+)#$2\#$1").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneSubstitute(pat, rep, cols)
+                if pat == r"(\d+)/(\d+)" && rep == "$2#$1" && cols.is_empty()
+        ));
+
+        let msg = parse(r"%s /a\/b/c/").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneSubstitute(pat, rep, _) if pat == "a/b" && rep == "c"));
+
+        assert!(parse("%s").is_none());
+        assert!(parse("%s/a").unwrap().is_err());
+        assert!(parse("%s//b/").unwrap().is_err());
+        assert!(parse("%sabc").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(split_args("a  b").unwrap(), vec!["a", "b"]);
+        assert_eq!(
+            split_args("\"first name\" 'x y' z").unwrap(),
+            vec!["first name", "x y", "z"]
+        );
+        assert_eq!(split_args("'' a").unwrap(), vec!["", "a"]);
+        assert_eq!(split_args(r"/^c(\d+)$/").unwrap(), vec![r"/^c(\d+)$/"]);
+        assert!(split_args("\"open").is_err());
+    }
+
+    #[test]
+    fn test_parse_rename() {
+        let msg = parse("rename old new").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneRenameColumn(old, new) if old == "old" && new == "new"));
+
+        let msg = parse(r"rename /^col_(\d+)$/ c$1").unwrap().unwrap();
+        assert!(
+            matches!(msg, Message::PaneRenameColumns(pat, rep) if pat == r"^col_(\d+)$" && rep == "c$1")
+        );
+
+        assert!(parse("rename only").unwrap().is_err());
+        assert!(parse("rename a b c").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_drop() {
+        let msg = parse("drop a price_* /^tmp_\\d+$/").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneDropColumns(patterns) if patterns == vec![
+                ColumnPattern::Name("a".to_owned()),
+                ColumnPattern::Glob("price_*".to_owned()),
+                ColumnPattern::Regex("^tmp_\\d+$".to_owned()),
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_parse_hide_show() {
+        let msg = parse("hide notes tmp_*").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneHideColumns(patterns) if patterns == vec![
+                ColumnPattern::Name("notes".to_owned()),
+                ColumnPattern::Glob("tmp_*".to_owned()),
+            ]
+        ));
+
+        let msg = parse("show 'unit price'").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneUnhideColumns(patterns)
+                if patterns == vec![ColumnPattern::Name("unit price".to_owned())]
+        ));
+
+        assert!(parse("hide").is_none());
+    }
+
+    #[test]
+    fn test_parse_footer() {
+        let msg = parse("footer mean").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneSetFooter(Some(Footer::Mean))));
+
+        let msg = parse("footer off").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneSetFooter(None)));
+
+        assert!(parse("footer median").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_width() {
+        let msg = parse("width 'unit price' 12").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneSetColumnWidth(name, Some(12)) if name == "unit price"));
+
+        let msg = parse("width notes auto").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneSetColumnWidth(name, None) if name == "notes"));
+
+        assert!(parse("width notes 0").unwrap().is_err());
+        assert!(parse("width notes").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_fill_null() {
+        let msg = parse("fill-null price mean").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneFillNull(col, FillNull::Mean) if col == "price"));
+
+        let msg = parse("fill-null city 'New York'").unwrap().unwrap();
+        assert!(
+            matches!(msg, Message::PaneFillNull(_, FillNull::Value(value)) if value == "New York")
+        );
+
+        assert!(parse("fill-null price").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_dedup() {
+        let msg = parse("dedup id keep=last").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneDedup(patterns, Keep::Last)
+                if patterns == vec![ColumnPattern::Name("id".to_owned())]
+        ));
+
+        let msg = parse("dedup keep=first").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneDedup(patterns, Keep::First) if patterns.is_empty()));
+
+        assert!(parse("dedup keep=middle").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_sample() {
+        let msg = parse("sample 100").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneSample(SampleSize::Rows(100), None)
+        ));
+
+        let msg = parse("sample 5% seed=42").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneSample(SampleSize::Percent(5.0), Some(42))
+        ));
+
+        assert!(parse("sample 120%").unwrap().is_err());
+        assert!(parse("sample seed=1").unwrap().is_err());
+        assert!(parse("sample ten").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_slices() {
+        let msg = parse("head 10").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneSlice(Slice::Head(10))));
+
+        let msg = parse("tail 5").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneSlice(Slice::Tail(5))));
+
+        let msg = parse("slice 100 20").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneSlice(Slice::Range(100, 20))));
+
+        assert!(parse("head -1").unwrap().is_err());
+        assert!(parse("slice 100").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_agg() {
+        let msg = parse("agg by=region,city sum(sales) mean(price) count()")
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneAggregate(by, aggs)
+                if by == vec!["region", "city"]
+                    && aggs.iter().map(ToString::to_string).collect::<Vec<_>>()
+                        == vec!["sum(sales)", "mean(price)", "count()"]
+        ));
+
+        let msg = parse("agg MAX(price)").unwrap().unwrap();
+        assert!(
+            matches!(msg, Message::PaneAggregate(by, aggs) if by.is_empty() && aggs.len() == 1)
+        );
+
+        assert!(parse("agg by=region").unwrap().is_err());
+        assert!(parse("agg sum()").unwrap().is_err());
+        assert!(parse("agg total(sales)").unwrap().is_err());
+        assert!(parse("agg sales").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_join() {
+        let msg = parse("join users on=user_id:id how=left").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::TabsJoin(tab, keys, JoinHow::Left)
+                if tab == "users" && keys == vec![("user_id".to_owned(), "id".to_owned())]
+        ));
+
+        let msg = parse("join \"order items\" on=a,b").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::TabsJoin(tab, keys, JoinHow::Inner) if tab == "order items" && keys.len() == 2
+        ));
+
+        assert!(parse("join users").unwrap().is_err());
+        assert!(parse("join users on=id how=sideways").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_complete_tab_name() {
+        let tabs = vec!["sales".to_owned(), "order items".to_owned()];
+        assert_eq!(
+            complete_tab_name("join sa", &tabs),
+            Some("join sales ".to_owned())
+        );
+        assert_eq!(
+            complete_tab_name("join items", &tabs),
+            Some("join \"order items\" ".to_owned())
+        );
+        assert_eq!(
+            complete_tab_name("join ", &tabs),
+            Some("join sales ".to_owned())
+        );
+        assert_eq!(complete_tab_name("join sales on=", &tabs), None);
+        assert_eq!(complete_tab_name("join xyz", &tabs), None);
+        assert_eq!(complete_tab_name("drop sa", &tabs), None);
+    }
+
+    #[test]
+    fn test_parse_concat() {
+        let msg = parse("concat jan feb \"q1 total\"").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::TabsConcat(tabs) if tabs == vec!["jan", "feb", "q1 total"]
+        ));
+        let tabs = vec!["jan".to_owned(), "feb".to_owned()];
+        assert_eq!(
+            complete_tab_name("concat jan f", &tabs),
+            Some("concat jan feb ".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_parse_diff() {
+        let msg = parse("diff jan feb key=id,region").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::TabsDiff(Some(left), right, keys)
+                if left == "jan" && right == "feb" && keys == vec!["id", "region"]
+        ));
+        let msg = parse("diff \"feb export\"").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::TabsDiff(None, right, keys) if right == "feb export" && keys.is_empty()
+        ));
+        assert!(parse("diff a b c").unwrap().is_err());
+        assert!(parse("diff key=id").unwrap().is_err());
+        let tabs = vec!["jan".to_owned(), "feb".to_owned()];
+        assert_eq!(
+            complete_tab_name("diff jan f", &tabs),
+            Some("diff jan feb ".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_parse_split() {
+        let msg = parse("split name ' '").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneSplit(name, TextPattern::Literal(delimiter))
+                if name == "name" && delimiter == " "
+        ));
+
+        let msg = parse("split path /[\\/\\\\]/").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneSplit(name, TextPattern::Regex(pattern))
+                if name == "path" && pattern == "[\\/\\\\]"
+        ));
+
+        assert!(parse("split name").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        let msg = parse("parse-dates \"order date\" \"%d/%m/%Y %H:%M\"")
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneParseDates(name, fmt) if name == "order date" && fmt == "%d/%m/%Y %H:%M"
+        ));
+        assert!(parse("parse-dates date").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_tz() {
+        let msg = parse("tz ts UTC America/New_York").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneConvertTimeZone(name, from, to)
+                if name == "ts" && from == "UTC" && to == "America/New_York"
+        ));
+        assert!(parse("tz ts UTC").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_replace() {
+        let msg = parse("replace city NYC \"New York\"").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneReplace(name, TextPattern::Literal(text), replacement)
+                if name == "city" && text == "NYC" && replacement == "New York"
+        ));
+
+        let msg = parse("replace phone /(\\d{3})-(\\d{4})/ '$1$2'")
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneReplace(_, TextPattern::Regex(pattern), replacement)
+                if pattern == "(\\d{3})-(\\d{4})" && replacement == "$1$2"
+        ));
+
+        let msg = parse("replace notes 'n/a' ''").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneReplace(_, TextPattern::Literal(text), replacement)
+                if text == "n/a" && replacement.is_empty()
+        ));
+
+        assert!(parse("replace city NYC").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_cut() {
+        let msg = parse("cut age 5").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneCut(name, Bins::Count(5)) if name == "age"));
+
+        let msg = parse("cut age 0,18,65,120").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneCut(_, Bins::Edges(edges)) if edges == vec![0.0, 18.0, 65.0, 120.0]
+        ));
+
+        assert!(parse("cut age 0").unwrap().is_err());
+        assert!(parse("cut age 10,5").unwrap().is_err());
+        assert!(parse("cut age 1,x").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_rank() {
+        let msg = parse("rank score").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneRank(name, Rank::Average, false) if name == "score"
+        ));
+        let msg = parse("rank score dense desc").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneRank(_, Rank::Dense, true)));
+        let msg = parse("pct-rank score").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneRank(_, Rank::Percent, false)));
+        assert!(parse("rank score best").unwrap().is_err());
+        assert!(parse("pct-rank score other").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_window() {
+        let msg = parse("cumsum sales").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneWindow(name, WindowFunc::CumSum) if name == "sales"
+        ));
+        let msg = parse("rolling mean sales window=7").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneWindow(_, WindowFunc::Rolling(Rolling::Mean, 7))
+        ));
+        assert!(parse("rolling mean sales").unwrap().is_err());
+        assert!(parse("rolling median sales window=7").unwrap().is_err());
+        assert!(parse("rolling mean sales window=0").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_drop_nulls() {
+        let msg = parse("drop-nulls email /^addr_/").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneDropNulls(patterns) if patterns == vec![
+                ColumnPattern::Name("email".to_owned()),
+                ColumnPattern::Regex("^addr_".to_owned()),
+            ]
+        ));
+        assert!(parse("drop-nulls").is_none());
+    }
+
+    #[test]
+    fn test_parse_explode() {
+        let msg = parse("explode \"tag list\"").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneExplode(name) if name == "tag list"));
+        assert!(parse("explode a b").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_unnest() {
+        let msg = parse("unnest address").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneUnnest(name) if name == "address"));
+        assert!(parse("unnest a b").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_dummies() {
+        let msg = parse("dummies color").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneDummies(name) if name == "color"));
+        assert!(parse("dummies a b").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_bar() {
+        let msg = parse("bar city").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneShowBarPlot(name) if name == "city"));
+        assert!(parse("bar").is_none());
+        assert!(parse("bar a b").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_plot() {
+        let msg = parse("plot scatter x=area y=price color=city where price > 100")
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            msg,
+            Message::PanePlot(Plot::Scatter { x, y, color: Some(color) }, Some(condition))
+                if x == "area" && y == "price" && color == "city" && condition == "price > 100"
+        ));
+        let msg = parse("plot hist age buckets=20").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PanePlot(Plot::Histogram { column, buckets: Some(20) }, None)
+                if column == "age"
+        ));
+        let msg = parse("plot bar \"home city\" WHERE age >= 18")
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            msg,
+            Message::PanePlot(Plot::Bar(column), Some(condition))
+                if column == "home city" && condition == "age >= 18"
+        ));
+    }
+
+    #[test]
+    fn test_parse_plot_errors() {
+        assert!(parse("plot scatter x=a").unwrap().is_err());
+        assert!(parse("plot scatter x=a y=b z=c").unwrap().is_err());
+        assert!(parse("plot hist age buckets=0").unwrap().is_err());
+        assert!(parse("plot bar a b").unwrap().is_err());
+        assert!(parse("plot pie a").unwrap().is_err());
+        assert!(parse("plot bar a where ").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_rename_tab() {
+        let msg = parse("rename-tab \"big spenders\"").unwrap().unwrap();
+        assert!(matches!(msg, Message::TabsRename(name) if name == "big spenders"));
+        assert!(parse("rename-tab").is_none());
+    }
+
+    #[test]
+    fn test_parse_freq() {
+        let msg = parse("freq \"home city\"").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneFrequency(name) if name == "home city"));
+        assert!(parse("freq a b").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_goto() {
+        let msg = parse("goto 250").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneGoTo(None, RowTarget::Line(250))));
+        let msg = parse("goto 50%").unwrap().unwrap();
+        assert!(matches!(msg, Message::PaneGoTo(None, RowTarget::Percent(p)) if p == 50.0));
+        let msg = parse("goto +100").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneGoTo(None, RowTarget::Offset(100))
+        ));
+        let msg = parse("goto -100").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneGoTo(None, RowTarget::Offset(-100))
+        ));
+        let msg = parse("goto price:12").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneGoTo(Some(column), RowTarget::Line(12)) if column == "price"
+        ));
+        let msg = parse("goto \"unit price\":-5").unwrap().unwrap();
+        assert!(matches!(
+            msg,
+            Message::PaneGoTo(Some(column), RowTarget::Offset(-5)) if column == "unit price"
+        ));
+        assert!(parse("goto 150%").unwrap().is_err());
+        assert!(parse("goto abc").unwrap().is_err());
+        assert!(parse("goto :12").unwrap().is_err());
+        assert!(parse("goto price:").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_mutate_invalid() {
+        assert!(parse("mutate total").unwrap().is_err());
+        assert!(parse("mutate = price").unwrap().is_err());
+        assert!(parse("mutate total =").unwrap().is_err());
+    }
+}
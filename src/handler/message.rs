@@ -6,47 +6,194 @@ use std::sync::{
 use polars::frame::DataFrame;
 use url::Url;
 
-use crate::{misc::remote_load::Reader, tui::pane::TableDescription};
+use crate::{
+    misc::{
+        remote_load::Reader,
+        transform::{
+            Aggregation, Bins, ColumnPattern, FillNull, Footer, JoinHow, Keep, Plot, Rank,
+            RowTarget, SampleSize, Slice, TextPattern, WindowFunc,
+        },
+    },
+    tui::{pane::TableDescription, popups::inline_query_picker::QueryType, table::Motion},
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Message {
     AppDismissOverlay,
     AppShowCommandPicker,
+    /// Opens the command palette with the text typed in.
+    AppPromptCommand(String),
+    /// Keeps the command line run from the palette for `.` and `!!`.
+    AppRememberCommand(String),
+    /// Runs the last command line again; only the last one changing the data
+    /// when `true`.
+    AppRepeatCommand(bool),
     AppShowError(String),
     AppShowToast(String),
     AppShowThemeSelector,
+    /// Switches to a theme by name, file, or `auto`, or opens the theme selector
+    /// on the best matches when there is no such theme.
+    AppSelectTheme(String),
     AppShowSchema,
     AppShowImporter,
+    AppShowHelp,
     AppDismissSchema,
     AppShowSqlQuery,
     AppDownloadDataSource(Url, Arc<dyn Reader>),
     AppReloadConfig,
     TabsSelect(usize),
+    /// Selects the tab the given number of places to the right, or to the left
+    /// when negative, as for a count typed before `H` / `L`.
+    TabsSelectBy(isize),
     TabsDismissSwitcher,
     TabsAddNamePane(DataFrame, String),
     TabsAddQueryPane(DataFrame, String),
+    TabsAddPane(DataFrame, TableDescription),
+    TabsJoin(String, Vec<(String, String)>, JoinHow),
+    TabsConcat(Vec<String>),
+    TabsDiff(Option<String>, String, Vec<String>),
+    /// Renames the selected tab and its SQL table.
+    TabsRename(String),
+    /// Opens a copy of the selected tab, with its frames and view, in a new tab.
+    TabsDuplicate,
+    /// Closes every tab but the selected one.
+    TabsCloseOthers,
+    /// Closes the tabs to the right of the selected one.
+    TabsCloseRight,
+    /// Moves the selected tab by an offset, e.g. `-1` one place to the left.
+    TabsMoveSelected(isize),
     PaneEditInExternalEditor,
     PaneShowExporter,
     PaneShowFuzzySearch,
     PaneShowInlineFilter,
+    PaneShowFilterBar,
     PaneShowInlineOrder,
     PaneShowHistogram(String, usize),
     PaneShowHistogramBuilder,
+    PaneShowBarPlot(String),
     PaneShowScatterPlot(String, String, Option<String>),
+    PaneShowCorrelation,
+    /// Plot of the current frame, or of its rows matching an SQL condition.
+    PanePlot(Plot, Option<String>),
     PaneShowScatterPlotBuilder,
     PaneShowSearch,
     PaneDismissModal,
+    PaneAddFilter(String),
+    PaneRemoveFilter(usize),
     PanePushDataFrame(DataFrame, TableDescription),
     PanePopDataFrame,
+    /// Sorts by the column ascending, descending, or not at all, in turn.
+    PaneCycleSort(String),
+    /// Sorts by the column, descending when `true`.
+    PaneSort(String, bool),
+    /// Runs the select, filter, or order typed into the inline prompt on the
+    /// current frame.
+    PaneInlineQuery(QueryType, String),
     PaneTableSelect(usize),
+    /// Moves in the table the given number of times, as for a count typed
+    /// before the motion key.
+    PaneTableMove(Motion, usize),
+    PaneTableJump(usize),
+    PaneRecordJump(usize),
+    PaneGoToLine(usize),
+    PaneGoTo(Option<String>, RowTarget),
+    PaneEditCell(usize, String, String),
+    PaneEditRecord(usize, Vec<(String, String)>),
+    PaneShowGoToColumn,
+    PaneGoToColumn(String),
     PaneShowInlineSelect,
     PaneShowTableRegisterer,
     PaneShowTableInfo,
     PaneShowColumnCaster,
+    PaneShowColumnVisibility,
+    PaneHideColumns(Vec<ColumnPattern>),
+    PaneUnhideColumns(Vec<ColumnPattern>),
+    PaneSetColumnWidth(String, Option<u16>),
+    PaneSetFooter(Option<Footer>),
+    PaneMutate(String, String),
+    PaneRenameColumn(String, String),
+    PaneRenameColumns(String, String),
+    PaneDropColumns(Vec<ColumnPattern>),
+    PaneFillNull(String, FillNull),
+    PaneDedup(Vec<ColumnPattern>, Keep),
+    PaneDropNulls(Vec<ColumnPattern>),
+    PaneExplode(String),
+    PaneUnnest(String),
+    PaneDummies(String),
+    PaneFrequency(String),
+    PaneDescribe,
+    /// Reads the file of the current tab again.
+    PaneReload,
+    /// Turns reloading the file of the current tab on changes on or off.
+    PaneToggleAutoReload,
+    PaneSample(SampleSize, Option<u64>),
+    PaneSlice(Slice),
+    PaneAggregate(Vec<String>, Vec<Aggregation>),
+    PaneTranspose,
+    PaneSplit(String, TextPattern),
+    PaneParseDates(String, String),
+    PaneConvertTimeZone(String, String, String),
+    PaneReplace(String, TextPattern, String),
+    PaneSubstitute(String, String, Vec<ColumnPattern>),
+    PaneCut(String, Bins),
+    PaneRank(String, Rank, bool),
+    PaneWindow(String, WindowFunc),
     Quit,
 }
 
 impl Message {
+    /// Whether a macro can replay the message on another tab: messages opening
+    /// a popup, giving feedback, or carrying a frame computed for the tab they
+    /// were sent on are left out.
+    pub fn is_replayable(&self) -> bool {
+        !matches!(
+            self,
+            Message::AppDismissOverlay
+                | Message::AppShowCommandPicker
+                | Message::AppPromptCommand(_)
+                | Message::AppRememberCommand(_)
+                | Message::AppRepeatCommand(_)
+                | Message::AppShowError(_)
+                | Message::AppShowToast(_)
+                | Message::AppShowThemeSelector
+                | Message::AppSelectTheme(_)
+                | Message::AppShowSchema
+                | Message::AppShowImporter
+                | Message::AppShowHelp
+                | Message::AppDismissSchema
+                | Message::AppShowSqlQuery
+                | Message::AppDownloadDataSource(_, _)
+                | Message::AppReloadConfig
+                | Message::TabsDismissSwitcher
+                | Message::TabsAddNamePane(_, _)
+                | Message::TabsAddQueryPane(_, _)
+                | Message::TabsAddPane(_, _)
+                | Message::PaneEditInExternalEditor
+                | Message::PaneShowExporter
+                | Message::PaneShowFuzzySearch
+                | Message::PaneShowInlineFilter
+                | Message::PaneShowFilterBar
+                | Message::PaneShowInlineOrder
+                | Message::PaneShowHistogram(_, _)
+                | Message::PaneShowHistogramBuilder
+                | Message::PaneShowBarPlot(_)
+                | Message::PaneShowScatterPlot(_, _, _)
+                | Message::PaneShowCorrelation
+                | Message::PanePlot(_, _)
+                | Message::PaneShowScatterPlotBuilder
+                | Message::PaneShowSearch
+                | Message::PaneDismissModal
+                | Message::PanePushDataFrame(_, _)
+                | Message::PaneShowGoToColumn
+                | Message::PaneShowInlineSelect
+                | Message::PaneShowTableRegisterer
+                | Message::PaneShowTableInfo
+                | Message::PaneShowColumnCaster
+                | Message::PaneShowColumnVisibility
+                | Message::Quit
+        )
+    }
+
     pub fn enqueue(self) {
         let _ = SHARED_CHANNEL.0.send(self);
     }
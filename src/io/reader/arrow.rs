@@ -4,20 +4,34 @@ use polars::{io::SerReader, prelude::IpcReader};
 
 use crate::{
     AppResult,
+    args::Args,
     io::reader::{DataFrameReader, NamedFrames, ReaderSource},
     misc::stdin::stdin,
 };
 
-#[derive(Debug)]
-pub struct ArrowIpcToDataFrame;
+#[derive(Debug, Default)]
+pub struct ArrowIpcToDataFrame {
+    /// Rows read at most, from `--limit`.
+    n_rows: Option<usize>,
+}
+
+impl ArrowIpcToDataFrame {
+    pub fn from_args(args: &Args) -> Self {
+        Self { n_rows: args.limit }
+    }
+}
 
 impl DataFrameReader for ArrowIpcToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
         let df = match &input {
             ReaderSource::File(path) => IpcReader::new(File::open(path)?)
+                .with_n_rows(self.n_rows)
+                .set_rechunk(true)
+                .finish()?,
+            ReaderSource::Stdin => IpcReader::new(stdin())
+                .with_n_rows(self.n_rows)
                 .set_rechunk(true)
                 .finish()?,
-            ReaderSource::Stdin => IpcReader::new(stdin()).set_rechunk(true).finish()?,
         };
         Ok([(input.table_name(), df)].into())
     }
@@ -1,16 +1,20 @@
-use std::fs::File;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Cursor, Read, Seek},
+};
 
 use anyhow::anyhow;
 use polars::{
     frame::DataFrame,
     io::{SerReader, mmap::MmapBytesReader},
-    prelude::{CsvParseOptions, CsvReadOptions},
+    prelude::{Column, CsvParseOptions, CsvReadOptions},
 };
+use rand::RngExt;
 
 use crate::{
     AppResult,
     args::{Args, InferSchema},
-    io::reader::{DataFrameReader, NamedFrames, ReaderSource},
+    io::reader::{DataFrameReader, NamedFrame, NamedFrames, ReaderSource},
     misc::{stdin::stdin, type_ext::ToAscii},
 };
 
@@ -22,6 +26,13 @@ pub struct CsvToDataFrame {
     no_header: bool,
     ignore_errors: bool,
     truncate_ragged_lines: bool,
+    /// Rows read at most, from `--limit`.
+    n_rows: Option<usize>,
+    /// Rows drawn at random while reading, from `--sample`.
+    sample: Option<usize>,
+    /// Whether rows with a wrong number of fields are left out and listed in a
+    /// companion table, from `--ignore-errors`.
+    skip_malformed_rows: bool,
 }
 
 impl CsvToDataFrame {
@@ -33,6 +44,9 @@ impl CsvToDataFrame {
             no_header: args.no_header,
             ignore_errors: args.ignore_errors,
             truncate_ragged_lines: args.truncate_ragged_lines,
+            n_rows: args.limit,
+            sample: args.sample,
+            skip_malformed_rows: args.ignore_errors,
         }
     }
 
@@ -56,6 +70,7 @@ impl CsvToDataFrame {
             .with_ignore_errors(self.ignore_errors)
             .with_infer_schema_length(self.infer_schema.to_csv_infer_schema_length())
             .with_has_header(!self.no_header)
+            .with_n_rows(self.n_rows)
             .with_parse_options(
                 CsvParseOptions::default()
                     .with_truncate_ragged_lines(self.truncate_ragged_lines)
@@ -82,12 +97,21 @@ impl Default for CsvToDataFrame {
             no_header: false,
             ignore_errors: true,
             truncate_ragged_lines: false,
+            n_rows: None,
+            sample: None,
+            skip_malformed_rows: false,
         }
     }
 }
 
 impl DataFrameReader for CsvToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        if let Some(rows) = self.sample {
+            return self.read_sample(input, rows);
+        }
+        if self.skip_malformed_rows {
+            return self.read_skipping_malformed_rows(input);
+        }
         let df = match &input {
             ReaderSource::File(path) => self.try_into_frame(File::open(path)?),
             ReaderSource::Stdin => self.try_into_frame(stdin()),
@@ -95,3 +119,520 @@ impl DataFrameReader for CsvToDataFrame {
         Ok([(input.table_name(), df)].into())
     }
 }
+
+impl CsvToDataFrame {
+    /// Reads `rows` rows drawn at random in a single pass over the input,
+    /// holding only the drawn rows in memory, and the malformed ones when they
+    /// are left out.
+    fn read_sample(&self, input: ReaderSource, rows: usize) -> AppResult<NamedFrames> {
+        let separator = self
+            .separator_char
+            .to_ascii()
+            .ok_or(anyhow!("non-ASCII separator character"))?;
+        let sample = |reader: Box<dyn BufRead>| {
+            sample_rows(
+                reader,
+                separator,
+                self.quote_char.to_ascii(),
+                !self.no_header,
+                rows,
+                self.skip_malformed_rows,
+                self.truncate_ragged_lines,
+            )
+        };
+        let (kept, malformed) = match &input {
+            ReaderSource::File(path) => sample(Box::new(BufReader::new(File::open(path)?)))?,
+            ReaderSource::Stdin => sample(Box::new(stdin()))?,
+        };
+        let df = self.try_into_frame(Cursor::new(kept))?;
+        with_errors_table(input.table_name(), df, &malformed)
+    }
+
+    /// Reads the well-formed rows, followed by a `<name>_errors` table with the
+    /// line and text of every malformed one, when there are any. The kept rows
+    /// are streamed to a temporary file the reader maps, and the input is
+    /// scanned until `n_rows` rows are kept.
+    fn read_skipping_malformed_rows(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        let separator = self
+            .separator_char
+            .to_ascii()
+            .ok_or(anyhow!("non-ASCII separator character"))?;
+        let max_rows = self.n_rows.map(|rows| rows + usize::from(!self.no_header));
+        let mut kept = tempfile::tempfile()?;
+        let mut copy = |reader: Box<dyn BufRead>| {
+            let mut rows = WellFormedRows::new(
+                reader,
+                separator,
+                self.quote_char.to_ascii(),
+                self.truncate_ragged_lines,
+                max_rows,
+            );
+            io::copy(&mut rows, &mut kept).map(|_| rows.malformed)
+        };
+        let malformed = match &input {
+            ReaderSource::File(path) => copy(Box::new(BufReader::new(File::open(path)?)))?,
+            ReaderSource::Stdin => copy(Box::new(stdin()))?,
+        };
+        kept.rewind()?;
+        let df = self.try_into_frame(kept)?;
+        with_errors_table(input.table_name(), df, &malformed)
+    }
+}
+
+/// Names `df` after the input, followed by a `<name>_errors` table with the line
+/// and text of every malformed row, when there are any.
+fn with_errors_table(
+    name: String,
+    df: DataFrame,
+    malformed: &[MalformedRow],
+) -> AppResult<NamedFrames> {
+    if malformed.is_empty() {
+        return Ok([(name, df)].into());
+    }
+    let errors = DataFrame::new_infer_height(vec![
+        Column::new(
+            "line".into(),
+            malformed
+                .iter()
+                .map(|row| row.line as u64)
+                .collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "error".into(),
+            malformed
+                .iter()
+                .map(|row| row.error.as_str())
+                .collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "text".into(),
+            malformed
+                .iter()
+                .map(|row| row.text.as_str())
+                .collect::<Vec<_>>(),
+        ),
+    ])?;
+    let errors_name = format!("{name}_errors");
+    Ok([(name, df), (errors_name, errors)].into())
+}
+
+/// Takes the `<name>_errors` table read with `--ignore-errors` after the table
+/// of a CSV file off `frames`, when there is one.
+pub fn take_errors_table(frames: &mut Vec<NamedFrame>) -> Option<NamedFrame> {
+    match frames.as_slice() {
+        [(name, _), (errors, _)] if *errors == format!("{name}_errors") => frames.pop(),
+        _ => None,
+    }
+}
+
+/// Row left out of the table by `--ignore-errors`.
+#[derive(Debug, PartialEq)]
+struct MalformedRow {
+    /// Line the row starts on, from 1.
+    line: usize,
+    error: String,
+    text: String,
+}
+
+/// Rows of `reader` having as many fields as the first one (the header), read
+/// one at a time while the others are collected in `malformed`, until
+/// `max_rows` rows are kept. Rows with more fields are kept when
+/// `truncate_ragged_lines` is set, as the reader cuts them.
+struct WellFormedRows<R> {
+    reader: R,
+    separator: u8,
+    quote: Option<u8>,
+    truncate_ragged_lines: bool,
+    max_rows: Option<usize>,
+    malformed: Vec<MalformedRow>,
+    kept_rows: usize,
+    expected: Option<usize>,
+    /// Line the next row starts on, from 1.
+    line: usize,
+    /// Kept row being read, from `pos`.
+    row: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: BufRead> WellFormedRows<R> {
+    fn new(
+        reader: R,
+        separator: u8,
+        quote: Option<u8>,
+        truncate_ragged_lines: bool,
+        max_rows: Option<usize>,
+    ) -> Self {
+        Self {
+            reader,
+            separator,
+            quote,
+            truncate_ragged_lines,
+            max_rows,
+            malformed: Vec::new(),
+            kept_rows: 0,
+            expected: None,
+            line: 1,
+            row: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Reads rows into `row` until one is kept, returning false at the end.
+    fn next_row(&mut self) -> io::Result<bool> {
+        while self.max_rows.is_none_or(|max| self.kept_rows < max) {
+            self.row.clear();
+            self.pos = 0;
+            let (fields, lines, closed) =
+                read_row(&mut self.reader, &mut self.row, self.separator, self.quote)?;
+            if self.row.is_empty() {
+                break;
+            }
+            let line = self.line;
+            self.line += lines;
+            let text = row_text(&self.row);
+            let error = row_error(
+                fields,
+                closed,
+                text.is_empty(),
+                &mut self.expected,
+                self.truncate_ragged_lines,
+            );
+            match error {
+                Some(error) => self.malformed.push(MalformedRow { line, error, text }),
+                None => {
+                    self.kept_rows += usize::from(!text.is_empty());
+                    return Ok(true);
+                }
+            }
+        }
+        self.row.clear();
+        self.pos = 0;
+        Ok(false)
+    }
+}
+
+impl<R: BufRead> Read for WellFormedRows<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut read = 0;
+        while read < buf.len() {
+            if self.pos == self.row.len() && !self.next_row()? {
+                break;
+            }
+            let len = (self.row.len() - self.pos).min(buf.len() - read);
+            buf[read..read + len].copy_from_slice(&self.row[self.pos..self.pos + len]);
+            self.pos += len;
+            read += len;
+        }
+        Ok(read)
+    }
+}
+
+/// Why a row of `fields` fields is malformed, if it is. The first row that is
+/// not blank sets the `expected` number of fields.
+fn row_error(
+    fields: usize,
+    closed: bool,
+    blank: bool,
+    expected: &mut Option<usize>,
+    truncate_ragged_lines: bool,
+) -> Option<String> {
+    if !closed {
+        Some("Unterminated quoted field".to_owned())
+    } else if blank {
+        None
+    } else {
+        let expected = *expected.get_or_insert(fields);
+        (fields < expected || (fields > expected && !truncate_ragged_lines))
+            .then(|| format!("Expected {expected} fields, found {fields}"))
+    }
+}
+
+fn row_text(row: &[u8]) -> String {
+    String::from_utf8_lossy(row)
+        .trim_end_matches(['\r', '\n'])
+        .to_owned()
+}
+
+/// Draws `rows` rows of `reader` at random with reservoir sampling, returning
+/// them in their input order after the header, when there is one. Blank lines
+/// are left out. With `skip_malformed_rows`, rows with a wrong number of fields
+/// are never drawn but all returned apart, as by `WellFormedRows`.
+fn sample_rows(
+    mut reader: impl BufRead,
+    separator: u8,
+    quote: Option<u8>,
+    header: bool,
+    rows: usize,
+    skip_malformed_rows: bool,
+    truncate_ragged_lines: bool,
+) -> io::Result<(Vec<u8>, Vec<MalformedRow>)> {
+    let mut kept = Vec::new();
+    let mut malformed = Vec::new();
+    let mut expected = None;
+    let mut line = 1;
+    if header {
+        let (fields, lines, _) = read_row(&mut reader, &mut kept, separator, quote)?;
+        expected = Some(fields);
+        line += lines;
+        if !kept.is_empty() && !kept.ends_with(b"\n") {
+            kept.push(b'\n');
+        }
+    }
+    let mut rng = rand::rng();
+    let mut drawn: Vec<(usize, Vec<u8>)> = Vec::new();
+    let mut seen = 0;
+    let mut row = Vec::new();
+    loop {
+        row.clear();
+        let (fields, lines, closed) = read_row(&mut reader, &mut row, separator, quote)?;
+        if row.is_empty() {
+            break;
+        }
+        let row_line = line;
+        line += lines;
+        let blank = row.trim_ascii().is_empty();
+        if skip_malformed_rows
+            && let Some(error) =
+                row_error(fields, closed, blank, &mut expected, truncate_ragged_lines)
+        {
+            malformed.push(MalformedRow {
+                line: row_line,
+                error,
+                text: row_text(&row),
+            });
+            continue;
+        }
+        if blank {
+            continue;
+        }
+        if !row.ends_with(b"\n") {
+            row.push(b'\n');
+        }
+        if drawn.len() < rows {
+            drawn.push((seen, row.clone()));
+        } else {
+            let idx = rng.random_range(0..=seen);
+            if idx < rows {
+                drawn[idx] = (seen, row.clone());
+            }
+        }
+        seen += 1;
+    }
+    drawn.sort_unstable_by_key(|(idx, _)| *idx);
+    for (_, row) in drawn {
+        kept.extend_from_slice(&row);
+    }
+    Ok((kept, malformed))
+}
+
+/// Reads the next row of `reader` with its line break into `row`, returning its
+/// number of fields, the lines it spans, and whether its quotes are closed.
+fn read_row(
+    reader: &mut impl BufRead,
+    row: &mut Vec<u8>,
+    separator: u8,
+    quote: Option<u8>,
+) -> io::Result<(usize, usize, bool)> {
+    let mut fields = 1;
+    let mut lines = 0;
+    let mut quoted = false;
+    loop {
+        let start = row.len();
+        if reader.read_until(b'\n', row)? == 0 {
+            break;
+        }
+        lines += 1;
+        for byte in &row[start..] {
+            match *byte {
+                byte if Some(byte) == quote => quoted = !quoted,
+                byte if byte == separator && !quoted => fields += 1,
+                _ => (),
+            }
+        }
+        if !quoted || !row.ends_with(b"\n") {
+            break;
+        }
+    }
+    Ok((fields, lines.max(1), !quoted))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn well_formed_rows(
+        contents: &[u8],
+        separator: u8,
+        quote: Option<u8>,
+        truncate_ragged_lines: bool,
+        max_rows: Option<usize>,
+    ) -> (Vec<u8>, Vec<MalformedRow>) {
+        let mut rows =
+            WellFormedRows::new(contents, separator, quote, truncate_ragged_lines, max_rows);
+        let mut kept = Vec::new();
+        rows.read_to_end(&mut kept).unwrap();
+        (kept, rows.malformed)
+    }
+
+    #[test]
+    fn test_splits_malformed_rows() {
+        let contents = b"a,b,c\n1,2,3\n4,5\n\"x\ny\",8,9\n6,7,8,9\n\"open,1,2\n";
+        let (kept, malformed) = well_formed_rows(&contents[..], b',', Some(b'"'), false, None);
+        assert_eq!(kept, b"a,b,c\n1,2,3\n\"x\ny\",8,9\n");
+        assert_eq!(
+            malformed,
+            vec![
+                MalformedRow {
+                    line: 3,
+                    error: "Expected 3 fields, found 2".to_owned(),
+                    text: "4,5".to_owned(),
+                },
+                MalformedRow {
+                    line: 6,
+                    error: "Expected 3 fields, found 4".to_owned(),
+                    text: "6,7,8,9".to_owned(),
+                },
+                MalformedRow {
+                    line: 7,
+                    error: "Unterminated quoted field".to_owned(),
+                    text: "\"open,1,2".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keeps_long_rows_when_truncating() {
+        let contents = b"a;b\r\n1;2;3\r\n";
+        let (kept, malformed) = well_formed_rows(&contents[..], b';', None, true, None);
+        assert_eq!(kept, b"a;b\r\n1;2;3\r\n");
+        assert!(malformed.is_empty());
+    }
+
+    #[test]
+    fn test_samples_rows_in_input_order() {
+        let contents = (0..100)
+            .map(|n| format!("{n},\"x\ny\"\n"))
+            .collect::<String>();
+        let contents = format!("n,s\n{contents}");
+        let (kept, _) =
+            sample_rows(contents.as_bytes(), b',', Some(b'"'), true, 5, false, false).unwrap();
+        let kept = String::from_utf8(kept).unwrap();
+        assert!(kept.starts_with("n,s\n"));
+        let numbers = kept
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                line.split_once(',')
+                    .map(|(n, _)| n.parse::<usize>().unwrap())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(numbers.len(), 5);
+        assert!(numbers.is_sorted_by(|a, b| a < b));
+
+        let contents = b"a\n1\n\n2";
+        let (kept, _) = sample_rows(&contents[..], b',', None, true, 10, false, false).unwrap();
+        assert_eq!(kept, b"a\n1\n2\n");
+        let (kept, _) = sample_rows(&contents[..], b',', None, false, 10, false, false).unwrap();
+        assert_eq!(kept, b"a\n1\n2\n");
+    }
+
+    #[test]
+    fn test_samples_only_well_formed_rows() {
+        let contents = b"a,b\n1,2\n3\n\n4,5\n6,7,8\n";
+        let (kept, malformed) =
+            sample_rows(&contents[..], b',', None, true, 10, true, false).unwrap();
+        assert_eq!(kept, b"a,b\n1,2\n4,5\n");
+        assert_eq!(
+            malformed,
+            vec![
+                MalformedRow {
+                    line: 3,
+                    error: "Expected 2 fields, found 1".to_owned(),
+                    text: "3".to_owned(),
+                },
+                MalformedRow {
+                    line: 6,
+                    error: "Expected 2 fields, found 3".to_owned(),
+                    text: "6,7,8".to_owned(),
+                },
+            ]
+        );
+
+        let (kept, malformed) =
+            sample_rows(&contents[..], b',', None, true, 10, false, false).unwrap();
+        assert_eq!(kept, b"a,b\n1,2\n3\n4,5\n6,7,8\n");
+        assert!(malformed.is_empty());
+    }
+
+    #[test]
+    fn test_splits_rows_with_custom_separator_and_quote() {
+        let contents = b"a;b\n'x\ny;z';1\n2\n'it''s';3\n";
+        let (kept, malformed) = well_formed_rows(&contents[..], b';', Some(b'\''), false, None);
+        assert_eq!(kept, b"a;b\n'x\ny;z';1\n'it''s';3\n");
+        assert_eq!(
+            malformed,
+            vec![MalformedRow {
+                line: 4,
+                error: "Expected 2 fields, found 1".to_owned(),
+                text: "2".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reads_quoted_newlines_skipping_malformed_rows() {
+        let mut file = tempfile::Builder::new().suffix(".csv").tempfile().unwrap();
+        file.write_all(b"a|b\n'x\ny|z'|1\n2\n'w'|3\n").unwrap();
+        let reader = CsvToDataFrame {
+            separator_char: '|',
+            quote_char: '\'',
+            skip_malformed_rows: true,
+            ..Default::default()
+        };
+        let frames = reader
+            .read_to_data_frames(ReaderSource::File(file.path().to_owned()))
+            .unwrap();
+        let [(_, df), (_, errors)] = &frames[..] else {
+            panic!("expected a table and its errors, found {}", frames.len());
+        };
+        let a = df.column("a").unwrap().str().unwrap();
+        assert_eq!(a.get(0), Some("x\ny|z"));
+        assert_eq!(a.get(1), Some("w"));
+        assert_eq!(df.height(), 2);
+        assert_eq!(errors.height(), 1);
+    }
+
+    #[test]
+    fn test_takes_errors_table() {
+        let mut file = tempfile::Builder::new().suffix(".csv").tempfile().unwrap();
+        file.write_all(b"a,b\n1,2\n3\n4,5\n").unwrap();
+        let reader = CsvToDataFrame {
+            skip_malformed_rows: true,
+            ..Default::default()
+        };
+        let mut frames = reader
+            .read_to_data_frames(ReaderSource::File(file.path().to_owned()))
+            .unwrap()
+            .into_vec();
+        let (name, errors) = take_errors_table(&mut frames).unwrap();
+        assert!(name.ends_with("_errors"));
+        assert_eq!(errors.height(), 1);
+        let [(_, df)] = frames.as_slice() else {
+            panic!("expected a single table, found {}", frames.len());
+        };
+        assert_eq!(df.height(), 2);
+        assert!(take_errors_table(&mut frames).is_none());
+    }
+
+    #[test]
+    fn test_stops_once_enough_rows_are_kept() {
+        let contents = b"a,b\n1,2\n3\n\n4,5\n6\n7,8\n";
+        let (kept, malformed) = well_formed_rows(&contents[..], b',', None, false, Some(3));
+        assert_eq!(kept, b"a,b\n1,2\n\n4,5\n");
+        assert_eq!(malformed.len(), 1);
+        assert_eq!(malformed[0].line, 3);
+    }
+}
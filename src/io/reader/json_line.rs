@@ -1,7 +1,11 @@
-use std::fs::File;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Cursor},
+};
 
 use polars::{
-    io::SerReader,
+    frame::DataFrame,
+    io::{SerReader, mmap::MmapBytesReader},
     prelude::{JsonFormat, JsonReader},
 };
 
@@ -18,40 +22,78 @@ use crate::{
 #[derive(Debug)]
 pub struct JsonLineToDataFrame {
     ignore_errors: bool,
+    /// Rows read at most, from `--limit`.
+    n_rows: Option<usize>,
 }
 
 impl JsonLineToDataFrame {
     pub fn from_args(args: &Args) -> Self {
         Self {
             ignore_errors: args.ignore_errors,
+            n_rows: args.limit,
         }
     }
+
+    fn try_into_frame(&self, reader: impl MmapBytesReader) -> AppResult<DataFrame> {
+        Ok(JsonReader::new(reader)
+            .with_json_format(JsonFormat::JsonLines)
+            .infer_schema_len(None)
+            .with_ignore_errors(self.ignore_errors)
+            .set_rechunk(true)
+            .finish()?)
+    }
 }
 
 impl Default for JsonLineToDataFrame {
     fn default() -> Self {
         Self {
             ignore_errors: true,
+            n_rows: None,
         }
     }
 }
 
 impl DataFrameReader for JsonLineToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
-        let df = match &input {
-            ReaderSource::File(path) => JsonReader::new(File::open(path)?)
-                .with_json_format(JsonFormat::JsonLines)
-                .infer_schema_len(None)
-                .with_ignore_errors(self.ignore_errors)
-                .set_rechunk(true)
-                .finish()?,
-            ReaderSource::Stdin => JsonReader::new(stdin())
-                .with_json_format(JsonFormat::JsonLines)
-                .infer_schema_len(None)
-                .with_ignore_errors(self.ignore_errors)
-                .set_rechunk(true)
-                .finish()?,
+        let df = match (&input, self.n_rows) {
+            (ReaderSource::File(path), None) => self.try_into_frame(File::open(path)?)?,
+            (ReaderSource::Stdin, None) => self.try_into_frame(stdin())?,
+            (ReaderSource::File(path), Some(rows)) => self.try_into_frame(Cursor::new(
+                head_lines(BufReader::new(File::open(path)?), rows)?,
+            ))?,
+            (ReaderSource::Stdin, Some(rows)) => {
+                self.try_into_frame(Cursor::new(head_lines(stdin(), rows)?))?
+            }
         };
         Ok([(input.table_name(), df)].into())
     }
 }
+
+/// First `rows` non-blank lines of `reader`, read no further.
+fn head_lines(mut reader: impl BufRead, rows: usize) -> io::Result<Vec<u8>> {
+    let mut lines = Vec::new();
+    let mut kept = 0;
+    while kept < rows {
+        let start = lines.len();
+        if reader.read_until(b'\n', &mut lines)? == 0 {
+            break;
+        }
+        kept += usize::from(!lines[start..].trim_ascii().is_empty());
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_head_lines_skips_blank_lines() {
+        let input = b"{\"a\":1}\n\n{\"a\":2}\n{\"a\":3}\n";
+        assert_eq!(
+            head_lines(&input[..], 2).unwrap(),
+            b"{\"a\":1}\n\n{\"a\":2}\n"
+        );
+        assert_eq!(head_lines(&input[..], 10).unwrap(), input);
+    }
+}
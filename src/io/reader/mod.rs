@@ -9,13 +9,14 @@ mod json_line;
 mod logfmt;
 mod markdown;
 mod parquet;
+mod rows;
 mod source;
 mod sqlite;
 mod traits;
 
 pub use arrow::ArrowIpcToDataFrame;
 pub use avro::AvroToDataFrame;
-pub use csv::CsvToDataFrame;
+pub use csv::{CsvToDataFrame, take_errors_table};
 pub use excel::ExcelToDataFrames;
 pub use fwf::FwfToDataFrame;
 pub use html::HtmlToDataFrame;
@@ -24,6 +25,7 @@ pub use json_line::JsonLineToDataFrame;
 pub use logfmt::LogfmtToDataFrame;
 pub use markdown::MarkdownToDataFrame;
 pub use parquet::ParquetToDataFrame;
+pub use rows::{RowSelection, SelectRows};
 pub use source::ReaderSource;
 pub use sqlite::SqliteToDataFrames;
-pub use traits::{BuildReader, DataFrameReader, NamedFrames};
+pub use traits::{BuildReader, DataFrameReader, NamedFrame, NamedFrames};
@@ -4,21 +4,35 @@ use polars::{io::SerReader, prelude::ParquetReader};
 
 use crate::{
     AppResult,
+    args::Args,
     io::reader::{DataFrameReader, NamedFrames, ReaderSource},
     misc::stdin::stdin,
 };
 
-#[derive(Debug)]
-pub struct ParquetToDataFrame;
+#[derive(Debug, Default)]
+pub struct ParquetToDataFrame {
+    /// Rows read at most, from `--limit`.
+    n_rows: Option<usize>,
+}
+
+impl ParquetToDataFrame {
+    pub fn from_args(args: &Args) -> Self {
+        Self { n_rows: args.limit }
+    }
+}
 
 impl DataFrameReader for ParquetToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
         let df = match &input {
             ReaderSource::File(path) => ParquetReader::new(File::open(path)?)
+                .with_slice(self.n_rows.map(|rows| (0, rows)))
                 .set_rechunk(true)
                 .finish()?,
 
-            ReaderSource::Stdin => ParquetReader::new(stdin()).set_rechunk(true).finish()?,
+            ReaderSource::Stdin => ParquetReader::new(stdin())
+                .with_slice(self.n_rows.map(|rows| (0, rows)))
+                .set_rechunk(true)
+                .finish()?,
         };
         Ok([(input.table_name(), df)].into())
     }
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use crate::{
+    AppResult,
+    io::reader::{DataFrameReader, NamedFrames, ReaderSource},
+    misc::{
+        remote_load::Reader,
+        transform::{SampleSize, sample},
+    },
+};
+
+/// Rows kept by `--limit` or `--sample`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowSelection {
+    /// The first rows.
+    Head(usize),
+    /// Rows drawn at random.
+    Sample(usize),
+}
+
+/// Reader keeping only the selected rows of every frame of another reader.
+#[derive(Debug)]
+pub struct SelectRows {
+    reader: Arc<dyn Reader>,
+    selection: RowSelection,
+}
+
+impl SelectRows {
+    pub fn new(reader: Arc<dyn Reader>, selection: RowSelection) -> Self {
+        Self { reader, selection }
+    }
+}
+
+impl DataFrameReader for SelectRows {
+    fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        self.reader
+            .read_to_data_frames(input)?
+            .into_iter()
+            .map(|(name, df)| {
+                let df = match self.selection {
+                    RowSelection::Head(rows) => df.head(Some(rows)),
+                    RowSelection::Sample(rows) if df.height() > rows => {
+                        sample(&df, SampleSize::Rows(rows), None)?
+                    }
+                    // already sampled while reading, as CSV files are
+                    RowSelection::Sample(_) => df,
+                };
+                Ok((name, df))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::{df, prelude::DataFrame};
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct Numbers;
+
+    impl DataFrameReader for Numbers {
+        fn read_to_data_frames(&self, _input: ReaderSource) -> AppResult<NamedFrames> {
+            let df = df!("n" => (0..10).collect::<Vec<i64>>())?;
+            Ok([("first".to_owned(), df.clone()), ("second".to_owned(), df)].into())
+        }
+    }
+
+    fn select(selection: RowSelection) -> Vec<(String, DataFrame)> {
+        SelectRows::new(Arc::new(Numbers), selection)
+            .read_to_data_frames(ReaderSource::Stdin)
+            .unwrap()
+            .into_vec()
+    }
+
+    #[test]
+    fn test_head_keeps_first_rows_of_every_frame() {
+        let frames = select(RowSelection::Head(3));
+        assert_eq!(frames.len(), 2);
+        for (_, df) in frames {
+            let n = df.column("n").unwrap().i64().unwrap();
+            assert_eq!(n.into_no_null_iter().collect::<Vec<_>>(), [0, 1, 2]);
+        }
+    }
+
+    #[test]
+    fn test_sample_keeps_distinct_rows() {
+        for (_, df) in select(RowSelection::Sample(4)) {
+            assert_eq!(df.height(), 4);
+            assert_eq!(df.column("n").unwrap().n_unique().unwrap(), 4);
+        }
+    }
+
+    #[test]
+    fn test_selection_larger_than_frame_keeps_all_rows() {
+        for (_, df) in select(RowSelection::Head(20)) {
+            assert_eq!(df.height(), 10);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{path::Path, sync::Arc};
 
 use polars::frame::DataFrame;
 
@@ -10,9 +10,11 @@ use crate::{
         reader::{
             ArrowIpcToDataFrame, AvroToDataFrame, CsvToDataFrame, ExcelToDataFrames,
             FwfToDataFrame, HtmlToDataFrame, JsonLineToDataFrame, JsonToDataFrame,
-            LogfmtToDataFrame, MarkdownToDataFrame, ParquetToDataFrame, SqliteToDataFrames,
+            LogfmtToDataFrame, MarkdownToDataFrame, ParquetToDataFrame, RowSelection, SelectRows,
+            SqliteToDataFrames,
         },
     },
+    misc::{remote_load::Reader, stdin::stdin},
 };
 
 pub type NamedFrame = (String, DataFrame);
@@ -23,46 +25,118 @@ pub trait DataFrameReader {
 }
 
 pub trait BuildReader {
-    fn build_reader(&self, path: impl AsRef<Path>) -> AppResult<Box<dyn DataFrameReader>>;
+    fn build_reader(&self, path: impl AsRef<Path>) -> AppResult<Arc<dyn Reader>>;
 }
 
 impl BuildReader for Args {
-    fn build_reader(&self, path: impl AsRef<Path>) -> AppResult<Box<dyn DataFrameReader>> {
-        match self.format {
-            Some(Format::Dsv) | Some(Format::Csv) => Ok(Box::new(CsvToDataFrame::from_args(self))),
-            Some(Format::Tsv) => Ok(Box::new(
-                CsvToDataFrame::from_args(self).with_separator('\t'),
-            )),
-            Some(Format::Parquet) => Ok(Box::new(ParquetToDataFrame)),
-            Some(Format::Json) => Ok(Box::new(JsonToDataFrame::from_args(self))),
-            Some(Format::Jsonl) => Ok(Box::new(JsonLineToDataFrame::from_args(self))),
-            Some(Format::Arrow) => Ok(Box::new(ArrowIpcToDataFrame)),
-            Some(Format::Fwf) => Ok(Box::new(FwfToDataFrame::from_args(self))),
-            Some(Format::Sqlite) => Ok(Box::new(SqliteToDataFrames::from_args(self))),
-            Some(Format::Excel) => Ok(Box::new(ExcelToDataFrames::from_args(self))),
-            Some(Format::Logfmt) => Ok(Box::new(LogfmtToDataFrame::from_args(self))),
-            Some(Format::Avro) => Ok(Box::new(AvroToDataFrame)),
-            Some(Format::Html) => Ok(Box::new(HtmlToDataFrame::from_args(self))),
-            Some(Format::Markdown) => Ok(Box::new(MarkdownToDataFrame::from_args(self))),
+    fn build_reader(&self, path: impl AsRef<Path>) -> AppResult<Arc<dyn Reader>> {
+        let format = match &self.format {
+            Some(format) => Some(format.clone()),
+            // stdin has no extension, its format is guessed from its contents
+            None if path.as_ref().as_os_str().is_empty() => sniff_format(stdin().get_ref()),
+            None => None,
+        };
+        let reader: Arc<dyn Reader> = match format {
+            Some(Format::Dsv) | Some(Format::Csv) => Arc::new(CsvToDataFrame::from_args(self)),
+            Some(Format::Tsv) => Arc::new(CsvToDataFrame::from_args(self).with_separator('\t')),
+            Some(Format::Parquet) => Arc::new(ParquetToDataFrame::from_args(self)),
+            Some(Format::Json) => Arc::new(JsonToDataFrame::from_args(self)),
+            Some(Format::Jsonl) => Arc::new(JsonLineToDataFrame::from_args(self)),
+            Some(Format::Arrow) => Arc::new(ArrowIpcToDataFrame::from_args(self)),
+            Some(Format::Fwf) => Arc::new(FwfToDataFrame::from_args(self)),
+            Some(Format::Sqlite) => Arc::new(SqliteToDataFrames::from_args(self)),
+            Some(Format::Excel) => Arc::new(ExcelToDataFrames::from_args(self)),
+            Some(Format::Logfmt) => Arc::new(LogfmtToDataFrame::from_args(self)),
+            Some(Format::Avro) => Arc::new(AvroToDataFrame),
+            Some(Format::Html) => Arc::new(HtmlToDataFrame::from_args(self)),
+            Some(Format::Markdown) => Arc::new(MarkdownToDataFrame::from_args(self)),
             None => match path.as_ref().extension().and_then(|ext| ext.to_str()) {
                 Some("tsv") => {
                     let reader = CsvToDataFrame::from_args(self).with_separator('\t');
-                    Ok(Box::new(reader))
+                    Arc::new(reader)
                 }
-                Some("parquet") | Some("pqt") => Ok(Box::new(ParquetToDataFrame)),
-                Some("json") => Ok(Box::new(JsonToDataFrame::from_args(self))),
-                Some("jsonl") => Ok(Box::new(JsonLineToDataFrame::from_args(self))),
-                Some("arrow") => Ok(Box::new(ArrowIpcToDataFrame)),
-                Some("avro") => Ok(Box::new(AvroToDataFrame)),
-                Some("fwf") => Ok(Box::new(FwfToDataFrame::from_args(self))),
-                Some("db") | Some("sqlite") => Ok(Box::new(SqliteToDataFrames::from_args(self))),
+                Some("parquet") | Some("pqt") => Arc::new(ParquetToDataFrame::from_args(self)),
+                Some("json") => Arc::new(JsonToDataFrame::from_args(self)),
+                Some("jsonl") => Arc::new(JsonLineToDataFrame::from_args(self)),
+                Some("arrow") => Arc::new(ArrowIpcToDataFrame::from_args(self)),
+                Some("avro") => Arc::new(AvroToDataFrame),
+                Some("fwf") => Arc::new(FwfToDataFrame::from_args(self)),
+                Some("db") | Some("sqlite") => Arc::new(SqliteToDataFrames::from_args(self)),
                 Some("xls") | Some("xlsx") | Some("xlsm") | Some("xlsb") => {
-                    Ok(Box::new(ExcelToDataFrames::from_args(self)))
+                    Arc::new(ExcelToDataFrames::from_args(self))
                 }
-                Some("html") | Some("htm") => Ok(Box::new(HtmlToDataFrame::from_args(self))),
-                Some("md") | Some("markdown") => Ok(Box::new(MarkdownToDataFrame::from_args(self))),
-                _ => Ok(Box::new(CsvToDataFrame::from_args(self))),
+                Some("html") | Some("htm") => Arc::new(HtmlToDataFrame::from_args(self)),
+                Some("md") | Some("markdown") => Arc::new(MarkdownToDataFrame::from_args(self)),
+                _ => Arc::new(CsvToDataFrame::from_args(self)),
             },
+        };
+        Ok(match (self.limit, self.sample) {
+            (Some(rows), _) => Arc::new(SelectRows::new(reader, RowSelection::Head(rows))),
+            (None, Some(rows)) => Arc::new(SelectRows::new(reader, RowSelection::Sample(rows))),
+            (None, None) => reader,
+        })
+    }
+}
+
+/// Format of binary data recognized by its magic bytes, or of JSON and HTML
+/// text by its first characters; `None` for anything else, read as CSV.
+fn sniff_format(contents: &[u8]) -> Option<Format> {
+    const MAGIC: [(&[u8], Format); 6] = [
+        (b"PAR1", Format::Parquet),
+        (b"ARROW1", Format::Arrow),
+        (b"Obj\x01", Format::Avro),
+        (b"SQLite format 3\0", Format::Sqlite),
+        (b"PK\x03\x04", Format::Excel),
+        (b"\xd0\xcf\x11\xe0", Format::Excel),
+    ];
+    if let Some((_, format)) = MAGIC.iter().find(|(magic, _)| contents.starts_with(magic)) {
+        return Some(format.clone());
+    }
+    let text = contents.trim_ascii_start();
+    match text.first()? {
+        b'[' => Some(Format::Json),
+        b'{' => {
+            let first_line = text.split(|byte| *byte == b'\n').next()?;
+            if first_line.trim_ascii_end().ends_with(b"}") {
+                Some(Format::Jsonl)
+            } else {
+                Some(Format::Json)
+            }
         }
+        b'<' => Some(Format::Html),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniffs_stdin_format() {
+        assert!(matches!(
+            sniff_format(b"PAR1\x15\x04"),
+            Some(Format::Parquet)
+        ));
+        assert!(matches!(sniff_format(b"ARROW1\0\0"), Some(Format::Arrow)));
+        assert!(matches!(
+            sniff_format(b"SQLite format 3\0"),
+            Some(Format::Sqlite)
+        ));
+        assert!(matches!(
+            sniff_format(b"  [{\"a\": 1}]"),
+            Some(Format::Json)
+        ));
+        assert!(matches!(
+            sniff_format(b"{\n  \"a\": 1\n}"),
+            Some(Format::Json)
+        ));
+        assert!(matches!(
+            sniff_format(b"{\"a\": 1}\n{\"a\": 2}\n"),
+            Some(Format::Jsonl)
+        ));
+        assert!(matches!(sniff_format(b"<table><tr>"), Some(Format::Html)));
+        assert!(sniff_format(b"a,b\n1,2\n").is_none());
+        assert!(sniff_format(b"").is_none());
     }
 }
@@ -25,7 +25,8 @@ impl WriteToFile for WriteToMarkdown {
     }
 }
 
-fn render_markdown(df: &DataFrame) -> String {
+/// Markdown table of the frame, with every row.
+pub fn render_markdown(df: &DataFrame) -> String {
     let columns = df.columns();
     let height = df.height();
 
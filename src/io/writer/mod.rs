@@ -10,6 +10,6 @@ pub use arrow::WriteToArrow;
 pub use avro::WriteToAvro;
 pub use csv::WriteToCsv;
 pub use json::{JsonFormat, WriteToJson};
-pub use markdown::WriteToMarkdown;
+pub use markdown::{WriteToMarkdown, render_markdown};
 pub use parquet::WriteToParquet;
-pub use traits::{Destination, WriteToFile};
+pub use traits::{Destination, WriteToFile, build_writer};
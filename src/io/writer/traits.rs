@@ -1,8 +1,14 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use polars::frame::DataFrame;
 
-use crate::AppResult;
+use crate::{
+    AppResult,
+    io::writer::{
+        JsonFormat, WriteToArrow, WriteToAvro, WriteToCsv, WriteToJson, WriteToMarkdown,
+        WriteToParquet,
+    },
+};
 
 #[derive(Debug, Clone)]
 pub enum Destination {
@@ -13,3 +19,21 @@ pub enum Destination {
 pub trait WriteToFile {
     fn write_to_file(&self, dest: Destination, data_frame: &mut DataFrame) -> AppResult<()>;
 }
+
+/// Writer of the format named by the extension of `path`, CSV by default.
+pub fn build_writer(path: impl AsRef<Path>) -> Box<dyn WriteToFile> {
+    match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+        Some("tsv") => Box::new(
+            WriteToCsv::default()
+                .with_separator_char('\t')
+                .with_header(true),
+        ),
+        Some("parquet") | Some("pqt") => Box::new(WriteToParquet),
+        Some("json") => Box::new(WriteToJson::default()),
+        Some("jsonl") => Box::new(WriteToJson::default().with_format(JsonFormat::JsonLine)),
+        Some("arrow") => Box::new(WriteToArrow),
+        Some("avro") => Box::new(WriteToAvro),
+        Some("md") | Some("markdown") => Box::new(WriteToMarkdown),
+        _ => Box::new(WriteToCsv::default().with_header(true)),
+    }
+}
@@ -1,22 +1,34 @@
-use clap::{CommandFactory, Parser};
+use anyhow::anyhow;
+use clap::{CommandFactory, FromArgMatches};
+use clap_complete::generate;
 use indexmap::IndexMap;
+use itertools::Itertools;
 use polars::frame::DataFrame;
-use polars::prelude::Schema;
+use polars::io::SerWriter;
+use polars::prelude::{Column, CsvWriter, Schema};
+use std::fmt::Write;
 use std::io::IsTerminal;
+use std::path::Path;
 use std::sync::Arc;
 use tabiew::app::App;
-use tabiew::args::Args;
+use tabiew::args::{Args, Command};
 use tabiew::handler::event::{Event, read_event};
 use tabiew::handler::message::Message;
 use tabiew::io::DataSource;
 use tabiew::io::reader::ReaderSource;
-use tabiew::io::reader::{BuildReader, NamedFrames};
+use tabiew::io::reader::{BuildReader, NamedFrame, NamedFrames, take_errors_table};
+use tabiew::io::writer::{Destination, build_writer, render_markdown};
 use tabiew::misc::config::config;
 use tabiew::misc::download::download_to_temp;
+use tabiew::misc::keybindings::keybindings;
 use tabiew::misc::osc52::flush_osc52_buffer;
-use tabiew::misc::sql::{TableSource, sql};
+use tabiew::misc::remote_load::Reader;
+use tabiew::misc::sql::{TableReader, TableSchema, TableSource, sql};
+use tabiew::misc::terminal_background;
+use tabiew::misc::transform;
 use tabiew::misc::type_ext::UnwrapOrGracefulShutdown;
 use tabiew::misc::type_inferer::TypeInferer;
+use tabiew::misc::workspace::Workspace;
 use tabiew::tui::component::{Component, FocusState};
 use tabiew::tui::pane::TableDescription;
 use tabiew::tui::terminal::{draw, start_tui, stop_tui};
@@ -26,17 +38,48 @@ use tabiew::tui::Pane;
 
 fn main() {
     // Parse CLI
-    let args = {
+    let (mut args, matches) = {
         let args_os = std::env::args_os();
         // Show help message if no arguments are given and stdin is not piped
         if args_os.len() == 1 && std::io::stdin().is_terminal() {
             return Args::command().print_help().unwrap_or_graceful_shutdown();
         } else {
-            Args::parse_from(args_os)
+            let matches = Args::command().get_matches_from(args_os);
+            let args = Args::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+            (args, matches)
         }
     };
 
     let _ = config().reload();
+    config().reader_defaults().apply(&mut args, &matches);
+
+    match &args.command {
+        Some(Command::Convert { input, output }) => {
+            return convert(&args, input, output).unwrap_or_graceful_shutdown();
+        }
+        Some(Command::Completions { shell }) => {
+            return generate(*shell, &mut Args::command(), "tw", &mut std::io::stdout());
+        }
+        None => (),
+    }
+
+    if let Some(theme) = &args.theme {
+        config().select_theme(theme).unwrap_or_graceful_shutdown();
+    }
+    // The terminal is only asked for its background when the TUI starts with
+    // the automatic theme
+    if config().auto_theme() && args.sql.is_none() && !args.describe {
+        terminal_background::detect();
+        config().select_theme("auto").unwrap_or_graceful_shutdown();
+    }
+    if let Some(path) = args
+        .keybindings
+        .clone()
+        .or_else(|| config().keybindings_path())
+    {
+        keybindings().set_path(path);
+    }
+    keybindings().reload().unwrap_or_graceful_shutdown();
 
     let type_infer = TypeInferer::from_args(&args);
 
@@ -46,7 +89,8 @@ fn main() {
     // Load multiparts to data frames
     let mut multiparts = IndexMap::<Arc<Schema>, (String, DataFrame)>::new();
     for resource in args.multiparts.iter() {
-        for (name, new_df) in try_read_path(&args, resource).unwrap_or_graceful_shutdown() {
+        let (_, frames) = try_read_path(&args, resource).unwrap_or_graceful_shutdown();
+        for (name, new_df) in frames {
             let schema = new_df.schema().clone();
             if let Some((_, df)) = multiparts.get_mut(&schema) {
                 df.vstack_mut_owned(new_df).unwrap_or_graceful_shutdown();
@@ -63,10 +107,22 @@ fn main() {
     }
 
     // Load files to data frames
+    let mut query_tabs = Vec::new();
     for resource in args.resources.iter() {
-        for (name, mut df) in try_read_path(&args, resource).unwrap_or_graceful_shutdown() {
+        if args.format.is_none()
+            && Workspace::is_workspace(resource)
+            && let DataSource::File(path) = resource
+        {
+            let (frames, tabs) = load_workspace(&args, path).unwrap_or_graceful_shutdown();
+            name_dfs.extend(frames);
+            query_tabs.extend(tabs);
+            continue;
+        }
+        let (reader, frames) = try_read_path(&args, resource).unwrap_or_graceful_shutdown();
+        for (frame, mut df) in frames {
             type_infer.update(&mut df);
-            let name = sql().register(&name, df.clone(), resource.clone());
+            let name = sql().register(&frame, df.clone(), resource.clone());
+            sql().set_reader(&name, TableReader::new(reader.clone(), frame, type_infer));
             name_dfs.push((name, df))
         }
     }
@@ -84,19 +140,28 @@ fn main() {
         }
     }
 
-    let _ = start_app(name_dfs);
-}
+    if let Some(query) = &args.sql {
+        return run_query(query, name_dfs, args.output.as_deref()).unwrap_or_graceful_shutdown();
+    }
 
-fn start_app(tabs: Vec<(String, DataFrame)>) -> AppResult<()> {
-    let tabs = tabs
-        .into_iter()
-        .map(|(name, df)| Pane::new(df, TableDescription::Table(name)))
-        .collect();
+    if args.describe {
+        return describe(&name_dfs).unwrap_or_graceful_shutdown();
+    }
+
+    let _ = start_app(
+        name_dfs
+            .into_iter()
+            .map(|(name, df)| Pane::new(df, TableDescription::Table(name)))
+            .chain(query_tabs)
+            .collect(),
+    );
+}
 
+fn start_app(tabs: Vec<Pane>) -> AppResult<()> {
     start_tui()?;
 
     // Initialize the app
-    let mut app = App::new(tabs);
+    let mut app = App::new(tabs.into_iter().collect());
 
     // Main loop
     while app.running() {
@@ -120,7 +185,9 @@ fn start_app(tabs: Vec<(String, DataFrame)>) -> AppResult<()> {
                     app.handle(key_event);
                 }
             }
-            Event::Mouse(_) => {}
+            Event::Mouse(mouse_event) => {
+                app.handle_mouse(mouse_event);
+            }
             Event::Resize(_, _) => {}
             Event::FocusGained => {}
             Event::FocusLost => {}
@@ -138,18 +205,156 @@ fn start_app(tabs: Vec<(String, DataFrame)>) -> AppResult<()> {
     Ok(())
 }
 
-fn try_read_path(args: &Args, resource: &DataSource) -> AppResult<NamedFrames> {
+/// Runs `query` on the loaded tables, with the first one as `_`, and writes the
+/// result to `output` (or stdout, as CSV) in place of the TUI.
+fn run_query(
+    query: &str,
+    name_dfs: Vec<(String, DataFrame)>,
+    output: Option<&Path>,
+) -> AppResult<()> {
+    let default = name_dfs.into_iter().next().map(|(_, df)| df);
+    let mut df = sql().execute(query, default)?;
+    match output {
+        Some(path) => build_writer(path).write_to_file(Destination::File(path.to_owned()), &mut df),
+        None => Ok(CsvWriter::new(std::io::stdout().lock())
+            .include_header(true)
+            .finish(&mut df)?),
+    }
+}
+
+/// Prints the schema of every table, with the null count and range of each
+/// column, followed by the statistics of its numeric columns.
+fn describe(name_dfs: &[(String, DataFrame)]) -> AppResult<()> {
+    let mut out = String::new();
+    for (name, df) in name_dfs {
+        let schema = TableSchema::new(df);
+        let fields = DataFrame::new_infer_height(vec![
+            Column::new(
+                "column".into(),
+                schema.iter().map(|(name, _)| name.as_str()).collect_vec(),
+            ),
+            Column::new(
+                "dtype".into(),
+                schema
+                    .iter()
+                    .map(|(_, info)| info.dtype().to_string())
+                    .collect_vec(),
+            ),
+            Column::new(
+                "nulls".into(),
+                schema
+                    .iter()
+                    .map(|(_, info)| info.null_count() as u64)
+                    .collect_vec(),
+            ),
+            Column::new(
+                "min".into(),
+                schema.iter().map(|(_, info)| info.min()).collect_vec(),
+            ),
+            Column::new(
+                "max".into(),
+                schema.iter().map(|(_, info)| info.max()).collect_vec(),
+            ),
+        ])?;
+        writeln!(
+            out,
+            "## {name} ({} rows, {} columns)\n",
+            df.height(),
+            df.width()
+        )?;
+        out.push_str(&render_markdown(&fields));
+        // Frames without numeric columns have no statistics to list.
+        if df
+            .columns()
+            .iter()
+            .any(|column| column.dtype().is_numeric())
+        {
+            let stats = transform::describe(df)?;
+            out.push('\n');
+            out.push_str(&render_markdown(&stats));
+        }
+        out.push('\n');
+    }
+    print!("{out}");
+    Ok(())
+}
+
+/// Reads the single table of `input` and writes it to `output`, in the format
+/// of its extension.
+fn convert(args: &Args, input: &DataSource, output: &Path) -> AppResult<()> {
+    let (_, frames) = try_read_path(args, input)?;
+    let mut frames = frames.into_vec();
+    if let Some((_, errors)) = take_errors_table(&mut frames) {
+        eprintln!(
+            "{} malformed row(s) of {} left out",
+            errors.height(),
+            input.display_path()
+        );
+    }
+    let [(_, df)] = frames.as_mut_slice() else {
+        return Err(anyhow!(
+            "{} holds several tables; open it and export one instead",
+            input.display_path()
+        ));
+    };
+    TypeInferer::from_args(args).update(df);
+    build_writer(output).write_to_file(Destination::File(output.to_owned()), df)
+}
+
+/// Registers the sources of the workspace at `path`, returning them with the
+/// tabs of its queries.
+fn load_workspace(args: &Args, path: &Path) -> AppResult<(Vec<NamedFrame>, Vec<Pane>)> {
+    let workspace = Workspace::from_path(path)?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let mut name_dfs = Vec::new();
+    for source in workspace.sources.iter() {
+        let args = source.args(args);
+        let resource = source.data_source(dir)?;
+        let type_infer = TypeInferer::from_args(&args);
+        let (reader, frames) = try_read_path(&args, &resource)?;
+        let single = frames.len() == 1;
+        for (frame, mut df) in frames {
+            type_infer.update(&mut df);
+            let name = match &source.name {
+                Some(preferred) if single => preferred,
+                _ => &frame,
+            };
+            let name = sql().register(name, df.clone(), resource.clone());
+            sql().set_reader(&name, TableReader::new(reader.clone(), frame, type_infer));
+            name_dfs.push((name, df));
+        }
+    }
+    let mut tabs = Vec::new();
+    for query in workspace.queries.iter() {
+        let df = sql().execute(&query.sql, None)?;
+        tabs.push(match &query.name {
+            Some(name) => {
+                let name = sql().register(name, df.clone(), TableSource::User);
+                Pane::new(df, TableDescription::Table(name))
+            }
+            None => Pane::new(df, TableDescription::Query(query.sql.to_owned())),
+        });
+    }
+    Ok((name_dfs, tabs))
+}
+
+fn try_read_path(args: &Args, resource: &DataSource) -> AppResult<(Arc<dyn Reader>, NamedFrames)> {
     match resource {
-        DataSource::Stdin => args
-            .build_reader("")?
-            .read_to_data_frames(ReaderSource::Stdin),
-        DataSource::File(path_buf) => args
-            .build_reader(path_buf)?
-            .read_to_data_frames(ReaderSource::File(path_buf.clone())),
+        DataSource::Stdin => {
+            let reader = args.build_reader("")?;
+            let frames = reader.read_to_data_frames(ReaderSource::Stdin)?;
+            Ok((reader, frames))
+        }
+        DataSource::File(path_buf) => {
+            let reader = args.build_reader(path_buf)?;
+            let frames = reader.read_to_data_frames(ReaderSource::File(path_buf.clone()))?;
+            Ok((reader, frames))
+        }
         DataSource::Url(url) => {
             let file = download_to_temp(url)?;
-            args.build_reader(file.path())?
-                .read_to_data_frames(ReaderSource::File(file.path().to_owned()))
+            let reader = args.build_reader(file.path())?;
+            let frames = reader.read_to_data_frames(ReaderSource::File(file.path().to_owned()))?;
+            Ok((reader, frames))
         }
     }
 }
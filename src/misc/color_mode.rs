@@ -0,0 +1,204 @@
+use std::sync::OnceLock;
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::misc::config::theme;
+
+/// Colors the terminal can draw; the frame is degraded to them before it is
+/// drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    /// Detected from `NO_COLOR`, `COLORTERM`, and `TERM`.
+    #[default]
+    Auto,
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    /// No colors, with the highlighted cells drawn reversed instead.
+    None,
+}
+
+impl ColorMode {
+    /// Resolves `Auto` from the environment.
+    pub fn resolve(self) -> ColorMode {
+        static DETECTED: OnceLock<ColorMode> = OnceLock::new();
+        match self {
+            ColorMode::Auto => *DETECTED.get_or_init(|| {
+                let var = |name| std::env::var(name).ok();
+                detect(
+                    var("NO_COLOR").as_deref(),
+                    var("COLORTERM").as_deref(),
+                    var("TERM").as_deref(),
+                )
+            }),
+            mode => mode,
+        }
+    }
+}
+
+fn detect(no_color: Option<&str>, colorterm: Option<&str>, term: Option<&str>) -> ColorMode {
+    if no_color.is_some_and(|value| !value.is_empty()) || term == Some("dumb") {
+        ColorMode::None
+    } else if matches!(colorterm, Some("truecolor" | "24bit")) {
+        ColorMode::TrueColor
+    } else if term.is_some_and(|term| term.contains("256color")) {
+        ColorMode::Ansi256
+    } else {
+        ColorMode::Ansi16
+    }
+}
+
+/// Replaces the colors of the frame with the ones `mode` can draw.
+pub fn degrade(buf: &mut Buffer, mode: ColorMode) {
+    match mode.resolve() {
+        ColorMode::Auto | ColorMode::TrueColor => (),
+        ColorMode::Ansi256 => buf.content.iter_mut().for_each(|cell| {
+            cell.fg = to_ansi256(cell.fg);
+            cell.bg = to_ansi256(cell.bg);
+        }),
+        ColorMode::Ansi16 => buf.content.iter_mut().for_each(|cell| {
+            cell.fg = to_ansi16(cell.fg);
+            cell.bg = to_ansi16(cell.bg);
+        }),
+        ColorMode::None => {
+            // backgrounds of the plain text, rows, and blocks; any other marks a highlight
+            let plain = [
+                theme().text().bg,
+                theme().row(0).bg,
+                theme().row(1).bg,
+                theme().table_header().bg,
+                theme().block().bg,
+            ];
+            buf.content.iter_mut().for_each(|cell| {
+                if cell.bg != Color::Reset && !plain.contains(&Some(cell.bg)) {
+                    cell.modifier.insert(Modifier::REVERSED);
+                }
+                cell.fg = Color::Reset;
+                cell.bg = Color::Reset;
+            });
+        }
+    }
+}
+
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Levels of the 6x6x6 color cube of the 256-color palette.
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn to_ansi256(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    let level = |value: u8| {
+        (0..CUBE.len())
+            .min_by_key(|idx| CUBE[*idx].abs_diff(value))
+            .unwrap_or_default() as u8
+    };
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+    // the grays run from 8 to 238 in steps of 10
+    let average = (r as u16 + g as u16 + b as u16) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+    Color::Indexed(
+        [cube, gray]
+            .into_iter()
+            .min_by_key(|idx| distance(indexed_rgb(*idx), (r, g, b)))
+            .unwrap_or(cube),
+    )
+}
+
+fn to_ansi16(color: Color) -> Color {
+    let rgb = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(idx) => indexed_rgb(idx),
+        color => return color,
+    };
+    ANSI16
+        .iter()
+        .min_by_key(|(_, ansi)| distance(*ansi, rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(color)
+}
+
+fn indexed_rgb(idx: u8) -> (u8, u8, u8) {
+    match idx {
+        0..16 => ANSI16[idx as usize].1,
+        16..232 => {
+            let idx = idx - 16;
+            (
+                CUBE[idx as usize / 36],
+                CUBE[idx as usize / 6 % 6],
+                CUBE[idx as usize % 6],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (idx - 232);
+            (level, level, level)
+        }
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    [(a.0, b.0), (a.1, b.1), (a.2, b.2)]
+        .into_iter()
+        .map(|(a, b)| (a.abs_diff(b) as u32).pow(2))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_color_mode() {
+        assert_eq!(detect(Some("1"), Some("truecolor"), None), ColorMode::None);
+        assert_eq!(detect(None, None, Some("dumb")), ColorMode::None);
+        assert_eq!(
+            detect(Some(""), Some("24bit"), Some("xterm")),
+            ColorMode::TrueColor
+        );
+        assert_eq!(
+            detect(None, None, Some("xterm-256color")),
+            ColorMode::Ansi256
+        );
+        assert_eq!(detect(None, None, Some("vt100")), ColorMode::Ansi16);
+        assert_eq!(detect(None, None, None), ColorMode::Ansi16);
+    }
+
+    #[test]
+    fn test_degrades_to_256_colors() {
+        assert_eq!(to_ansi256(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(to_ansi256(Color::Rgb(0, 0, 0)), Color::Indexed(16));
+        assert_eq!(to_ansi256(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+        assert_eq!(to_ansi256(Color::Green), Color::Green);
+    }
+
+    #[test]
+    fn test_degrades_to_16_colors() {
+        assert_eq!(to_ansi16(Color::Rgb(250, 10, 10)), Color::LightRed);
+        assert_eq!(to_ansi16(Color::Rgb(30, 30, 30)), Color::Black);
+        assert_eq!(to_ansi16(Color::Indexed(46)), Color::LightGreen);
+        assert_eq!(to_ansi16(Color::Reset), Color::Reset);
+    }
+}
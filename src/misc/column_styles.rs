@@ -0,0 +1,195 @@
+use polars::prelude::DataType;
+use ratatui::style::{Color, Modifier, Style};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{AppResult, misc::transform::ColumnPattern};
+
+/// Config rule styling the values of the columns matching a name (or glob, or
+/// `/regex/`) and a data type group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "ColumnStyleRule", into = "ColumnStyleRule")]
+pub struct ColumnStyle {
+    rule: ColumnStyleRule,
+    regex: Option<Regex>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ColumnStyleRule {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dtype: Option<DtypeGroup>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fg: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bg: Option<Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underlined: bool,
+}
+
+impl ColumnStyleRule {
+    fn style(&self) -> Style {
+        let mut style = Style::new();
+        style.fg = self.fg;
+        style.bg = self.bg;
+        [
+            (self.bold, Modifier::BOLD),
+            (self.dim, Modifier::DIM),
+            (self.italic, Modifier::ITALIC),
+            (self.underlined, Modifier::UNDERLINED),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .fold(style, |style, (_, modifier)| style.add_modifier(modifier))
+    }
+}
+
+/// Data types a column style rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DtypeGroup {
+    Integer,
+    Float,
+    Numeric,
+    String,
+    Boolean,
+    Date,
+    Datetime,
+    Temporal,
+    Nested,
+}
+
+impl DtypeGroup {
+    fn contains(self, dtype: &DataType) -> bool {
+        match self {
+            DtypeGroup::Integer => dtype.is_integer(),
+            DtypeGroup::Float => dtype.is_float(),
+            DtypeGroup::Numeric => dtype.is_numeric(),
+            DtypeGroup::String => dtype.is_string(),
+            DtypeGroup::Boolean => dtype.is_bool(),
+            DtypeGroup::Date => matches!(dtype, DataType::Date),
+            DtypeGroup::Datetime => matches!(dtype, DataType::Datetime(_, _)),
+            DtypeGroup::Temporal => dtype.is_temporal(),
+            DtypeGroup::Nested => dtype.is_nested(),
+        }
+    }
+}
+
+impl ColumnStyle {
+    fn matches(&self, name: &str, dtype: &DataType) -> bool {
+        self.regex.as_ref().is_none_or(|regex| regex.is_match(name))
+            && self.rule.dtype.is_none_or(|group| group.contains(dtype))
+    }
+}
+
+impl TryFrom<ColumnStyleRule> for ColumnStyle {
+    type Error = anyhow::Error;
+
+    fn try_from(rule: ColumnStyleRule) -> AppResult<Self> {
+        let regex = match rule.column.as_deref() {
+            Some(column) => Some(match ColumnPattern::from(column).to_regex()? {
+                Some(regex) => regex,
+                None => Regex::new(&format!("^{}$", regex::escape(column)))?,
+            }),
+            None => None,
+        };
+        Ok(ColumnStyle { rule, regex })
+    }
+}
+
+impl From<ColumnStyle> for ColumnStyleRule {
+    fn from(value: ColumnStyle) -> Self {
+        value.rule
+    }
+}
+
+/// Style of the values of a column: every matching rule, later ones on top.
+pub fn column_style(rules: &[ColumnStyle], name: &str, dtype: &DataType) -> Style {
+    rules
+        .iter()
+        .filter(|rule| rule.matches(name, dtype))
+        .fold(Style::default(), |style, rule| {
+            style.patch(rule.rule.style())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(toml: &str) -> Vec<ColumnStyle> {
+        #[derive(Deserialize)]
+        struct Rules {
+            column_styles: Vec<ColumnStyle>,
+        }
+        toml::from_str::<Rules>(toml).unwrap().column_styles
+    }
+
+    #[test]
+    fn test_matches_names_and_dtypes() {
+        let rules = rules(
+            r#"
+            [[column_styles]]
+            column = "*_id"
+            dim = true
+
+            [[column_styles]]
+            column = "/^(price|cost)$/"
+            dtype = "numeric"
+            fg = "Green"
+
+            [[column_styles]]
+            dtype = "date"
+            fg = "Cyan"
+            "#,
+        );
+        assert_eq!(
+            column_style(&rules, "user_id", &DataType::Int64),
+            Style::new().add_modifier(Modifier::DIM)
+        );
+        assert_eq!(
+            column_style(&rules, "price", &DataType::Float64),
+            Style::new().fg(Color::Green)
+        );
+        assert_eq!(
+            column_style(&rules, "price", &DataType::String),
+            Style::new()
+        );
+        assert_eq!(
+            column_style(&rules, "created", &DataType::Date),
+            Style::new().fg(Color::Cyan)
+        );
+        assert_eq!(column_style(&rules, "user", &DataType::Int64), Style::new());
+    }
+
+    #[test]
+    fn test_later_rules_take_precedence() {
+        let rules = rules(
+            r#"
+            [[column_styles]]
+            column = "total"
+            fg = "Green"
+            bold = true
+
+            [[column_styles]]
+            column = "total"
+            fg = "Red"
+            "#,
+        );
+        assert_eq!(
+            column_style(&rules, "total", &DataType::Float64),
+            Style::new().fg(Color::Red).add_modifier(Modifier::BOLD)
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_rules() {
+        assert!(toml::from_str::<ColumnStyle>("column = \"/(/\"").is_err());
+        assert!(toml::from_str::<ColumnStyle>("dtype = \"money\"").is_err());
+        assert!(toml::from_str::<ColumnStyle>("colour = \"red\"").is_err());
+    }
+}
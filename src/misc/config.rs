@@ -1,18 +1,32 @@
 use std::{
     fs,
     ops::Deref,
+    path::PathBuf,
     sync::{
         OnceLock, RwLock,
         atomic::{AtomicBool, Ordering},
     },
 };
 
+use polars::prelude::DataType;
+use ratatui::style::Style;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     AppResult,
-    misc::{http::HttpConfig, paths::config_path},
-    tui::themes::theme::LoadedTheme,
+    misc::{
+        color_mode::ColorMode,
+        column_styles::{ColumnStyle, column_style},
+        http::HttpConfig,
+        paths::config_path,
+        reader_defaults::ReaderDefaults,
+        terminal_background::{Background, background},
+        value_format::{FormatConfig, ValueFormat},
+    },
+    tui::{
+        table::RowsConfig,
+        themes::theme::{LoadedTheme, Theme},
+    },
 };
 
 use super::type_ext::UnwrapOrGracefulShutdown;
@@ -21,9 +35,23 @@ use super::type_ext::UnwrapOrGracefulShutdown;
 #[serde(default)]
 pub struct Config {
     theme: RwLock<LoadedTheme>,
+    auto_theme: AtomicBool,
+    light_theme: RwLock<LoadedTheme>,
+    dark_theme: RwLock<LoadedTheme>,
     http: RwLock<HttpConfig>,
     show_table_borders: AtomicBool,
     show_table_row_numbers: AtomicBool,
+    show_local_time: AtomicBool,
+    show_null_marker: AtomicBool,
+    null_marker: RwLock<String>,
+    show_dtype_badges: AtomicBool,
+    column_styles: RwLock<Vec<ColumnStyle>>,
+    color_mode: RwLock<ColorMode>,
+    rows: RwLock<RowsConfig>,
+    format: RwLock<FormatConfig>,
+    reader: RwLock<ReaderDefaults>,
+    /// Keybindings file read instead of the default one, unless `--keybindings` is given.
+    keybindings: RwLock<Option<PathBuf>>,
 }
 
 impl Config {
@@ -32,16 +60,49 @@ impl Config {
         let contents = fs::read_to_string(path)?;
         let Config {
             theme,
+            auto_theme,
+            light_theme,
+            dark_theme,
             http,
             show_table_borders: table_borders,
             show_table_row_numbers: table_row_numbers,
+            show_local_time: local_time,
+            show_null_marker: null_marker_visible,
+            null_marker,
+            show_dtype_badges: dtype_badges,
+            column_styles,
+            color_mode,
+            rows,
+            format,
+            reader,
+            keybindings,
         } = toml::from_str(&contents)?;
         self.set_theme(theme.into_inner()?);
+        self.auto_theme
+            .swap(auto_theme.into_inner(), Ordering::Relaxed);
+        *self.light_theme.write().unwrap_or_graceful_shutdown() = light_theme.into_inner()?;
+        *self.dark_theme.write().unwrap_or_graceful_shutdown() = dark_theme.into_inner()?;
+        if self.auto_theme() {
+            self.apply_auto_theme();
+        }
         self.set_http_config(http.into_inner()?);
         self.show_table_borders
             .swap(table_borders.into_inner(), Ordering::Relaxed);
         self.show_table_row_numbers
             .swap(table_row_numbers.into_inner(), Ordering::Relaxed);
+        self.show_local_time
+            .swap(local_time.into_inner(), Ordering::Relaxed);
+        self.show_null_marker
+            .swap(null_marker_visible.into_inner(), Ordering::Relaxed);
+        *self.null_marker.write().unwrap_or_graceful_shutdown() = null_marker.into_inner()?;
+        self.show_dtype_badges
+            .swap(dtype_badges.into_inner(), Ordering::Relaxed);
+        *self.column_styles.write().unwrap_or_graceful_shutdown() = column_styles.into_inner()?;
+        *self.color_mode.write().unwrap_or_graceful_shutdown() = color_mode.into_inner()?;
+        *self.rows.write().unwrap_or_graceful_shutdown() = rows.into_inner()?;
+        *self.format.write().unwrap_or_graceful_shutdown() = format.into_inner()?;
+        *self.reader.write().unwrap_or_graceful_shutdown() = reader.into_inner()?;
+        *self.keybindings.write().unwrap_or_graceful_shutdown() = keybindings.into_inner()?;
         Ok(())
     }
 
@@ -62,6 +123,40 @@ impl Config {
         *self.theme.write().unwrap_or_graceful_shutdown() = theme.into();
     }
 
+    /// Whether the theme follows the terminal background, switching between the
+    /// light and the dark theme.
+    pub fn auto_theme(&self) -> bool {
+        self.auto_theme.load(Ordering::Relaxed)
+    }
+
+    /// Selects a theme by name or file, or follows the terminal background for `auto`.
+    pub fn select_theme(&self, name: &str) -> AppResult<()> {
+        if name.eq_ignore_ascii_case("auto") {
+            self.set_auto_theme(true);
+            self.apply_auto_theme();
+        } else {
+            self.set_theme(name.parse::<LoadedTheme>()?);
+            self.set_auto_theme(false);
+        }
+        Ok(())
+    }
+
+    pub fn set_auto_theme(&self, auto: bool) {
+        self.auto_theme.store(auto, Ordering::Relaxed);
+    }
+
+    /// Uses the light or the dark theme for the terminal background, the dark
+    /// one when the terminal did not report its background.
+    fn apply_auto_theme(&self) {
+        let theme = match background() {
+            Some(Background::Light) => self.light_theme.read(),
+            Some(Background::Dark) | None => self.dark_theme.read(),
+        }
+        .unwrap_or_graceful_shutdown()
+        .clone();
+        self.set_theme(theme);
+    }
+
     pub fn http_config(&self) -> impl Deref<Target = HttpConfig> {
         self.http.read().unwrap_or_graceful_shutdown()
     }
@@ -86,14 +181,101 @@ impl Config {
         self.show_table_row_numbers
             .fetch_xor(true, Ordering::Relaxed);
     }
+
+    pub fn show_local_time(&self) -> bool {
+        self.show_local_time.load(Ordering::Relaxed)
+    }
+
+    pub fn toggle_show_local_time(&self) {
+        self.show_local_time.fetch_xor(true, Ordering::Relaxed);
+    }
+
+    pub fn show_null_marker(&self) -> bool {
+        self.show_null_marker.load(Ordering::Relaxed)
+    }
+
+    pub fn toggle_show_null_marker(&self) {
+        self.show_null_marker.fetch_xor(true, Ordering::Relaxed);
+    }
+
+    /// Text shown in place of null values when the marker is visible.
+    pub fn null_marker(&self) -> impl Deref<Target = String> {
+        self.null_marker.read().unwrap_or_graceful_shutdown()
+    }
+
+    /// Whether column headers carry a badge with the column data type.
+    pub fn show_dtype_badges(&self) -> bool {
+        self.show_dtype_badges.load(Ordering::Relaxed)
+    }
+
+    pub fn toggle_show_dtype_badges(&self) {
+        self.show_dtype_badges.fetch_xor(true, Ordering::Relaxed);
+    }
+
+    pub fn rows_config(&self) -> impl Deref<Target = RowsConfig> {
+        self.rows.read().unwrap_or_graceful_shutdown()
+    }
+
+    pub fn toggle_striped_rows(&self) {
+        let mut rows = self.rows.write().unwrap_or_graceful_shutdown();
+        rows.striped = !rows.striped;
+    }
+
+    /// Display options of the values of a column, from the `[format]` section.
+    pub fn value_format(&self, column: &str) -> ValueFormat {
+        self.format
+            .read()
+            .unwrap_or_graceful_shutdown()
+            .column(column)
+    }
+
+    /// Reader options used when the command line does not give them.
+    pub fn reader_defaults(&self) -> ReaderDefaults {
+        self.reader.read().unwrap_or_graceful_shutdown().clone()
+    }
+
+    /// Keybindings file set in the config, if any.
+    pub fn keybindings_path(&self) -> Option<PathBuf> {
+        self.keybindings
+            .read()
+            .unwrap_or_graceful_shutdown()
+            .clone()
+    }
+
+    /// Colors the frame is degraded to before it is drawn.
+    pub fn color_mode(&self) -> ColorMode {
+        *self.color_mode.read().unwrap_or_graceful_shutdown()
+    }
+
+    /// Style the `column_styles` rules give to the values of a column.
+    pub fn column_style(&self, name: &str, dtype: &DataType) -> Style {
+        column_style(
+            &self.column_styles.read().unwrap_or_graceful_shutdown(),
+            name,
+            dtype,
+        )
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             theme: RwLock::new(LoadedTheme::default()),
+            auto_theme: AtomicBool::new(false),
+            light_theme: RwLock::new(Theme::GithubLightDefault.into()),
+            dark_theme: RwLock::new(LoadedTheme::default()),
             show_table_borders: AtomicBool::new(true),
             show_table_row_numbers: AtomicBool::new(true),
+            show_local_time: AtomicBool::new(false),
+            show_null_marker: AtomicBool::new(true),
+            null_marker: RwLock::new("\u{2205}".to_owned()),
+            show_dtype_badges: AtomicBool::new(false),
+            column_styles: RwLock::new(Vec::new()),
+            color_mode: RwLock::new(ColorMode::default()),
+            rows: RwLock::new(RowsConfig::default()),
+            format: RwLock::new(FormatConfig::default()),
+            reader: RwLock::new(ReaderDefaults::default()),
+            keybindings: RwLock::new(None),
             http: RwLock::new(HttpConfig::default()),
         }
     }
@@ -1,4 +1,4 @@
-use std::{env, process::Command};
+use std::{env, fs, path::Path, process::Command};
 
 use anyhow::anyhow;
 use polars::frame::DataFrame;
@@ -16,7 +16,6 @@ use crate::{
 };
 
 pub fn edit_in_external_editor(mut df: DataFrame) -> AppResult<DataFrame> {
-    let editor = env::var("EDITOR").map_err(|_| anyhow!("$EDITOR is not set"))?;
     let tempfile = tempfile::NamedTempFile::new()?;
 
     WriteToCsv::default()
@@ -25,17 +24,7 @@ pub fn edit_in_external_editor(mut df: DataFrame) -> AppResult<DataFrame> {
         .with_separator_char(',')
         .write_to_file(Destination::File(tempfile.path().to_owned()), &mut df)?;
 
-    let editor_status = {
-        disable_event_read();
-        stop_tui()?;
-        let status = Command::new(editor).arg(tempfile.path()).status();
-        start_tui()?;
-        invalidate_tui();
-        enable_event_read();
-        status
-    }?;
-
-    if editor_status.success() {
+    if run_editor(tempfile.path())? {
         let mut df = CsvToDataFrame::default()
             .with_no_header(false)
             .with_quote_char('"')
@@ -52,3 +41,38 @@ pub fn edit_in_external_editor(mut df: DataFrame) -> AppResult<DataFrame> {
         Err(anyhow!("Editor failed"))
     }
 }
+
+/// Opens `text` in `$EDITOR`, returning the edited text, or `None` when it was
+/// left unchanged. The newline most editors add at the end is dropped.
+pub fn edit_text_in_external_editor(text: &str) -> AppResult<Option<String>> {
+    let tempfile = tempfile::NamedTempFile::new()?;
+    fs::write(tempfile.path(), text)?;
+
+    if run_editor(tempfile.path())? {
+        let mut edited = fs::read_to_string(tempfile.path())?;
+        if !text.ends_with('\n') && edited.ends_with('\n') {
+            edited.pop();
+            if edited.ends_with('\r') {
+                edited.pop();
+            }
+        }
+        Ok((edited != text).then_some(edited))
+    } else {
+        Err(anyhow!("Editor failed"))
+    }
+}
+
+/// Runs `$EDITOR` on `path` with the TUI suspended, returning whether it succeeded.
+fn run_editor(path: &Path) -> AppResult<bool> {
+    let editor = env::var("EDITOR").map_err(|_| anyhow!("$EDITOR is not set"))?;
+    let status = {
+        disable_event_read();
+        stop_tui()?;
+        let status = Command::new(editor).arg(path).status();
+        start_tui()?;
+        invalidate_tui();
+        enable_event_read();
+        status
+    }?;
+    Ok(status.success())
+}
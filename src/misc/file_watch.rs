@@ -0,0 +1,93 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{Receiver, channel},
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use polars::frame::DataFrame;
+
+use crate::{AppResult, io::reader::ReaderSource, misc::sql::TableReader};
+
+/// Time between two checks of a watched file.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// File checked for changes in a background thread, which reads it again with
+/// the reader of its table whenever it changes.
+#[derive(Debug)]
+pub struct FileWatch {
+    path: PathBuf,
+    reloads: Receiver<AppResult<DataFrame>>,
+    /// Frame read after the latest change, until it is taken.
+    reloaded: Option<DataFrame>,
+    alive: Arc<AtomicBool>,
+}
+
+impl FileWatch {
+    pub fn new(path: PathBuf, reader: TableReader) -> Self {
+        let (sender, reloads) = channel();
+        let alive = Arc::new(AtomicBool::new(true));
+        let watching = alive.clone();
+        let watched = path.clone();
+        thread::spawn(move || {
+            let mut modified = modified_time(&watched);
+            loop {
+                thread::sleep(WATCH_INTERVAL);
+                if !watching.load(Ordering::Relaxed) {
+                    break;
+                }
+                let current = modified_time(&watched);
+                if current.is_none() || current == modified {
+                    continue;
+                }
+                modified = current;
+                let df = reader.read(ReaderSource::File(watched.clone()));
+                if sender.send(df).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            path,
+            reloads,
+            reloaded: None,
+            alive,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Keeps the latest frame read since the last call, returning whether there
+    /// was one, or the error of a failed read.
+    pub fn update(&mut self) -> AppResult<bool> {
+        let mut updated = false;
+        for df in self.reloads.try_iter() {
+            self.reloaded = Some(df?);
+            updated = true;
+        }
+        Ok(updated)
+    }
+
+    /// Frame read after the latest change, when it was not taken yet.
+    pub fn take_reloaded(&mut self) -> Option<DataFrame> {
+        self.reloaded.take()
+    }
+}
+
+impl Drop for FileWatch {
+    fn drop(&mut self) {
+        self.alive.store(false, Ordering::Relaxed);
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -0,0 +1,232 @@
+use std::{
+    env,
+    io::Write,
+    sync::{LazyLock, Mutex},
+};
+
+use base64::Engine;
+use ratatui::layout::Rect;
+
+use super::{png, sixel, type_ext::UnwrapOrGracefulShutdown};
+
+/// Terminal graphics protocol used to draw images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Iterm,
+    Sixel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+}
+
+impl ImageFormat {
+    /// Format of an image by its magic bytes.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(ImageFormat::Png)
+        } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+            Some(ImageFormat::Jpeg)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "PNG",
+            ImageFormat::Jpeg => "JPEG",
+        }
+    }
+}
+
+/// Graphics protocol of the terminal, guessed from its environment variables.
+pub fn protocol() -> Option<GraphicsProtocol> {
+    static PROTOCOL: LazyLock<Option<GraphicsProtocol>> = LazyLock::new(|| {
+        let term = env::var("TERM").unwrap_or_default();
+        let program = env::var("TERM_PROGRAM").unwrap_or_default();
+        if env::var_os("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" || program == "ghostty"
+        {
+            Some(GraphicsProtocol::Kitty)
+        } else if matches!(program.as_str(), "iTerm.app" | "WezTerm") {
+            Some(GraphicsProtocol::Iterm)
+        } else if matches!(term.as_str(), "foot" | "foot-extra" | "mlterm" | "contour")
+            || term.contains("sixel")
+            || matches!(program.as_str(), "mintty" | "contour")
+            || env::var_os("KONSOLE_VERSION").is_some()
+        {
+            Some(GraphicsProtocol::Sixel)
+        } else {
+            None
+        }
+    });
+    *PROTOCOL
+}
+
+/// Whether the terminal can draw an image in `format`; kitty takes PNG only,
+/// and sixel images are decoded from PNG only.
+pub fn supports(format: ImageFormat) -> bool {
+    match protocol() {
+        Some(GraphicsProtocol::Kitty | GraphicsProtocol::Sixel) => format == ImageFormat::Png,
+        Some(GraphicsProtocol::Iterm) => true,
+        None => false,
+    }
+}
+
+#[derive(Debug, Default)]
+struct Images {
+    /// Image to draw after the current frame.
+    pending: Option<(Rect, Vec<u8>)>,
+    /// Image on the screen.
+    shown: Option<(Rect, Vec<u8>)>,
+}
+
+static IMAGES: LazyLock<Mutex<Images>> = LazyLock::new(Default::default);
+
+/// Draws `bytes` over `area` once the current frame is on the screen.
+pub fn show_image(area: Rect, bytes: &[u8]) {
+    IMAGES.lock().unwrap_or_graceful_shutdown().pending = Some((area, bytes.to_vec()));
+}
+
+/// Writes the image requested while rendering the frame, or removes the one
+/// on the screen when none was; `cleared` tells the screen was cleared before
+/// the frame. Returns whether the screen has to be redrawn.
+pub fn flush_images(cleared: bool) -> bool {
+    let Some(protocol) = protocol() else {
+        return false;
+    };
+    let mut images = IMAGES.lock().unwrap_or_graceful_shutdown();
+    if cleared {
+        images.shown = None;
+    }
+    let pending = images.pending.take();
+    if pending == images.shown {
+        return false;
+    }
+    let mut stdout = std::io::stdout();
+    let redraw = images.shown.is_some();
+    if redraw && protocol == GraphicsProtocol::Kitty {
+        let _ = write!(stdout, "\x1b_Ga=d\x1b\\");
+    }
+    if let Some((area, bytes)) = &pending {
+        let _ = write!(
+            stdout,
+            "\x1b7\x1b[{};{}H{}\x1b8",
+            area.y + 1,
+            area.x + 1,
+            escape(protocol, bytes, area.width, area.height)
+        );
+    }
+    let _ = stdout.flush();
+    images.shown = pending;
+    redraw
+}
+
+/// Escape sequence drawing `bytes` within `cols` x `rows` cells at the cursor.
+fn escape(protocol: GraphicsProtocol, bytes: &[u8], cols: u16, rows: u16) -> String {
+    let encoded = || base64::engine::general_purpose::STANDARD.encode(bytes);
+    match protocol {
+        GraphicsProtocol::Iterm => format!(
+            "\x1b]1337;File=inline=1;size={};width={cols};height={rows};preserveAspectRatio=1:{}\x07",
+            bytes.len(),
+            encoded()
+        ),
+        GraphicsProtocol::Kitty => {
+            let encoded = encoded();
+            let chunks = encoded.as_bytes().chunks(4096).collect::<Vec<_>>();
+            chunks
+                .iter()
+                .enumerate()
+                .map(|(idx, chunk)| {
+                    let more = u8::from(idx + 1 < chunks.len());
+                    let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+                    if idx == 0 {
+                        format!("\x1b_Gf=100,a=T,q=2,c={cols},r={rows},m={more};{chunk}\x1b\\")
+                    } else {
+                        format!("\x1b_Gm={more};{chunk}\x1b\\")
+                    }
+                })
+                .collect()
+        }
+        GraphicsProtocol::Sixel => {
+            let (width, height) = cell_size();
+            png::decode(bytes)
+                .map(|image| {
+                    sixel::encode(
+                        &image,
+                        usize::from(cols) * width,
+                        usize::from(rows) * height,
+                    )
+                })
+                .unwrap_or_default()
+        }
+    }
+}
+
+/// Size of a cell in pixels, or 10 x 20 when the terminal does not tell.
+fn cell_size() -> (usize, usize) {
+    crossterm::terminal::window_size()
+        .ok()
+        .filter(|size| size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0)
+        .map(|size| {
+            (
+                usize::from(size.width / size.columns),
+                usize::from(size.height / size.rows),
+            )
+        })
+        .unwrap_or((10, 20))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GraphicsProtocol, ImageFormat, escape};
+    use crate::misc::png::tests::png;
+
+    #[test]
+    fn test_detect_by_magic_bytes() {
+        assert_eq!(
+            ImageFormat::detect(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            Some(ImageFormat::Png)
+        );
+        assert_eq!(
+            ImageFormat::detect(&[0xff, 0xd8, 0xff, 0xe0]),
+            Some(ImageFormat::Jpeg)
+        );
+        assert_eq!(ImageFormat::detect(b"GIF89a"), None);
+        assert_eq!(ImageFormat::detect(&[]), None);
+    }
+
+    #[test]
+    fn test_kitty_escape_is_chunked() {
+        let escape = escape(GraphicsProtocol::Kitty, &[0; 6000], 10, 5);
+        assert!(escape.starts_with("\x1b_Gf=100,a=T,q=2,c=10,r=5,m=1;"));
+        assert!(escape.contains("\x1b_Gm=0;"));
+        assert_eq!(escape.matches("\x1b_G").count(), 2);
+    }
+
+    #[test]
+    fn test_iterm_escape_carries_the_size() {
+        let escape = escape(GraphicsProtocol::Iterm, b"abc", 10, 5);
+        assert_eq!(
+            escape,
+            "\x1b]1337;File=inline=1;size=3;width=10;height=5;preserveAspectRatio=1:YWJj\x07"
+        );
+    }
+
+    #[test]
+    fn test_sixel_escape_draws_a_png() {
+        let sixel = escape(
+            GraphicsProtocol::Sixel,
+            &png(1, 1, 8, 2, &[], &[0, 255, 0, 0]),
+            1,
+            1,
+        );
+        assert!(sixel.starts_with("\x1bP0;1q\"1;1;"));
+        assert!(sixel.contains("#180;2;100;0;0"));
+        assert!(sixel.ends_with("-\x1b\\"));
+        assert_eq!(escape(GraphicsProtocol::Sixel, b"not a png", 1, 1), "");
+    }
+}
@@ -0,0 +1,130 @@
+/// Indented form of `text` when it holds a JSON object or array.
+pub fn pretty(text: &str) -> Option<String> {
+    let trimmed = text.trim_start();
+    if !trimmed.starts_with(['{', '[']) {
+        return None;
+    }
+    serde_json::from_str::<serde_json::Value>(trimmed)
+        .ok()
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonToken {
+    Key,
+    String,
+    Number,
+    Literal,
+    Punctuation,
+    Space,
+}
+
+/// Splits a line of indented JSON into tokens for highlighting.
+pub fn tokens(line: &str) -> Vec<(JsonToken, &str)> {
+    let mut tokens = Vec::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let (token, len) = match c {
+            '"' => {
+                let mut escaped = false;
+                let len = rest[1..]
+                    .char_indices()
+                    .find(|(_, c)| {
+                        let end = *c == '"' && !escaped;
+                        escaped = *c == '\\' && !escaped;
+                        end
+                    })
+                    .map_or(rest.len(), |(idx, _)| idx + 2);
+                let token = if rest[len..].trim_start().starts_with(':') {
+                    JsonToken::Key
+                } else {
+                    JsonToken::String
+                };
+                (token, len)
+            }
+            '{' | '}' | '[' | ']' | ',' | ':' => (JsonToken::Punctuation, 1),
+            c if c.is_whitespace() => (
+                JsonToken::Space,
+                rest.find(|c: char| !c.is_whitespace())
+                    .unwrap_or(rest.len()),
+            ),
+            c => (
+                if c == '-' || c.is_ascii_digit() {
+                    JsonToken::Number
+                } else {
+                    JsonToken::Literal
+                },
+                rest.find(|c: char| c.is_whitespace() || "{}[],:\"".contains(c))
+                    .unwrap_or(rest.len()),
+            ),
+        };
+        let (head, tail) = rest.split_at(len);
+        tokens.push((token, head));
+        rest = tail;
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonToken, pretty, tokens};
+
+    #[test]
+    fn test_pretty_indents_objects_and_arrays() {
+        assert_eq!(
+            pretty(r#"{"b":1,"a":[true,null]}"#).as_deref(),
+            Some("{\n  \"b\": 1,\n  \"a\": [\n    true,\n    null\n  ]\n}")
+        );
+        assert_eq!(pretty("[]").as_deref(), Some("[]"));
+    }
+
+    #[test]
+    fn test_pretty_skips_scalars_and_invalid_json() {
+        assert_eq!(pretty("42"), None);
+        assert_eq!(pretty("\"text\""), None);
+        assert_eq!(pretty("{not json}"), None);
+        assert_eq!(pretty("plain text"), None);
+    }
+
+    #[test]
+    fn test_tokens_of_a_key_value_line() {
+        assert_eq!(
+            tokens(r#"  "name": "a \"b\"","#),
+            vec![
+                (JsonToken::Space, "  "),
+                (JsonToken::Key, r#""name""#),
+                (JsonToken::Punctuation, ":"),
+                (JsonToken::Space, " "),
+                (JsonToken::String, r#""a \"b\"""#),
+                (JsonToken::Punctuation, ","),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokens_of_numbers_and_literals() {
+        assert_eq!(
+            tokens("[-1.5, true]"),
+            vec![
+                (JsonToken::Punctuation, "["),
+                (JsonToken::Number, "-1.5"),
+                (JsonToken::Punctuation, ","),
+                (JsonToken::Space, " "),
+                (JsonToken::Literal, "true"),
+                (JsonToken::Punctuation, "]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokens_keep_the_whole_line() {
+        let line = r#"    "k": {"unterminated"#;
+        assert_eq!(
+            tokens(line)
+                .iter()
+                .map(|(_, text)| *text)
+                .collect::<String>(),
+            line
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+
+/// Positions jumped away from, walked back and forth like vim's jumplist.
+#[derive(Debug, Clone)]
+pub struct JumpList<T> {
+    jumps: VecDeque<T>,
+    /// Index of the current position; equal to the length when it is not in the list.
+    pos: usize,
+    limit: usize,
+}
+
+impl<T: Clone + PartialEq> JumpList<T> {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            jumps: VecDeque::new(),
+            pos: 0,
+            limit: limit.max(1),
+        }
+    }
+
+    /// Records a jump away from `from`, dropping the positions walked back over.
+    pub fn record(&mut self, from: T) {
+        self.jumps.truncate(self.pos);
+        if self.jumps.back() != Some(&from) {
+            if self.jumps.len() == self.limit {
+                self.jumps.pop_front();
+            }
+            self.jumps.push_back(from);
+        }
+        self.pos = self.jumps.len();
+    }
+
+    /// Position before the current one, keeping `current` to come back to.
+    pub fn back(&mut self, current: T) -> Option<T> {
+        if self.pos == 0 {
+            return None;
+        }
+        if self.pos == self.jumps.len() {
+            self.jumps.push_back(current);
+        }
+        self.pos -= 1;
+        self.jumps.get(self.pos).cloned()
+    }
+
+    /// Position after the current one, once walked back.
+    pub fn forward(&mut self) -> Option<T> {
+        if self.pos + 1 >= self.jumps.len() {
+            return None;
+        }
+        self.pos += 1;
+        self.jumps.get(self.pos).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.jumps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jumps.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JumpList;
+
+    #[test]
+    fn test_back_and_forward() {
+        let mut jumps = JumpList::new(10);
+        jumps.record(1);
+        jumps.record(5);
+        assert_eq!(jumps.back(9), Some(5));
+        assert_eq!(jumps.back(5), Some(1));
+        assert_eq!(jumps.back(1), None);
+        assert_eq!(jumps.forward(), Some(5));
+        assert_eq!(jumps.forward(), Some(9));
+        assert_eq!(jumps.forward(), None);
+    }
+
+    #[test]
+    fn test_empty_list() {
+        let mut jumps = JumpList::<usize>::new(10);
+        assert!(jumps.is_empty());
+        assert_eq!(jumps.back(3), None);
+        assert_eq!(jumps.forward(), None);
+        assert!(jumps.is_empty());
+    }
+
+    #[test]
+    fn test_record_drops_positions_walked_back_over() {
+        let mut jumps = JumpList::new(10);
+        jumps.record(1);
+        jumps.record(2);
+        assert_eq!(jumps.back(3), Some(2));
+        assert_eq!(jumps.back(2), Some(1));
+        jumps.record(1);
+        assert_eq!(jumps.len(), 1);
+        assert_eq!(jumps.forward(), None);
+        assert_eq!(jumps.back(7), Some(1));
+    }
+
+    #[test]
+    fn test_repeated_position_is_recorded_once() {
+        let mut jumps = JumpList::new(10);
+        jumps.record(4);
+        jumps.record(4);
+        assert_eq!(jumps.len(), 1);
+    }
+
+    #[test]
+    fn test_limit_drops_oldest() {
+        let mut jumps = JumpList::new(2);
+        jumps.record(1);
+        jumps.record(2);
+        jumps.record(3);
+        assert_eq!(jumps.back(4), Some(3));
+        assert_eq!(jumps.back(3), Some(2));
+        assert_eq!(jumps.back(2), None);
+    }
+}
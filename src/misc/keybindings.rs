@@ -0,0 +1,412 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::ErrorKind,
+    path::PathBuf,
+    str::FromStr,
+    sync::{OnceLock, RwLock},
+};
+
+use anyhow::anyhow;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use itertools::Itertools;
+use serde::Deserialize;
+
+use crate::{
+    AppResult,
+    handler::{command, message::Message},
+    misc::{paths::keybindings_path, type_ext::UnwrapOrGracefulShutdown},
+    tui::popups::command_palette::Command,
+};
+
+/// Part of the interface a binding applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Context {
+    /// Table of the current tab, with no popup or prompt open.
+    Table,
+    /// Schema view, with no popup open.
+    Schema,
+    /// Hex viewer of a binary value, outside its search prompt.
+    HexViewer,
+    /// Key typed after the leader key.
+    Leader,
+}
+
+/// Key with its modifiers, as written in the keybindings file, e.g. `ctrl+d`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shift is carried by the character itself, and reported or not
+        // depending on the terminal.
+        match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => Self {
+                code: KeyCode::Char(c.to_ascii_uppercase()),
+                modifiers: modifiers.difference(KeyModifiers::SHIFT),
+            },
+            KeyCode::BackTab => Self {
+                code,
+                modifiers: modifiers.difference(KeyModifiers::SHIFT),
+            },
+            _ => Self { code, modifiers },
+        }
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(num) => write!(f, "F{num}"),
+            code => write!(
+                f,
+                "{}",
+                code.to_string().to_ascii_lowercase().replace(' ', "")
+            ),
+        }
+    }
+}
+
+impl From<&KeyEvent> for KeyBinding {
+    fn from(event: &KeyEvent) -> Self {
+        KeyBinding::new(event.code, event.modifiers)
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The key itself may be '+', as in `+` or `ctrl++`.
+        let (mods, key) = match s.strip_suffix('+') {
+            Some(mods) if mods.is_empty() || mods.ends_with('+') => (mods, "+"),
+            _ => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in mods.split('+').filter(|modifier| !modifier.is_empty()) {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(anyhow!("Unknown modifier '{modifier}' in '{s}'")),
+            };
+        }
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                lower => lower
+                    .strip_prefix('f')
+                    .and_then(|num| num.parse().ok())
+                    .filter(|num| (1..=24).contains(num))
+                    .map(KeyCode::F)
+                    .ok_or(anyhow!("Unknown key '{key}' in '{s}'"))?,
+            },
+        };
+        Ok(KeyBinding::new(code, modifiers))
+    }
+}
+
+/// What a bound key does.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// Command of the palette without arguments, by name, e.g. `Transpose`.
+    Palette(Command),
+    /// Command line as typed in the palette, e.g. `goto 1`.
+    Command(String),
+    /// Opens the palette with the text after the colon typed in, e.g.
+    /// `:head ` waiting for the number of rows.
+    Prompt(String),
+    /// Disables the built-in binding of the key.
+    Disabled,
+}
+
+impl Action {
+    pub fn run(&self) {
+        match self {
+            Action::Palette(command) => command.execute(),
+            Action::Command(line) => match command::parse(line) {
+                Some(Ok(message)) => message.enqueue(),
+                Some(Err(err)) => Message::AppShowError(err.to_string()).enqueue(),
+                None => (),
+            },
+            Action::Prompt(text) => Message::AppPromptCommand(text.to_owned()).enqueue(),
+            Action::Disabled => (),
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Palette(command) => write!(f, "{command}"),
+            Action::Command(line) => write!(f, "{line}"),
+            Action::Prompt(text) => write!(f, ":{text}"),
+            Action::Disabled => write!(f, "none"),
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("none") {
+            return Ok(Action::Disabled);
+        }
+        if let Some(text) = s.strip_prefix(':') {
+            return Ok(Action::Prompt(text.to_owned()));
+        }
+        match command::parse(s) {
+            Some(Ok(_)) => Ok(Action::Command(s.to_owned())),
+            Some(Err(err)) => Err(anyhow!("Invalid command '{s}': {err}")),
+            None => match s.parse::<Command>() {
+                Ok(command) if command.keyword().is_none() => Ok(Action::Palette(command)),
+                Ok(command) => Err(anyhow!("Command '{command}' expects arguments")),
+                Err(_) => Err(anyhow!("Unknown command '{s}'")),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeybindingsFile {
+    /// Key starting the mappings of `[leader]`, `space` by default.
+    leader_key: Option<String>,
+    #[serde(default)]
+    table: HashMap<String, String>,
+    #[serde(default)]
+    schema: HashMap<String, String>,
+    #[serde(default)]
+    hex: HashMap<String, String>,
+    #[serde(default)]
+    leader: HashMap<String, String>,
+}
+
+/// Keys mapped to actions per context, read from the keybindings file and
+/// taking precedence over the built-in keys.
+#[derive(Debug, Default)]
+pub struct Keybindings {
+    bindings: RwLock<HashMap<(Context, KeyBinding), Action>>,
+    leader: RwLock<Option<KeyBinding>>,
+    /// File given with `--keybindings`, read instead of the default one.
+    path: RwLock<Option<PathBuf>>,
+}
+
+/// Bindings of a keybindings file and its leader key.
+type Parsed = (HashMap<(Context, KeyBinding), Action>, Option<KeyBinding>);
+
+impl Keybindings {
+    /// Reads the keybindings file again; a missing default file clears the
+    /// bindings, while a missing `--keybindings` file is an error.
+    pub fn reload(&self) -> AppResult<()> {
+        let path = self.path.read().unwrap_or_graceful_shutdown().clone();
+        let contents = match &path {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|err| anyhow!("Keybindings file {}: {err}", path.display()))?,
+            None => match fs::read_to_string(keybindings_path()?) {
+                Ok(contents) => contents,
+                Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+                Err(err) => return Err(err.into()),
+            },
+        };
+        let (bindings, leader) = parse(&contents)?;
+        *self.bindings.write().unwrap_or_graceful_shutdown() = bindings;
+        *self.leader.write().unwrap_or_graceful_shutdown() = leader;
+        Ok(())
+    }
+
+    /// Reads the bindings from `path` instead of the default keybindings file.
+    pub fn set_path(&self, path: PathBuf) {
+        *self.path.write().unwrap_or_graceful_shutdown() = Some(path);
+    }
+
+    /// Action bound to the key of `event` in `context`.
+    pub fn get(&self, context: Context, event: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .read()
+            .unwrap_or_graceful_shutdown()
+            .get(&(context, event.into()))
+            .cloned()
+    }
+
+    /// Whether `event` is the leader key.
+    pub fn is_leader(&self, event: &KeyEvent) -> bool {
+        *self.leader.read().unwrap_or_graceful_shutdown() == Some(event.into())
+    }
+
+    /// Keys bound in `context` with their actions, for display.
+    pub fn list(&self, context: Context) -> Vec<(String, String)> {
+        self.bindings
+            .read()
+            .unwrap_or_graceful_shutdown()
+            .iter()
+            .filter(|((ctx, _), action)| *ctx == context && **action != Action::Disabled)
+            .map(|((_, key), action)| (key.to_string(), action.to_string()))
+            .sorted()
+            .collect()
+    }
+}
+
+fn parse(contents: &str) -> AppResult<Parsed> {
+    let KeybindingsFile {
+        leader_key,
+        table,
+        schema,
+        hex,
+        leader,
+    } = toml::from_str(contents)?;
+    let leader_key = match leader_key {
+        Some(key) => Some(key.parse()?),
+        None if !leader.is_empty() => Some(KeyBinding::new(KeyCode::Char(' '), KeyModifiers::NONE)),
+        None => None,
+    };
+    let mut bindings = HashMap::new();
+    for (context, keys) in [
+        (Context::Table, table),
+        (Context::Schema, schema),
+        (Context::HexViewer, hex),
+        (Context::Leader, leader),
+    ] {
+        for (key, action) in keys {
+            bindings.insert((context, key.parse()?), action.parse()?);
+        }
+    }
+    Ok((bindings, leader_key))
+}
+
+pub fn keybindings() -> &'static Keybindings {
+    static KEYBINDINGS: OnceLock<Keybindings> = OnceLock::new();
+    KEYBINDINGS.get_or_init(Keybindings::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{Action, Context, KeyBinding, parse};
+    use crate::tui::popups::command_palette::Command;
+
+    #[test]
+    fn test_parses_keys() {
+        let key = |code, modifiers| KeyBinding::from(&KeyEvent::new(code, modifiers));
+        assert_eq!(
+            "ctrl+d".parse::<KeyBinding>().unwrap(),
+            key(KeyCode::Char('d'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            "Alt+Shift+Left".parse::<KeyBinding>().unwrap(),
+            key(KeyCode::Left, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        );
+        assert_eq!(
+            "F5".parse::<KeyBinding>().unwrap(),
+            key(KeyCode::F(5), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            "ctrl++".parse::<KeyBinding>().unwrap(),
+            key(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+        assert!("hyper+x".parse::<KeyBinding>().is_err());
+        assert!("f42".parse::<KeyBinding>().is_err());
+    }
+
+    #[test]
+    fn test_shift_is_carried_by_characters() {
+        let upper = KeyBinding::from(&KeyEvent::new(KeyCode::Char('Z'), KeyModifiers::SHIFT));
+        assert_eq!("Z".parse::<KeyBinding>().unwrap(), upper);
+        assert_eq!("shift+z".parse::<KeyBinding>().unwrap(), upper);
+    }
+
+    #[test]
+    fn test_parses_actions() {
+        assert_eq!(
+            "transpose".parse::<Action>().unwrap(),
+            Action::Palette(Command::Transpose)
+        );
+        assert_eq!(
+            "goto 1".parse::<Action>().unwrap(),
+            Action::Command("goto 1".to_owned())
+        );
+        assert_eq!("none".parse::<Action>().unwrap(), Action::Disabled);
+        assert_eq!(
+            ":export csv".parse::<Action>().unwrap(),
+            Action::Prompt("export csv".to_owned())
+        );
+        assert!("head".parse::<Action>().is_err());
+        assert!("no-such-command".parse::<Action>().is_err());
+    }
+
+    #[test]
+    fn test_parses_contexts() {
+        let (bindings, leader) = parse(
+            r#"
+            [table]
+            "ctrl+t" = "Transpose"
+            "q" = "none"
+
+            [schema]
+            "F2" = "Query"
+
+            [hex]
+            "q" = "none"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(bindings.len(), 4);
+        assert_eq!(leader, None);
+        assert_eq!(
+            bindings.get(&(Context::Schema, "f2".parse().unwrap())),
+            Some(&Action::Palette(Command::Query))
+        );
+        assert_eq!(
+            bindings.get(&(Context::HexViewer, "q".parse().unwrap())),
+            Some(&Action::Disabled)
+        );
+        assert!(parse("[tables]\n\"x\" = \"Quit\"").is_err());
+    }
+
+    #[test]
+    fn test_parses_leader_mappings() {
+        let (bindings, leader) = parse("[leader]\ne = \":export csv\"\nt = \"Transpose\"").unwrap();
+        assert_eq!(leader, Some(" ".parse().unwrap()));
+        assert_eq!(
+            bindings.get(&(Context::Leader, "e".parse().unwrap())),
+            Some(&Action::Prompt("export csv".to_owned()))
+        );
+        let (_, leader) = parse("leader_key = \",\"\n[leader]\nt = \"Transpose\"").unwrap();
+        assert_eq!(leader, Some(",".parse().unwrap()));
+    }
+}
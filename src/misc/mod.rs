@@ -1,18 +1,33 @@
 pub mod background;
+pub mod color_mode;
+pub mod column_styles;
 pub mod config;
 pub mod download;
 pub mod external_editor;
+pub mod file_watch;
+pub mod graphics;
 pub mod http;
 pub mod iter_ext;
+pub mod json;
+pub mod jump_list;
+pub mod keybindings;
 pub mod non_empty_stack;
 pub mod osc52;
 pub mod paths;
+pub mod png;
 pub mod polars_ext;
 pub mod ragged_vec;
+pub mod reader_defaults;
 pub mod remote_load;
 pub mod search;
+pub mod sixel;
 pub mod sql;
 pub mod stdin;
 pub mod table_name_generator;
+pub mod terminal_background;
+pub mod time_axis;
+pub mod transform;
 pub mod type_ext;
 pub mod type_inferer;
+pub mod value_format;
+pub mod workspace;
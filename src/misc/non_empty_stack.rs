@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NonEmptyStack<T> {
     base: T,
     stack: Vec<T>,
@@ -32,10 +32,20 @@ impl<T> NonEmptyStack<T> {
         self.stack.len()
     }
 
+    /// Element at `idx`, counting the base as `0`.
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        idx.checked_sub(1)
+            .map_or(Some(&self.base), |idx| self.stack.get(idx))
+    }
+
     pub fn base(&self) -> &T {
         &self.base
     }
 
+    pub fn base_mut(&mut self) -> &mut T {
+        &mut self.base
+    }
+
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
         std::iter::once(&self.base).chain(self.stack.iter())
     }
@@ -68,6 +78,24 @@ mod tests {
         assert_eq!(s.last(), &1);
     }
 
+    #[test]
+    fn base_mut_keeps_the_stack() {
+        let mut s = NonEmptyStack::new(1);
+        s.push(2);
+        *s.base_mut() = 5;
+        assert_eq!(s.base(), &5);
+        assert_eq!(s.last(), &2);
+    }
+
+    #[test]
+    fn get_counts_base_as_zero() {
+        let mut s = NonEmptyStack::new(1);
+        s.push(2);
+        assert_eq!(s.get(0), Some(&1));
+        assert_eq!(s.get(1), Some(&2));
+        assert_eq!(s.get(2), None);
+    }
+
     #[test]
     fn last_mut_modifies_top_or_base() {
         // modify base when stack is empty
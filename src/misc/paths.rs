@@ -4,14 +4,28 @@ use anyhow::anyhow;
 
 use crate::AppResult;
 
+/// `$XDG_CONFIG_HOME/tabiew`, or `~/.config/tabiew` when it is unset or relative.
+fn config_dir() -> AppResult<PathBuf> {
+    match std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from) {
+        Some(path) if path.is_absolute() => Ok(path.join("tabiew")),
+        _ => home::home_dir()
+            .map(|path| path.join(".config").join("tabiew"))
+            .ok_or(anyhow!("Home dir not found")),
+    }
+}
+
 pub fn theme_path() -> AppResult<PathBuf> {
-    home::home_dir()
-        .map(|path| path.join(".config").join("tabiew").join("theme.toml"))
-        .ok_or(anyhow!("Home dir not found"))
+    Ok(config_dir()?.join("theme.toml"))
+}
+
+pub fn themes_dir() -> AppResult<PathBuf> {
+    Ok(config_dir()?.join("themes"))
 }
 
 pub fn config_path() -> AppResult<PathBuf> {
-    home::home_dir()
-        .map(|path| path.join(".config").join("tabiew").join("config.toml"))
-        .ok_or(anyhow!("Home dir not found"))
+    Ok(config_dir()?.join("config.toml"))
+}
+
+pub fn keybindings_path() -> AppResult<PathBuf> {
+    Ok(config_dir()?.join("keybindings.toml"))
 }
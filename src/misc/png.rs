@@ -0,0 +1,307 @@
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+
+/// Largest image decoded, in pixels.
+const MAX_PIXELS: usize = 1 << 24;
+
+/// Decoded image with its pixels in RGBA, row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl Image {
+    pub fn pixel(&self, x: usize, y: usize) -> [u8; 4] {
+        self.pixels[y * self.width + x]
+    }
+}
+
+/// Decodes a non-interlaced PNG of any color type and bit depth. Returns
+/// `None` for an interlaced, malformed, or too large image.
+pub fn decode(bytes: &[u8]) -> Option<Image> {
+    let mut rest = bytes.strip_prefix(b"\x89PNG\r\n\x1a\n")?;
+    let mut header = None;
+    let mut palette: &[u8] = &[];
+    let mut transparency: &[u8] = &[];
+    let mut data = Vec::new();
+    while rest.len() >= 12 {
+        let len = u32::from_be_bytes(rest[..4].try_into().ok()?) as usize;
+        let body = rest.get(8..8 + len)?;
+        match &rest[4..8] {
+            b"IHDR" => header = Some(Header::parse(body)?),
+            b"PLTE" => palette = body,
+            b"tRNS" => transparency = body,
+            b"IDAT" => data.extend_from_slice(body),
+            b"IEND" => break,
+            _ => (),
+        }
+        rest = rest.get(12 + len..)?;
+    }
+    let header = header?;
+
+    let stride = (header.width * header.bits_per_pixel()).div_ceil(8);
+    let expected = header.height * (stride + 1);
+    let mut raw = Vec::with_capacity(expected);
+    ZlibDecoder::new(data.as_slice())
+        .take(expected as u64)
+        .read_to_end(&mut raw)
+        .ok()?;
+    if raw.len() != expected {
+        return None;
+    }
+
+    // filters work on whole bytes: the previous pixel is at least a byte back
+    let step = header.bits_per_pixel().div_ceil(8);
+    let mut previous = vec![0; stride];
+    let mut pixels = Vec::with_capacity(header.width * header.height);
+    for line in raw.chunks_exact(stride + 1) {
+        let mut row = line[1..].to_vec();
+        for idx in 0..stride {
+            let left = if idx >= step { row[idx - step] } else { 0 };
+            let up = previous[idx];
+            let up_left = if idx >= step { previous[idx - step] } else { 0 };
+            let predictor = match line[0] {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((u16::from(left) + u16::from(up)) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                _ => return None,
+            };
+            row[idx] = row[idx].wrapping_add(predictor);
+        }
+        for x in 0..header.width {
+            pixels.push(header.pixel(&row, x, palette, transparency)?);
+        }
+        previous = row;
+    }
+    Some(Image {
+        width: header.width,
+        height: header.height,
+        pixels,
+    })
+}
+
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = i16::from(left) + i16::from(up) - i16::from(up_left);
+    let distance = |value: u8| (estimate - i16::from(value)).abs();
+    if distance(left) <= distance(up) && distance(left) <= distance(up_left) {
+        left
+    } else if distance(up) <= distance(up_left) {
+        up
+    } else {
+        up_left
+    }
+}
+
+#[derive(Debug)]
+struct Header {
+    width: usize,
+    height: usize,
+    depth: usize,
+    color_type: u8,
+}
+
+impl Header {
+    fn parse(body: &[u8]) -> Option<Self> {
+        let width = u32::from_be_bytes(body.get(..4)?.try_into().ok()?) as usize;
+        let height = u32::from_be_bytes(body.get(4..8)?.try_into().ok()?) as usize;
+        let &[depth, color_type, 0, 0, 0] = body.get(8..13)? else {
+            return None;
+        };
+        let valid = match color_type {
+            0 => matches!(depth, 1 | 2 | 4 | 8 | 16),
+            3 => matches!(depth, 1 | 2 | 4 | 8),
+            2 | 4 | 6 => matches!(depth, 8 | 16),
+            _ => false,
+        };
+        let size = width.checked_mul(height)?;
+        (valid && size > 0 && size <= MAX_PIXELS).then_some(Header {
+            width,
+            height,
+            depth: depth.into(),
+            color_type,
+        })
+    }
+
+    fn channels(&self) -> usize {
+        match self.color_type {
+            2 => 3,
+            4 => 2,
+            6 => 4,
+            _ => 1,
+        }
+    }
+
+    fn bits_per_pixel(&self) -> usize {
+        self.channels() * self.depth
+    }
+
+    /// Sample `idx` of an unfiltered row, at the bit depth of the image.
+    fn sample(&self, row: &[u8], idx: usize) -> u16 {
+        match self.depth {
+            8 => row[idx].into(),
+            16 => u16::from_be_bytes([row[2 * idx], row[2 * idx + 1]]),
+            depth => {
+                let bit = idx * depth;
+                u16::from(row[bit / 8] >> (8 - depth - bit % 8)) & ((1 << depth) - 1)
+            }
+        }
+    }
+
+    /// Sample scaled to 8 bits.
+    fn scale(&self, sample: u16) -> u8 {
+        match self.depth {
+            16 => (sample >> 8) as u8,
+            depth => (u32::from(sample) * 255 / ((1 << depth) - 1)) as u8,
+        }
+    }
+
+    fn pixel(&self, row: &[u8], x: usize, palette: &[u8], transparency: &[u8]) -> Option<[u8; 4]> {
+        let samples = (0..self.channels())
+            .map(|channel| self.sample(row, x * self.channels() + channel))
+            .collect::<Vec<_>>();
+        // color types 0 and 2 mark one color transparent, in 16 bit samples
+        let transparent = || {
+            transparency
+                .chunks_exact(2)
+                .map(|sample| u16::from_be_bytes([sample[0], sample[1]]))
+                .eq(samples.iter().copied())
+        };
+        Some(match self.color_type {
+            0 => {
+                let gray = self.scale(samples[0]);
+                [gray, gray, gray, if transparent() { 0 } else { 255 }]
+            }
+            2 => {
+                let [r, g, b] = [0, 1, 2].map(|idx| self.scale(samples[idx]));
+                [r, g, b, if transparent() { 0 } else { 255 }]
+            }
+            3 => {
+                let idx = usize::from(samples[0]);
+                let [r, g, b] = palette.get(3 * idx..3 * idx + 3)?.try_into().ok()?;
+                [r, g, b, transparency.get(idx).copied().unwrap_or(255)]
+            }
+            4 => {
+                let gray = self.scale(samples[0]);
+                [gray, gray, gray, self.scale(samples[1])]
+            }
+            _ => {
+                let [r, g, b, a] = [0, 1, 2, 3].map(|idx| self.scale(samples[idx]));
+                [r, g, b, a]
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::Write;
+
+    use flate2::{Compression, write::ZlibEncoder};
+
+    use super::{Image, decode};
+
+    /// PNG of `rows` (filter byte first) with the given header fields and
+    /// extra chunks before the image data. The CRCs are left zero.
+    pub(crate) fn png(
+        width: u32,
+        height: u32,
+        depth: u8,
+        color_type: u8,
+        chunks: &[(&[u8; 4], &[u8])],
+        rows: &[u8],
+    ) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(rows).unwrap();
+        let data = encoder.finish().unwrap();
+        let header = [
+            &width.to_be_bytes()[..],
+            &height.to_be_bytes(),
+            &[depth, color_type, 0, 0, 0],
+        ]
+        .concat();
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        let all = [(b"IHDR", header.as_slice())]
+            .into_iter()
+            .chain(chunks.iter().copied())
+            .chain([(b"IDAT", data.as_slice()), (b"IEND", &[][..])]);
+        for (kind, body) in all {
+            png.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            png.extend_from_slice(kind);
+            png.extend_from_slice(body);
+            png.extend_from_slice(&[0; 4]);
+        }
+        png
+    }
+
+    #[test]
+    fn test_decodes_rgba_with_filters() {
+        // the second row is stored with the up filter, as its difference
+        let bytes = png(
+            2,
+            2,
+            8,
+            6,
+            &[],
+            &[
+                0, 255, 0, 0, 255, 0, 255, 0, 128, //
+                2, 0, 0, 255, 0, 0, 0, 255, 0,
+            ],
+        );
+        assert_eq!(
+            decode(&bytes),
+            Some(Image {
+                width: 2,
+                height: 2,
+                pixels: vec![
+                    [255, 0, 0, 255],
+                    [0, 255, 0, 128],
+                    [255, 0, 255, 255],
+                    [0, 255, 255, 128]
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_decodes_palette_and_gray_bit_depths() {
+        let palette = png(
+            3,
+            1,
+            2,
+            3,
+            &[
+                (b"PLTE", &[0, 0, 0, 10, 20, 30, 200, 100, 50]),
+                (b"tRNS", &[0]),
+            ],
+            &[0, 0b00_01_10_00],
+        );
+        assert_eq!(
+            decode(&palette).unwrap().pixels,
+            [[0, 0, 0, 0], [10, 20, 30, 255], [200, 100, 50, 255]]
+        );
+        let gray = png(2, 1, 1, 0, &[], &[0, 0b10_000000]);
+        assert_eq!(
+            decode(&gray).unwrap().pixels,
+            [[255, 255, 255, 255], [0, 0, 0, 255]]
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_images() {
+        assert_eq!(decode(b"GIF89a"), None);
+        // 2 rows declared, 1 stored
+        assert_eq!(decode(&png(1, 2, 8, 0, &[], &[0, 7])), None);
+        // palette index past the palette
+        assert_eq!(
+            decode(&png(1, 1, 8, 3, &[(b"PLTE", &[1, 2, 3])], &[0, 1])),
+            None
+        );
+        // 16 bit palettes do not exist
+        assert_eq!(decode(&png(1, 1, 16, 3, &[], &[0, 0, 0])), None);
+    }
+}
@@ -7,7 +7,7 @@ use std::{
 };
 
 use anyhow::anyhow;
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
 use itertools::{Itertools, izip};
 use polars::{
     frame::DataFrame,
@@ -18,7 +18,11 @@ use ratatui::widgets::Cell;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use unicode_width::UnicodeWidthStr;
 
-use crate::{AppResult, misc::ragged_vec::RaggedVec, tui::sheet::SheetSection};
+use crate::{
+    AppResult,
+    misc::{config::config, ragged_vec::RaggedVec},
+    tui::sheet::{SheetSection, ValueNode},
+};
 
 use super::type_ext::HasSubsequence;
 
@@ -26,6 +30,7 @@ pub trait AnyValueExt {
     fn into_single_line(self) -> String;
     fn width(self, num_buffer: &mut NumBuffer) -> usize;
     fn into_multi_line(self) -> String;
+    fn into_tree(self, key: String) -> ValueNode;
     fn into_cell(self, width: usize) -> Cell<'static>;
     fn fuzzy_cmp(self, other: &str) -> bool;
     fn parse_bool(slice: &str) -> Option<AnyValue<'static>>;
@@ -49,6 +54,11 @@ impl AnyValueExt for AnyValue<'_> {
             }
             AnyValue::Binary(buf) => format!("Blob (Length: {})", buf.len()),
             AnyValue::BinaryOwned(buf) => format!("Blob (Length: {})", buf.len()),
+            AnyValue::Datetime(value, unit, _) | AnyValue::DatetimeOwned(value, unit, _)
+                if config().show_local_time() =>
+            {
+                local_datetime(value, unit).unwrap_or_else(|| self.to_string())
+            }
             _ => self.to_string(),
         }
     }
@@ -97,10 +107,49 @@ impl AnyValueExt for AnyValue<'_> {
             }
             AnyValue::Binary(buf) => bytes_to_string(buf),
             AnyValue::BinaryOwned(buf) => bytes_to_string(buf),
+            AnyValue::Datetime(value, unit, _) | AnyValue::DatetimeOwned(value, unit, _)
+                if config().show_local_time() =>
+            {
+                local_datetime(value, unit).unwrap_or_else(|| self.to_string())
+            }
             _ => self.to_string(),
         }
     }
 
+    fn into_tree(self, key: String) -> ValueNode {
+        match &self {
+            AnyValue::List(series) | AnyValue::Array(series, _) => ValueNode::branch(
+                key,
+                format!("[{}]", series.len()),
+                series
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, value)| value.into_tree(idx.to_string()))
+                    .collect(),
+            ),
+            AnyValue::Struct(_, _, fields) => ValueNode::branch(
+                key,
+                format!("{{{}}}", fields.len()),
+                fields
+                    .iter()
+                    .zip(self._iter_struct_av())
+                    .map(|(field, value)| value.into_tree(field.name().to_string()))
+                    .collect(),
+            ),
+            AnyValue::StructOwned(payload) => ValueNode::branch(
+                key,
+                format!("{{{}}}", payload.1.len()),
+                payload
+                    .1
+                    .iter()
+                    .zip(payload.0.iter().cloned())
+                    .map(|(field, value)| value.into_tree(field.name().to_string()))
+                    .collect(),
+            ),
+            _ => ValueNode::leaf(key, self.into_single_line()),
+        }
+    }
+
     fn into_cell(self, width: usize) -> Cell<'static> {
         match self {
             AnyValue::Float32(f) => Cell::new(format!("{f:>w$.2}", w = width)),
@@ -164,9 +213,16 @@ pub trait SeriesExt {
     fn refine_to_bool(&self) -> AppResult<Series>;
     fn refine_to_date(&self) -> AppResult<Series>;
     fn refine_to_datetime(&self) -> AppResult<Series>;
+    fn refine_to_datetime_with(&self, fmt: &str) -> AppResult<Series>;
+    /// Widest of the name and the values, in terminal columns.
+    fn display_width(&self) -> usize;
 }
 
 impl SeriesExt for Series {
+    fn display_width(&self) -> usize {
+        series_width(self)
+    }
+
     fn refine_to_string(&self) -> AppResult<Series> {
         let casted = self.cast(&DataType::String)?;
         if casted.is_null().equal(&self.is_null()).all() {
@@ -277,10 +333,32 @@ impl SeriesExt for Series {
             DataType::Datetime(TimeUnit::Milliseconds, None)
         ))
     }
+
+    fn refine_to_datetime_with(&self, fmt: &str) -> AppResult<Series> {
+        let datetime_fmt = fmt.to_owned();
+        let date_fmt = fmt.to_owned();
+        self.try_map_all(move |val| match val {
+            AnyValue::String(s) => AnyValue::parse_datetime(s, &datetime_fmt),
+            AnyValue::StringOwned(s) => AnyValue::parse_datetime(s.as_str(), &datetime_fmt),
+            AnyValue::Null => Some(AnyValue::Null),
+            _ => None,
+        })
+        .or_else(|| {
+            self.try_map_all(move |val| match val {
+                AnyValue::String(s) => AnyValue::parse_date(s, &date_fmt),
+                AnyValue::StringOwned(s) => AnyValue::parse_date(s.as_str(), &date_fmt),
+                AnyValue::Null => Some(AnyValue::Null),
+                _ => None,
+            })
+        })
+        .ok_or(anyhow!(
+            "Column '{}' does not match the format '{fmt}'",
+            self.name()
+        ))
+    }
 }
 
 pub trait DataFrameExt {
-    fn widths(&self) -> Vec<usize>;
     fn get_sheet_sections(&self, pos: usize) -> Vec<SheetSection>;
     fn scatter_plot_data(&self, x_label: &str, y_label: &str) -> AppResult<RaggedVec<(f64, f64)>>;
     #[allow(clippy::type_complexity)]
@@ -290,7 +368,16 @@ pub trait DataFrameExt {
         y_label: &str,
         group_by: &str,
     ) -> AppResult<(RaggedVec<(f64, f64)>, Vec<String>)>;
+    #[allow(clippy::type_complexity)]
+    fn scatter_plot_data_binned(
+        &self,
+        x_label: &str,
+        y_label: &str,
+        value: &str,
+        bins: usize,
+    ) -> AppResult<(RaggedVec<(f64, f64)>, Vec<String>)>;
     fn histogram_plot_data(&self, col: &str, buckets: usize) -> AppResult<Vec<(String, u64)>>;
+    fn bar_plot_data(&self, col: &str) -> AppResult<Vec<(String, u64)>>;
 }
 
 pub trait TryMapAll {
@@ -300,6 +387,20 @@ pub trait TryMapAll {
     ) -> Option<Series>;
 }
 
+/// Formats a timestamp in the local time zone, treating naive datetimes as UTC.
+fn local_datetime(value: i64, unit: TimeUnit) -> Option<String> {
+    let utc = match unit {
+        TimeUnit::Nanoseconds => DateTime::from_timestamp_nanos(value),
+        TimeUnit::Microseconds => DateTime::from_timestamp_micros(value)?,
+        TimeUnit::Milliseconds => DateTime::from_timestamp_millis(value)?,
+    };
+    Some(
+        utc.with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S%.f")
+            .to_string(),
+    )
+}
+
 fn bytes_to_string(buf: impl AsRef<[u8]>) -> String {
     let buf = buf.as_ref();
     let index_width = buf.len().div(16).to_string().len();
@@ -325,34 +426,45 @@ fn bytes_to_string(buf: impl AsRef<[u8]>) -> String {
 }
 
 impl DataFrameExt for DataFrame {
-    fn widths(&self) -> Vec<usize> {
-        self.columns()
-            .iter()
-            .map(|col| series_width(col.as_materialized_series()))
-            .collect()
-    }
-
     fn get_sheet_sections(&self, pos: usize) -> Vec<SheetSection> {
         izip!(
             self.get_column_names().into_iter(),
-            self.get(pos)
-                .unwrap_or_default()
-                .into_iter()
-                .map(AnyValueExt::into_multi_line),
+            self.get(pos).unwrap_or_default(),
             self.dtypes()
         )
-        .map(|(header, content, dtype)| SheetSection::new(format!("{header} ({dtype})"), content))
+        .map(|(name, value, dtype)| {
+            let tree = matches!(
+                dtype,
+                DataType::List(_) | DataType::Array(_, _) | DataType::Struct(_)
+            )
+            .then(|| value.clone().into_tree(String::new()));
+            let image = match &value {
+                AnyValue::Binary(buf) => Some(buf.to_vec()),
+                AnyValue::BinaryOwned(buf) => Some(buf.clone()),
+                _ => None,
+            };
+            let content = config()
+                .value_format(name)
+                .format(&value)
+                .unwrap_or_else(|| value.into_multi_line());
+            SheetSection::new(name.to_string(), format!("{name} ({dtype})"), content)
+                .with_tree(tree)
+                .with_image(image)
+                .with_style(config().column_style(name, &dtype))
+        })
         .collect_vec()
     }
 
     fn scatter_plot_data(&self, x_label: &str, y_label: &str) -> AppResult<RaggedVec<(f64, f64)>> {
         Ok(self
             .column(x_label)?
+            .to_physical_repr()
             .cast(&DataType::Float64)?
             .f64()?
             .iter()
             .zip(
                 self.column(y_label)?
+                    .to_physical_repr()
                     .cast(&DataType::Float64)?
                     .f64()?
                     .iter(),
@@ -388,6 +500,55 @@ impl DataFrameExt for DataFrame {
         Ok((data, groups))
     }
 
+    fn scatter_plot_data_binned(
+        &self,
+        x_label: &str,
+        y_label: &str,
+        value: &str,
+        bins: usize,
+    ) -> AppResult<(RaggedVec<(f64, f64)>, Vec<String>)> {
+        let values = self.column(value)?.cast(&DataType::Float64)?;
+        let values = values.f64()?;
+        let (min, max) = values
+            .min_max()
+            .ok_or(anyhow!("Column '{value}' has no values"))?;
+        let bins = if max > min { bins.max(1) } else { 1 };
+        let step = (max - min) / bins as f64;
+        let xs = self
+            .column(x_label)?
+            .to_physical_repr()
+            .cast(&DataType::Float64)?;
+        let ys = self
+            .column(y_label)?
+            .to_physical_repr()
+            .cast(&DataType::Float64)?;
+        let mut points = vec![Vec::new(); bins];
+        for (x, y, value) in izip!(xs.f64()?.iter(), ys.f64()?.iter(), values.iter()) {
+            if let (Some(x), Some(y), Some(value)) = (x, y, value) {
+                let bin = if step > 0.0 {
+                    (((value - min) / step) as usize).min(bins - 1)
+                } else {
+                    0
+                };
+                points[bin].push((x, y));
+            }
+        }
+        let labels = (0..bins)
+            .map(|bin| {
+                format!(
+                    "{:.2} \u{2013} {:.2}",
+                    min + step * bin as f64,
+                    min + step * (bin + 1) as f64
+                )
+            })
+            .collect();
+        let mut data = RaggedVec::new();
+        for points in points {
+            data.push(points);
+        }
+        Ok((data, labels))
+    }
+
     fn histogram_plot_data(&self, col_name: &str, buckets: usize) -> AppResult<Vec<(String, u64)>> {
         let col = self.column(col_name)?;
         match col.dtype() {
@@ -414,6 +575,14 @@ impl DataFrameExt for DataFrame {
             _ => Err(anyhow!("Unsupported column type"))?,
         }
     }
+
+    fn bar_plot_data(&self, col_name: &str) -> AppResult<Vec<(String, u64)>> {
+        discrete_histogram(
+            self.column(col_name)?
+                .as_materialized_series()
+                .value_counts(true, false, "count".into(), false)?,
+        )
+    }
 }
 
 fn series_width(series: &Series) -> usize {
@@ -0,0 +1,96 @@
+use clap::{ArgMatches, parser::ValueSource};
+use serde::{Deserialize, Serialize};
+
+use crate::args::{Args, InferSchema};
+
+/// The `[reader]` section of the config: options used when the command line
+/// does not give them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReaderDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separator: Option<char>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_char: Option<char>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_header: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_errors: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub infer_schema: Option<InferSchema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub infer_datetimes: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncate_ragged_lines: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_type_inference: Option<bool>,
+}
+
+impl ReaderDefaults {
+    /// Sets the options of `args` not given on the command line, as told by
+    /// `matches`, to the defaults of the config.
+    pub fn apply(&self, args: &mut Args, matches: &ArgMatches) {
+        let unset = |id: &str| matches.value_source(id) != Some(ValueSource::CommandLine);
+        if let Some(separator) = self.separator.filter(|_| unset("separator")) {
+            args.separator = separator;
+        }
+        if let Some(quote_char) = self.quote_char.filter(|_| unset("quote_char")) {
+            args.quote_char = quote_char;
+        }
+        if let Some(no_header) = self.no_header.filter(|_| unset("no_header")) {
+            args.no_header = no_header;
+        }
+        if let Some(ignore_errors) = self.ignore_errors.filter(|_| unset("ignore_errors")) {
+            args.ignore_errors = ignore_errors;
+        }
+        if let Some(infer_schema) = self.infer_schema.filter(|_| unset("infer_schema")) {
+            args.infer_schema = infer_schema;
+        }
+        if let Some(infer_datetimes) = self.infer_datetimes.filter(|_| unset("infer_datetimes")) {
+            args.infer_datetimes = infer_datetimes;
+        }
+        if let Some(truncate_ragged_lines) = self
+            .truncate_ragged_lines
+            .filter(|_| unset("truncate_ragged_lines"))
+        {
+            args.truncate_ragged_lines = truncate_ragged_lines;
+        }
+        if let Some(no_type_inference) = self
+            .no_type_inference
+            .filter(|_| unset("no_type_inference"))
+        {
+            args.no_type_inference = no_type_inference;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::*;
+
+    #[test]
+    fn test_command_line_takes_precedence() {
+        let defaults = toml::from_str::<ReaderDefaults>(
+            r#"
+            separator = ";"
+            no_header = true
+            infer_schema = "no"
+            "#,
+        )
+        .unwrap();
+        let matches = Args::command().get_matches_from(["tw", "--separator", "|", "data.csv"]);
+        let mut args = Args::from_arg_matches(&matches).unwrap();
+        defaults.apply(&mut args, &matches);
+        assert_eq!(args.separator, '|');
+        assert!(args.no_header);
+        assert!(matches!(args.infer_schema, InferSchema::No));
+        assert_eq!(args.quote_char, '"');
+    }
+
+    #[test]
+    fn test_rejects_unknown_options() {
+        assert!(toml::from_str::<ReaderDefaults>("delimiter = \";\"").is_err());
+    }
+}
@@ -3,7 +3,7 @@ use std::{
     fmt::Debug,
     marker::PhantomData,
     sync::{
-        Arc, Mutex,
+        Arc, Mutex, OnceLock,
         atomic::{AtomicBool, Ordering},
         mpsc::{Receiver, TryRecvError, channel},
     },
@@ -13,6 +13,7 @@ use std::{
 use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
 use itertools::Itertools;
 use polars::{frame::DataFrame, prelude::IdxCa};
+use regex::Regex;
 
 use rayon::prelude::*;
 
@@ -39,12 +40,18 @@ impl Score for Skim {
     }
 }
 
+/// Substring matcher, treating a `/pattern/` as a regular expression.
 #[derive(Debug, Default)]
-pub struct Contain;
+pub struct Contain {
+    regex: OnceLock<Option<Regex>>,
+}
 
 impl Score for Contain {
     fn score(&self, a: &str, b: &str) -> Option<i64> {
-        a.contains(b).then_some(1)
+        match self.regex.get_or_init(|| regex_pattern(b)) {
+            Some(regex) => regex.is_match(a).then_some(1),
+            None => a.contains(b).then_some(1),
+        }
     }
 }
 
@@ -52,6 +59,9 @@ impl Score for Contain {
 pub struct Search<S> {
     pat: String,
     df: SyncDataFrame,
+    source: DataFrame,
+    index: Arc<SearchIndex>,
+    matched: Arc<OnceLock<Vec<u32>>>,
     _alive: SetFalseOnDrop,
     score: PhantomData<S>,
 }
@@ -61,17 +71,32 @@ where
     S: Score + Default + Sync + Send + 'static,
 {
     pub fn new(df: DataFrame, pat: String) -> Self {
+        let index = Arc::new(SearchIndex::new(df.width()));
+        Self::with_index(df, pat, index, None)
+    }
+
+    /// Searches `pat` reusing the cell cache of this search, scanning only the rows
+    /// this search matched when `pat` narrows its pattern.
+    pub fn refine(&self, pat: String) -> Self {
+        let candidates = narrows(&self.source, &self.pat, &pat)
+            .then(|| self.matched.get().cloned())
+            .flatten();
+        Self::with_index(self.source.clone(), pat, self.index.clone(), candidates)
+    }
+
+    fn with_index(
+        df: DataFrame,
+        pat: String,
+        index: Arc<SearchIndex>,
+        candidates: Option<Vec<u32>>,
+    ) -> Self {
         let sync_df = SyncDataFrame::new();
+        let matched = Arc::new(OnceLock::new());
         let alive = Arc::new(AtomicBool::new(true));
-        if pat.is_empty() {
+        let (columns, body) = scope(&df, &pat);
+        if body.is_empty() {
             // avoid search
-            sync_df.insert(df);
-            Self {
-                df: sync_df,
-                _alive: SetFalseOnDrop(alive),
-                score: Default::default(),
-                pat,
-            }
+            sync_df.insert(df.clone(), Vec::new());
         } else {
             // search
             // communication between search and collector threads
@@ -82,29 +107,36 @@ where
                 let matcher = S::default();
                 let alive = alive.clone();
                 let df = df.clone();
-                let pat = pat.clone();
+                let index = index.clone();
+                let pat = body.to_owned();
                 move || {
-                    let _ = df
-                        .columns()
+                    let rows = candidates.unwrap_or_else(|| (0..df.height() as u32).collect_vec());
+                    let _ = columns
                         .iter()
-                        .flat_map(|column| column.as_materialized_series().iter().enumerate())
+                        .filter_map(|idx| index.column(&df, *idx))
+                        .flat_map(|values| {
+                            rows.iter()
+                                .filter_map(move |row| values.get(*row as usize).map(|v| (*row, v)))
+                        })
                         .par_bridge()
                         .take_any_while(|_| alive.load(Ordering::Relaxed))
                         .filter_map(|(idx, value)| {
-                            let value = value.into_multi_line();
-                            if value == pat {
+                            if value == &pat {
                                 Some((idx, i64::MAX))
                             } else {
-                                matcher.score(&value, &pat).map(|score| (idx, score))
+                                matcher.score(value, &pat).map(|score| (idx, score))
                             }
                         })
-                        .try_for_each(|(idx, score)| tx.send((idx as u32, score)));
+                        .try_for_each(|(idx, score)| tx.send((idx, score)));
                 }
             });
 
             // collector thread
             std::thread::spawn({
                 let sync_df = sync_df.clone();
+                let matched = matched.clone();
+                let alive = alive.clone();
+                let df = df.clone();
                 move || {
                     let mut interval = Interval::new(Duration::from_millis(100));
                     let mut idx_score = HashMap::new();
@@ -122,46 +154,45 @@ where
                         }
 
                         if should_update {
+                            let rows = ranked(&idx_score);
                             sync_df.insert(
-                                df.take(&IdxCa::new_vec(
-                                    "name".into(),
-                                    idx_score
-                                        .iter()
-                                        .sorted_by_key(|(idx, score)| (-*score, *idx))
-                                        .map(|(idx, _)| *idx)
-                                        .collect(),
-                                ))
-                                .unwrap_or_default(),
+                                df.take(&IdxCa::new_vec("name".into(), rows.clone()))
+                                    .unwrap_or_default(),
+                                rows,
                             );
                             updated = true;
                         }
                         interval.sleep();
                     }
                     if !updated {
+                        let rows = ranked(&idx_score);
                         sync_df.insert(
-                            df.take(&IdxCa::new_vec(
-                                "name".into(),
-                                idx_score
-                                    .iter()
-                                    .sorted_by_key(|(idx, score)| (-*score, *idx))
-                                    .map(|(idx, _)| *idx)
-                                    .collect(),
-                            ))
-                            .unwrap_or_graceful_shutdown(),
+                            df.take(&IdxCa::new_vec("name".into(), rows.clone()))
+                                .unwrap_or_graceful_shutdown(),
+                            rows,
                         );
                     }
+                    // an aborted scan is incomplete, and must not seed refinements
+                    if alive.load(Ordering::Relaxed) {
+                        let _ = matched.set(idx_score.into_keys().sorted().collect());
+                    }
                 }
             });
-            Self {
-                df: sync_df,
-                _alive: SetFalseOnDrop(alive),
-                pat,
-                score: Default::default(),
-            }
+        }
+        Self {
+            df: sync_df,
+            source: df,
+            index,
+            matched,
+            _alive: SetFalseOnDrop(alive),
+            pat,
+            score: Default::default(),
         }
     }
 
-    pub fn latest(&self) -> Option<DataFrame> {
+    /// Latest matching frame along with the matching row indices of the searched
+    /// frame, best match first.
+    pub fn latest(&self) -> Option<(DataFrame, Vec<u32>)> {
         self.df.take()
     }
 
@@ -170,21 +201,194 @@ where
     }
 }
 
+/// Cells of a frame rendered to strings once per column, on first use, and shared
+/// by the successive searches of a search bar.
+#[derive(Debug)]
+struct SearchIndex {
+    columns: Vec<OnceLock<Vec<String>>>,
+}
+
+impl SearchIndex {
+    fn new(width: usize) -> Self {
+        Self {
+            columns: (0..width).map(|_| OnceLock::new()).collect(),
+        }
+    }
+
+    fn column(&self, df: &DataFrame, idx: usize) -> Option<&[String]> {
+        let cache = self.columns.get(idx)?;
+        let column = df.columns().get(idx)?;
+        Some(cache.get_or_init(|| {
+            column
+                .as_materialized_series()
+                .iter()
+                .map(|value| value.into_multi_line())
+                .collect()
+        }))
+    }
+}
+
+/// Whether every match of the query `new` is also a match of `old`, so a search for
+/// `new` only needs to scan the rows matched by `old`. Holds when both scan the same
+/// columns and `new` extends `old`, except for regex patterns.
+fn narrows(df: &DataFrame, old: &str, new: &str) -> bool {
+    let (old_columns, old_pat) = scope(df, old);
+    let (new_columns, new_pat) = scope(df, new);
+    !old_pat.is_empty()
+        && old_columns == new_columns
+        && new_pat.starts_with(old_pat)
+        && regex_pattern(old_pat).is_none()
+        && regex_pattern(new_pat).is_none()
+}
+
+fn regex_pattern(pat: &str) -> Option<Regex> {
+    pat.strip_prefix('/')
+        .and_then(|pat| pat.strip_suffix('/'))
+        .filter(|pat| !pat.is_empty())
+        .and_then(|pat| Regex::new(pat).ok())
+}
+
+/// Locates the fragments of cells matched by a search query, for highlighting.
 #[derive(Debug, Clone)]
-struct SyncDataFrame(Arc<Mutex<Option<DataFrame>>>);
+pub struct Highlight {
+    columns: Vec<String>,
+    matcher: HighlightMatcher,
+}
+
+#[derive(Debug, Clone)]
+enum HighlightMatcher {
+    Fuzzy(String),
+    Exact(String),
+    Regex(Regex),
+}
+
+impl Highlight {
+    pub fn fuzzy(df: &DataFrame, query: &str) -> Option<Self> {
+        Self::new(df, query, |pat| HighlightMatcher::Fuzzy(pat.to_owned()))
+    }
+
+    pub fn exact(df: &DataFrame, query: &str) -> Option<Self> {
+        Self::new(df, query, |pat| {
+            regex_pattern(pat)
+                .map(HighlightMatcher::Regex)
+                .unwrap_or_else(|| HighlightMatcher::Exact(pat.to_owned()))
+        })
+    }
+
+    /// Highlights the matches of `regex` in a single column.
+    pub fn regex(column: String, regex: Regex) -> Self {
+        Highlight {
+            columns: vec![column],
+            matcher: HighlightMatcher::Regex(regex),
+        }
+    }
+
+    fn new(
+        df: &DataFrame,
+        query: &str,
+        matcher: impl FnOnce(&str) -> HighlightMatcher,
+    ) -> Option<Self> {
+        let (columns, pat) = scope(df, query);
+        (!pat.is_empty()).then(|| Highlight {
+            columns: columns
+                .into_iter()
+                .filter_map(|idx| df.columns().get(idx))
+                .map(|column| column.name().as_str().to_owned())
+                .collect(),
+            matcher: matcher(pat),
+        })
+    }
+
+    pub fn applies_to(&self, column: &str) -> bool {
+        self.columns.iter().any(|name| name == column)
+    }
+
+    /// Sorted char indices of `text` matched by the query.
+    pub fn indices(&self, text: &str) -> Vec<usize> {
+        let ranges = match &self.matcher {
+            HighlightMatcher::Fuzzy(pat) => {
+                return SkimMatcherV2::default()
+                    .fuzzy_indices(text, pat)
+                    .map(|(_, indices)| indices)
+                    .unwrap_or_default();
+            }
+            HighlightMatcher::Exact(pat) => text
+                .match_indices(pat.as_str())
+                .map(|(start, found)| start..start + found.len())
+                .collect_vec(),
+            HighlightMatcher::Regex(regex) => regex
+                .find_iter(text)
+                .map(|found| found.range())
+                .collect_vec(),
+        };
+        text.char_indices()
+            .enumerate()
+            .filter(|(_, (byte, _))| ranges.iter().any(|range| range.contains(byte)))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+/// Index of the name matching `query`: a case-insensitive exact match, otherwise
+/// the best fuzzy match, preferring earlier names on ties.
+pub fn best_match<'a>(names: impl IntoIterator<Item = &'a str>, query: &str) -> Option<usize> {
+    let matcher = SkimMatcherV2::default();
+    names
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, name)| {
+            if name.eq_ignore_ascii_case(query) {
+                Some((i64::MAX, idx))
+            } else {
+                matcher.fuzzy_match(name, query).map(|score| (score, idx))
+            }
+        })
+        .min_by_key(|(score, idx)| (-score, *idx))
+        .map(|(_, idx)| idx)
+}
+
+fn ranked(idx_score: &HashMap<u32, i64>) -> Vec<u32> {
+    idx_score
+        .iter()
+        .sorted_by_key(|(idx, score)| (-**score, **idx))
+        .map(|(idx, _)| *idx)
+        .collect()
+}
+
+/// Splits a `column:pattern` (or `a,b:pattern`) query into the indices of the
+/// columns to scan and the pattern; every column is scanned when the prefix does
+/// not name existing columns.
+fn scope<'a>(df: &DataFrame, query: &'a str) -> (Vec<usize>, &'a str) {
+    query
+        .split_once(':')
+        .and_then(|(names, pat)| {
+            names
+                .split(',')
+                .map(|name| df.get_column_index(name.trim()))
+                .collect::<Option<Vec<_>>>()
+                .map(|columns| (columns, pat))
+        })
+        .unwrap_or_else(|| ((0..df.width()).collect(), query))
+}
+
+/// Matching rows with their positions in the searched frame.
+type Found = (DataFrame, Vec<u32>);
+
+#[derive(Debug, Clone)]
+struct SyncDataFrame(Arc<Mutex<Option<Found>>>);
 
 impl SyncDataFrame {
     fn new() -> Self {
         Self(Arc::new(Mutex::new(None)))
     }
 
-    fn insert(&self, df: DataFrame) {
+    fn insert(&self, df: DataFrame, rows: Vec<u32>) {
         if let Ok(mut mut_grd) = self.0.lock() {
-            *mut_grd = Some(df);
+            *mut_grd = Some((df, rows));
         }
     }
 
-    fn take(&self) -> Option<DataFrame> {
+    fn take(&self) -> Option<Found> {
         self.0.lock().ok().and_then(|mut mut_grd| mut_grd.take())
     }
 }
@@ -251,3 +455,80 @@ impl Interval {
         self.last_tick = Instant::now();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use polars::df;
+
+    use super::*;
+
+    #[test]
+    fn test_scope() {
+        let df = df!(
+            "status" => ["ok", "error"],
+            "message" => ["fine", "boom"],
+        )
+        .unwrap();
+        assert_eq!(scope(&df, "status:/err/"), (vec![0], "/err/"));
+        assert_eq!(scope(&df, "message, status:bo"), (vec![1, 0], "bo"));
+        assert_eq!(scope(&df, "12:30"), (vec![0, 1], "12:30"));
+        assert_eq!(scope(&df, "boom"), (vec![0, 1], "boom"));
+    }
+
+    #[test]
+    fn test_highlight() {
+        let df = df!(
+            "status" => ["ok", "error"],
+            "message" => ["fine", "boom"],
+        )
+        .unwrap();
+        let highlight = Highlight::exact(&df, "status:rr").unwrap();
+        assert!(highlight.applies_to("status"));
+        assert!(!highlight.applies_to("message"));
+        assert_eq!(highlight.indices("error"), vec![1, 2]);
+        assert_eq!(highlight.indices("ërrör"), vec![1, 2]);
+
+        let highlight = Highlight::exact(&df, "/o+/").unwrap();
+        assert!(highlight.applies_to("message"));
+        assert_eq!(highlight.indices("boom"), vec![1, 2]);
+
+        let highlight = Highlight::fuzzy(&df, "bm").unwrap();
+        assert_eq!(highlight.indices("boom"), vec![0, 3]);
+
+        assert!(Highlight::exact(&df, "status:").is_none());
+    }
+
+    #[test]
+    fn test_narrows() {
+        let df = df!(
+            "status" => ["ok", "error"],
+            "message" => ["fine", "boom"],
+        )
+        .unwrap();
+        assert!(narrows(&df, "er", "err"));
+        assert!(narrows(&df, "status:e", "status:er"));
+        assert!(!narrows(&df, "", "e"));
+        assert!(!narrows(&df, "err", "er"));
+        assert!(!narrows(&df, "e", "status:er"));
+        assert!(!narrows(&df, "/er", "/er/"));
+    }
+
+    #[test]
+    fn test_best_match() {
+        let names = ["order_id", "customer_name", "Price", "unit_price"];
+        assert_eq!(best_match(names, "price"), Some(2));
+        assert_eq!(best_match(names, "cname"), Some(1));
+        assert_eq!(best_match(names, "uprc"), Some(3));
+        assert_eq!(best_match(names, "zzz"), None);
+    }
+
+    #[test]
+    fn test_contain() {
+        assert_eq!(Contain::default().score("server error", "error"), Some(1));
+        assert_eq!(
+            Contain::default().score("server error", "/^serv.*r$/"),
+            Some(1)
+        );
+        assert_eq!(Contain::default().score("server error", "/^error/"), None);
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt::Write;
+
+use super::png::Image;
+
+/// Sixel sequence drawing `image` scaled to fit `width` x `height` pixels,
+/// keeping its aspect ratio. Colors are reduced to a 6x6x6 cube, and mostly
+/// transparent pixels are left as they are on the screen.
+pub fn encode(image: &Image, width: usize, height: usize) -> String {
+    let scale = f64::min(
+        width as f64 / image.width as f64,
+        height as f64 / image.height as f64,
+    );
+    let width = ((image.width as f64 * scale) as usize).clamp(1, width.max(1));
+    let height = ((image.height as f64 * scale) as usize).clamp(1, height.max(1));
+    let colors = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| quantize(image.pixel(x * image.width / width, y * image.height / height)))
+        .collect::<Vec<_>>();
+
+    let mut sixel = format!("\x1bP0;1q\"1;1;{width};{height}");
+    let mut used = [false; 216];
+    for color in colors.iter().flatten() {
+        used[*color as usize] = true;
+    }
+    for color in (0..216).filter(|color| used[*color]) {
+        let [r, g, b] = [color / 36, color / 6 % 6, color % 6].map(|level| level * 20);
+        let _ = write!(sixel, "#{color};2;{r};{g};{b}");
+    }
+    for top in (0..height).step_by(6) {
+        let band = &colors[top * width..(top + 6).min(height) * width];
+        let mut present = [false; 216];
+        for color in band.iter().flatten() {
+            present[*color as usize] = true;
+        }
+        let rows = (0..216).filter(|color| present[*color]).map(|color| {
+            let sixels = (0..width).map(|x| {
+                let bits = (0..band.len() / width)
+                    .filter(|dy| band[dy * width + x] == Some(color as u8))
+                    .fold(0, |bits, dy| bits | 1 << dy);
+                char::from(63 + bits)
+            });
+            format!("#{color}{}", run_length(sixels))
+        });
+        sixel.push_str(&rows.collect::<Vec<_>>().join("$"));
+        sixel.push('-');
+    }
+    sixel.push_str("\x1b\\");
+    sixel
+}
+
+/// Index of the color in the 6x6x6 cube, or `None` for a mostly transparent
+/// pixel.
+fn quantize([r, g, b, a]: [u8; 4]) -> Option<u8> {
+    let level = |channel: u8| (u16::from(channel) * 5 + 127) / 255;
+    (a >= 128).then(|| (level(r) * 36 + level(g) * 6 + level(b)) as u8)
+}
+
+/// Sixels with runs of more than three repeated as `!{count}{sixel}`.
+fn run_length(sixels: impl Iterator<Item = char>) -> String {
+    let mut encoded = String::new();
+    let mut sixels = sixels.peekable();
+    while let Some(sixel) = sixels.next() {
+        let mut count = 1;
+        while sixels.next_if_eq(&sixel).is_some() {
+            count += 1;
+        }
+        if count > 3 {
+            let _ = write!(encoded, "!{count}{sixel}");
+        } else {
+            encoded.extend(std::iter::repeat_n(sixel, count));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, quantize, run_length};
+    use crate::misc::png::Image;
+
+    #[test]
+    fn test_quantizes_to_the_color_cube() {
+        assert_eq!(quantize([0, 0, 0, 255]), Some(0));
+        assert_eq!(quantize([255, 255, 255, 255]), Some(215));
+        assert_eq!(quantize([255, 0, 0, 200]), Some(180));
+        assert_eq!(quantize([255, 0, 0, 100]), None);
+    }
+
+    #[test]
+    fn test_repeats_long_runs() {
+        assert_eq!(run_length("~~~~~@@@?".chars()), "!5~@@@?");
+        assert_eq!(run_length("".chars()), "");
+    }
+
+    #[test]
+    fn test_encodes_bands_of_six_rows() {
+        // red over the top six rows and white, then transparent, below them
+        let pixels = (0..8)
+            .flat_map(|y| {
+                (0..2).map(move |x| match y {
+                    0..6 => [255, 0, 0, 255],
+                    _ if x == 0 => [255, 255, 255, 255],
+                    _ => [0, 0, 0, 0],
+                })
+            })
+            .collect();
+        let image = Image {
+            width: 2,
+            height: 8,
+            pixels,
+        };
+        assert_eq!(
+            encode(&image, 2, 8),
+            "\x1bP0;1q\"1;1;2;8#180;2;100;0;0#215;2;100;100;100#180~~-#215B?-\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_scales_to_fit_keeping_the_aspect_ratio() {
+        let image = Image {
+            width: 4,
+            height: 2,
+            pixels: vec![[0, 0, 0, 255]; 8],
+        };
+        assert!(encode(&image, 100, 100).starts_with("\x1bP0;1q\"1;1;100;50#0"));
+        assert!(encode(&image, 2, 100).starts_with("\x1bP0;1q\"1;1;2;1#0"));
+    }
+}
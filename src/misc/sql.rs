@@ -2,9 +2,10 @@ use std::{
     borrow::Cow,
     ops::DerefMut,
     path::PathBuf,
-    sync::{LazyLock, Mutex},
+    sync::{Arc, LazyLock, Mutex},
 };
 
+use anyhow::anyhow;
 use indexmap::IndexMap;
 use polars::{
     error::PolarsResult,
@@ -15,7 +16,14 @@ use polars::{
 use polars_sql::SQLContext;
 use url::Url;
 
-use crate::{io::DataSource, misc::table_name_generator::TableNameGeneratorExt};
+use crate::{
+    AppResult,
+    io::{DataSource, reader::ReaderSource},
+    misc::{
+        download::download_to_temp, remote_load::Reader,
+        table_name_generator::TableNameGeneratorExt, type_inferer::TypeInferer,
+    },
+};
 
 use super::polars_ext::AnyValueExt;
 use super::type_ext::UnwrapOrGracefulShutdown;
@@ -57,6 +65,49 @@ impl SqlBackend {
         self.sql.unregister(name);
     }
 
+    /// Remembers how the table `name` was read, allowing it to be reloaded.
+    pub fn set_reader(&mut self, name: &str, reader: TableReader) {
+        if let Some(info) = self.schema.schema.get_mut(name) {
+            info.reader = Some(reader);
+        }
+    }
+
+    /// Reads the table `name` again from its file or URL with the reader it was
+    /// first read with, replacing the registered frame.
+    pub fn reload(&mut self, name: &str) -> AppResult<DataFrame> {
+        let info = self
+            .schema
+            .get(name)
+            .ok_or(anyhow!("Table '{name}' not found"))?;
+        let reader = info
+            .reader
+            .clone()
+            .ok_or(anyhow!("Table '{name}' was not read from a file"))?;
+        let df = match info.source() {
+            TableSource::File(path) => reader.read(ReaderSource::File(path.clone()))?,
+            TableSource::Url(url) => {
+                let file = download_to_temp(url)?;
+                reader.read(ReaderSource::File(file.path().to_owned()))?
+            }
+            TableSource::Stdin | TableSource::User => {
+                return Err(anyhow!("Table '{name}' was not read from a file"));
+            }
+        };
+        self.set_reloaded(name, &df);
+        Ok(df)
+    }
+
+    /// Puts `df`, read again with the reader of the table `name`, in place of
+    /// its registered frame.
+    pub fn set_reloaded(&mut self, name: &str, df: &DataFrame) {
+        if let Some(info) = self.schema.get(name) {
+            let mut reloaded = TableInfo::new(info.source().clone(), df);
+            reloaded.reader = info.reader.clone();
+            self.schema.insert(name.to_owned(), reloaded);
+        }
+        self.sql.register(name, df.clone().lazy());
+    }
+
     pub fn unset_default(&mut self) {
         self.sql.unregister("_");
     }
@@ -126,6 +177,7 @@ impl BackendSchema {
 #[derive(Debug, Clone, PartialEq)]
 pub struct TableInfo {
     origin: TableSource,
+    reader: Option<TableReader>,
     height: usize,
     width: usize,
     total_null: usize,
@@ -138,6 +190,7 @@ impl TableInfo {
         let schema = TableSchema::new(df);
         Self {
             origin: input,
+            reader: None,
             height: df.height(),
             width: df.width(),
             total_null: schema.iter().map(|(_, info)| info.null_count()).sum(),
@@ -150,6 +203,10 @@ impl TableInfo {
         &self.origin
     }
 
+    pub fn reader(&self) -> Option<&TableReader> {
+        self.reader.as_ref()
+    }
+
     pub fn height(&self) -> usize {
         self.height
     }
@@ -171,6 +228,42 @@ impl TableInfo {
     }
 }
 
+/// Reader and type inference a table was read with, and the name of its frame
+/// among the ones the reader returns.
+#[derive(Debug, Clone)]
+pub struct TableReader {
+    reader: Arc<dyn Reader>,
+    frame: String,
+    type_inferer: TypeInferer,
+}
+
+impl TableReader {
+    pub fn new(reader: Arc<dyn Reader>, frame: String, type_inferer: TypeInferer) -> Self {
+        Self {
+            reader,
+            frame,
+            type_inferer,
+        }
+    }
+
+    pub fn read(&self, source: ReaderSource) -> AppResult<DataFrame> {
+        let frames = self.reader.read_to_data_frames(source)?;
+        let single = frames.len() == 1;
+        let (_, mut df) = frames
+            .into_iter()
+            .find(|(name, _)| single || *name == self.frame)
+            .ok_or(anyhow!("'{}' is no longer in its source", self.frame))?;
+        self.type_inferer.update(&mut df);
+        Ok(df)
+    }
+}
+
+impl PartialEq for TableReader {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.reader, &other.reader) && self.frame == other.frame
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TableSource {
     Url(Url),
@@ -0,0 +1,141 @@
+use std::{sync::OnceLock, time::Duration};
+
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+
+/// Longest wait for the terminal to answer the background color query.
+const TIMEOUT: Duration = Duration::from_millis(200);
+
+static BACKGROUND: OnceLock<Option<Background>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    Light,
+    Dark,
+}
+
+/// Background of the terminal, if it answered the query of [`detect`].
+pub fn background() -> Option<Background> {
+    BACKGROUND.get().copied().flatten()
+}
+
+/// Asks the terminal for its background color (OSC 11).
+///
+/// Must run before the terminal events are read, as the answer arrives on the
+/// same input. The query is followed by a device attributes request, which
+/// nearly every terminal answers, so the read ends even when the background
+/// color query is not supported; otherwise it stops after [`TIMEOUT`].
+pub fn detect() -> Option<Background> {
+    *BACKGROUND.get_or_init(|| {
+        enable_raw_mode().ok()?;
+        let background = query();
+        let _ = disable_raw_mode();
+        background
+    })
+}
+
+/// Reads the answer without blocking, so nothing typed after the deadline is
+/// taken from the terminal events.
+#[cfg(unix)]
+fn query() -> Option<Background> {
+    use std::{
+        fs::OpenOptions,
+        io::{ErrorKind, Read, Write},
+        os::unix::fs::OpenOptionsExt,
+        thread,
+        time::Instant,
+    };
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open("/dev/tty")
+        .ok()?;
+    tty.write_all(b"\x1b]11;?\x07\x1b[c").ok()?;
+    tty.flush().ok()?;
+
+    let deadline = Instant::now() + TIMEOUT;
+    let mut response = Vec::new();
+    let mut buf = [0_u8; 64];
+    while !is_complete(&response) && Instant::now() < deadline {
+        match tty.read(&mut buf) {
+            Ok(0) => break,
+            Ok(len) => response.extend_from_slice(&buf[..len]),
+            Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(5))
+            }
+            Err(err) if err.kind() == ErrorKind::Interrupted => (),
+            Err(_) => break,
+        }
+    }
+    parse(&String::from_utf8_lossy(&response))
+}
+
+#[cfg(not(unix))]
+fn query() -> Option<Background> {
+    None
+}
+
+/// Whether the response ends with the answer to the device attributes request.
+fn is_complete(response: &[u8]) -> bool {
+    response.ends_with(b"c")
+        && response
+            .windows(3)
+            .rposition(|window| window == b"\x1b[?")
+            .is_some_and(|start| {
+                response[start + 3..response.len() - 1]
+                    .iter()
+                    .all(|byte| byte.is_ascii_digit() || *byte == b';')
+            })
+}
+
+/// Reads the background from an OSC 11 answer, e.g. `\x1b]11;rgb:ffff/ffff/ffff\x07`.
+fn parse(response: &str) -> Option<Background> {
+    let (_, color) = response.split_once("]11;rgb:")?;
+    let color = color.split(['\x07', '\x1b']).next()?;
+    let components = color
+        .split('/')
+        .map(|component| {
+            let max = 16_u32.checked_pow(component.len() as u32)?.checked_sub(1)?;
+            let value = u32::from_str_radix(component, 16).ok()?;
+            (max > 0 && component.len() <= 4).then(|| value as f64 / max as f64)
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let [r, g, b] = components[..] else {
+        return None;
+    };
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    Some(if luminance > 0.5 {
+        Background::Light
+    } else {
+        Background::Dark
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_background() {
+        assert_eq!(
+            parse("\x1b]11;rgb:ffff/ffff/ffff\x07\x1b[?62;22c"),
+            Some(Background::Light)
+        );
+        assert_eq!(
+            parse("\x1b]11;rgb:1e1e/1e1e/2e2e\x1b\\"),
+            Some(Background::Dark)
+        );
+        assert_eq!(parse("\x1b]11;rgb:fd/f6/e3\x07"), Some(Background::Light));
+        assert_eq!(parse("\x1b[?62;22c"), None);
+        assert_eq!(parse("\x1b]11;rgb:ffff/ffff\x07"), None);
+    }
+
+    #[test]
+    fn test_detects_complete_response() {
+        assert!(is_complete(b"\x1b]11;rgb:0000/0000/0000\x07\x1b[?62;22c"));
+        assert!(is_complete(b"\x1b[?1;2c"));
+        assert!(!is_complete(b"\x1b]11;rgb:0000/0000/0000\x07"));
+        assert!(!is_complete(b"\x1b]11;rgb:cccc/cccc/cccc\x07\x1b[?62;"));
+    }
+}
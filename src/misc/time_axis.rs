@@ -0,0 +1,211 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime};
+use polars::prelude::{DataType, TimeUnit};
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+
+/// Distance between two labels of a time axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Seconds(i64),
+    Months(i64),
+}
+
+const STEPS: [Step; 21] = [
+    Step::Seconds(1),
+    Step::Seconds(5),
+    Step::Seconds(15),
+    Step::Seconds(30),
+    Step::Seconds(MINUTE),
+    Step::Seconds(5 * MINUTE),
+    Step::Seconds(15 * MINUTE),
+    Step::Seconds(30 * MINUTE),
+    Step::Seconds(HOUR),
+    Step::Seconds(3 * HOUR),
+    Step::Seconds(6 * HOUR),
+    Step::Seconds(12 * HOUR),
+    Step::Seconds(DAY),
+    Step::Seconds(2 * DAY),
+    Step::Seconds(7 * DAY),
+    Step::Months(1),
+    Step::Months(3),
+    Step::Months(6),
+    Step::Months(12),
+    Step::Months(24),
+    Step::Months(60),
+];
+
+/// Seconds in one unit of the physical values of a Date or Datetime column.
+pub fn seconds_per_unit(dtype: &DataType) -> Option<f64> {
+    match dtype {
+        DataType::Date => Some(DAY as f64),
+        DataType::Datetime(TimeUnit::Milliseconds, _) => Some(1e-3),
+        DataType::Datetime(TimeUnit::Microseconds, _) => Some(1e-6),
+        DataType::Datetime(TimeUnit::Nanoseconds, _) => Some(1e-9),
+        _ => None,
+    }
+}
+
+/// Bounds and evenly spaced labels of an axis over the times between `min`
+/// and `max`, given in units of `seconds_per_unit`. The bounds are widened to
+/// whole steps (seconds, minutes, hours, days, months, or years) so that at
+/// most `ticks` labels fall on round times.
+pub fn time_axis(
+    min: f64,
+    max: f64,
+    seconds_per_unit: f64,
+    ticks: usize,
+) -> ([f64; 2], Vec<String>) {
+    let min = (min * seconds_per_unit).floor() as i64;
+    let max = (max * seconds_per_unit).ceil() as i64;
+    let span = (max - min).max(1);
+    // month after the last time, unless it starts a month itself
+    let end_month = month_index(max) + i64::from(!is_month_start(max));
+    let month_span = end_month - month_index(min);
+    let intervals = ticks.max(2) as i64 - 1;
+    let step = STEPS
+        .into_iter()
+        .find(|step| match step {
+            Step::Seconds(seconds) => span <= seconds * intervals,
+            Step::Months(months) => month_span <= months * intervals,
+        })
+        .unwrap_or_else(|| {
+            let years = 12 * intervals;
+            Step::Months(12 * nice((month_span + years - 1) / years))
+        });
+
+    let times = match step {
+        Step::Seconds(seconds) => {
+            let start = min.div_euclid(seconds) * seconds;
+            let end = (max + seconds - 1).div_euclid(seconds) * seconds;
+            let end = end.max(start + seconds);
+            (0..=(end - start) / seconds)
+                .map(|idx| start + idx * seconds)
+                .collect::<Vec<_>>()
+        }
+        Step::Months(months) => {
+            let start = month_index(min).div_euclid(months) * months;
+            let end = (end_month + months - 1).div_euclid(months) * months;
+            let end = end.max(start + months);
+            (0..=(end - start) / months)
+                .map(|idx| month_start(start + idx * months))
+                .collect()
+        }
+    };
+
+    let format = match step {
+        Step::Seconds(seconds) if seconds < MINUTE && span < DAY => "%H:%M:%S",
+        Step::Seconds(seconds) if seconds < MINUTE => "%m-%d %H:%M:%S",
+        Step::Seconds(seconds) if seconds < DAY && span < DAY => "%H:%M",
+        Step::Seconds(seconds) if seconds < DAY => "%m-%d %H:%M",
+        Step::Seconds(_) => "%Y-%m-%d",
+        Step::Months(months) if months < 12 => "%Y-%m",
+        Step::Months(_) => "%Y",
+    };
+    let labels = times
+        .iter()
+        .map(|time| {
+            datetime(*time)
+                .map(|time| time.format(format).to_string())
+                .unwrap_or_default()
+        })
+        .collect();
+    let bounds = [
+        times.first().copied().unwrap_or(min) as f64 / seconds_per_unit,
+        times.last().copied().unwrap_or(max) as f64 / seconds_per_unit,
+    ];
+    (bounds, labels)
+}
+
+/// Smallest of 1, 2, 5, 10, 20, 50, ... not below `value`.
+fn nice(value: i64) -> i64 {
+    let mut magnitude = 1;
+    loop {
+        for factor in [1, 2, 5] {
+            if factor * magnitude >= value {
+                return factor * magnitude;
+            }
+        }
+        magnitude *= 10;
+    }
+}
+
+fn datetime(seconds: i64) -> Option<NaiveDateTime> {
+    DateTime::from_timestamp(seconds, 0).map(|time| time.naive_utc())
+}
+
+/// Months between January 1970 and the month of `seconds`.
+fn month_index(seconds: i64) -> i64 {
+    datetime(seconds)
+        .map(|time| (time.year() as i64 - 1970) * 12 + time.month0() as i64)
+        .unwrap_or_default()
+}
+
+fn is_month_start(seconds: i64) -> bool {
+    month_start(month_index(seconds)) == seconds
+}
+
+/// Seconds at the start of the month `index` months after January 1970.
+fn month_start(index: i64) -> i64 {
+    NaiveDate::from_ymd_opt(
+        (1970 + index.div_euclid(12)) as i32,
+        index.rem_euclid(12) as u32 + 1,
+        1,
+    )
+    .and_then(|date| date.and_hms_opt(0, 0, 0))
+    .map(|time| time.and_utc().timestamp())
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DAY, HOUR, time_axis};
+
+    #[test]
+    fn test_hours_within_a_day() {
+        // 2024-01-01 01:10 to 2024-01-01 10:50
+        let start = 1_704_071_400.0;
+        let (bounds, labels) = time_axis(start, start + 9.0 * 3600.0 + 2400.0, 1.0, 6);
+        assert_eq!(labels, ["00:00", "03:00", "06:00", "09:00", "12:00"]);
+        assert_eq!(
+            bounds,
+            [1_704_067_200.0, 1_704_067_200.0 + 12.0 * HOUR as f64]
+        );
+    }
+
+    #[test]
+    fn test_days_of_a_date_column() {
+        // days since 1970 of 2024-01-02 and 2024-01-09
+        let (bounds, labels) = time_axis(19_724.0, 19_731.0, DAY as f64, 8);
+        assert_eq!(labels.first().map(String::as_str), Some("2024-01-02"));
+        assert_eq!(labels.last().map(String::as_str), Some("2024-01-09"));
+        assert_eq!(bounds, [19_724.0, 19_731.0]);
+    }
+
+    #[test]
+    fn test_months_fall_on_month_starts() {
+        // 2024-01-15 to 2024-06-10, in milliseconds
+        let (_, labels) = time_axis(1_705_276_800_000.0, 1_717_977_600_000.0, 1e-3, 8);
+        assert_eq!(
+            labels,
+            [
+                "2024-01", "2024-02", "2024-03", "2024-04", "2024-05", "2024-06", "2024-07"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decades_use_round_years() {
+        // 1990-01-01 to 2030-01-01
+        let (_, labels) = time_axis(631_152_000.0, 1_893_456_000.0, 1.0, 5);
+        assert_eq!(labels, ["1990", "2000", "2010", "2020", "2030"]);
+    }
+
+    #[test]
+    fn test_single_instant_gets_a_range() {
+        let (bounds, labels) = time_axis(0.0, 0.0, 1.0, 5);
+        assert!(bounds[0] < bounds[1]);
+        assert_eq!(labels.len(), 2);
+    }
+}
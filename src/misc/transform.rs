@@ -0,0 +1,2123 @@
+use std::{fmt::Display, str::FromStr, sync::Arc};
+
+use anyhow::anyhow;
+use itertools::Itertools;
+use polars::{
+    frame::DataFrame,
+    prelude::{
+        AnyValue, ChunkAgg, Column, DataType, ExplodeOptions, Expr, FillNullStrategy, IntoLazy,
+        JoinArgs, JoinCoalesce, JoinType, NULL, NamedFrom, NonExistent, QuantileMethod, RankMethod,
+        RankOptions, RollingOptionsFixedWindow, SeriesMethods, SortMultipleOptions, TimeZone,
+        UnionArgs, UniqueKeepStrategy, col, concat_lf_diagonal, len, lit, when,
+    },
+    series::Series,
+};
+use regex::{NoExpand, Regex};
+
+use crate::{
+    AppResult,
+    misc::{
+        polars_ext::{AnyValueExt, SeriesExt},
+        sql::sql,
+    },
+};
+
+const TRANSPOSE_MAX_ROWS: usize = 1_000;
+const DUMMIES_MAX_CATEGORIES: usize = 100;
+const STATS_TOP_VALUES: usize = 5;
+const DESCRIBE_STATS: [&str; 8] = ["count", "mean", "std", "min", "25%", "50%", "75%", "max"];
+
+pub fn mutate(df: &DataFrame, name: &str, expr: &str) -> AppResult<DataFrame> {
+    let computed = sql().execute(
+        &format!("SELECT {expr} AS \"{}\" FROM _", escape_ident(name)),
+        df.clone(),
+    )?;
+    let mut df = df.clone();
+    df.with_column(computed.column(name)?.clone())?;
+    Ok(df)
+}
+
+/// Name of the column carrying the row numbers through a row operation.
+const ROW_NUMBER: &str = "__tabiew_row_number";
+
+/// Appends a column numbering the rows by `numbers`, or from one in their
+/// order when `None`, to be taken back by [`take_row_numbers`] once the rows
+/// are sorted or filtered.
+pub fn number_rows(df: &DataFrame, numbers: Option<&[usize]>) -> AppResult<DataFrame> {
+    let numbers = match numbers {
+        Some(numbers) => numbers.iter().map(|number| *number as u64).collect_vec(),
+        None => (1..=df.height() as u64).collect_vec(),
+    };
+    let mut df = df.clone();
+    df.with_column(Column::new(ROW_NUMBER.into(), numbers))?;
+    Ok(df)
+}
+
+/// Removes the column added by [`number_rows`], returning the numbers of the
+/// rows left, or `None` when the operation did not keep the column.
+pub fn take_row_numbers(df: &mut DataFrame) -> Option<Arc<[usize]>> {
+    let column = df.drop_in_place(ROW_NUMBER).ok()?;
+    column
+        .u64()
+        .ok()?
+        .iter()
+        .map(|number| number.map(|number| number as usize))
+        .collect()
+}
+
+/// Sorts the frame by a single column, keeping nulls last.
+pub fn sort(df: &DataFrame, name: &str, descending: bool) -> AppResult<DataFrame> {
+    Ok(df.sort(
+        [name],
+        SortMultipleOptions::default()
+            .with_order_descending(descending)
+            .with_nulls_last(true),
+    )?)
+}
+
+/// Parses a string column into dates or datetimes using a strftime format such
+/// as `%d/%m/%Y %H:%M`; formats without a time component produce dates.
+pub fn parse_dates(df: &DataFrame, name: &str, fmt: &str) -> AppResult<DataFrame> {
+    let parsed = df
+        .column(name)?
+        .as_materialized_series()
+        .refine_to_datetime_with(fmt)?;
+    let mut df = df.clone();
+    df.replace(name, Column::from(parsed))?;
+    Ok(df)
+}
+
+/// Interprets a datetime column in `from` and converts it to `to`, both being
+/// IANA time zone names such as `UTC` or `Europe/Berlin`.
+pub fn convert_time_zone(df: &DataFrame, name: &str, from: &str, to: &str) -> AppResult<DataFrame> {
+    if !matches!(df.column(name)?.dtype(), DataType::Datetime(_, _)) {
+        return Err(anyhow!(
+            "Column '{name}' is not a datetime column, try parse-dates first"
+        ));
+    }
+    Ok(df
+        .clone()
+        .lazy()
+        .with_column(
+            col(name)
+                .dt()
+                .replace_time_zone(Some(time_zone(from)?), lit("raise"), NonExistent::Raise)
+                .dt()
+                .convert_time_zone(time_zone(to)?),
+        )
+        .collect()?)
+}
+
+fn time_zone(name: &str) -> AppResult<TimeZone> {
+    TimeZone::opt_try_new(Some(name))?.ok_or(anyhow!("Invalid time zone '{name}'"))
+}
+
+/// Bins a numeric column into a `{name}_bin` column holding the interval of
+/// every value, e.g. `(0, 5]`.
+pub fn cut(df: &DataFrame, name: &str, bins: &Bins) -> AppResult<DataFrame> {
+    let breaks = match bins {
+        Bins::Count(count) => {
+            let values = df.column(name)?.cast(&DataType::Float64)?;
+            let values = values.f64()?;
+            let (Some(min), Some(max)) = (values.min(), values.max()) else {
+                return Err(anyhow!("Column '{name}' has no values to bin"));
+            };
+            let width = (max - min) / *count as f64;
+            (1..*count)
+                .map(|idx| min + width * idx as f64)
+                .collect_vec()
+        }
+        Bins::Edges(edges) => edges.clone(),
+    };
+    let value = col(name).cast(DataType::Float64);
+    let bounds = [f64::NEG_INFINITY]
+        .into_iter()
+        .chain(breaks)
+        .chain([f64::INFINITY])
+        .collect_vec();
+    let bin = bounds
+        .windows(2)
+        .rev()
+        .fold(lit(NULL).cast(DataType::String), |rest, bounds| {
+            let (lower, upper) = (bounds[0], bounds[1]);
+            when(value.clone().lt_eq(lit(upper)))
+                .then(lit(format!("({lower}, {upper}]")))
+                .otherwise(rest)
+        });
+    Ok(df
+        .clone()
+        .lazy()
+        .with_column(bin.alias(format!("{name}_bin")))
+        .collect()?)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Bins {
+    Count(usize),
+    Edges(Vec<f64>),
+}
+
+impl FromStr for Bins {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains(',') {
+            let edges = s
+                .split(',')
+                .map(str::trim)
+                .filter(|edge| !edge.is_empty())
+                .map(|edge| {
+                    edge.parse::<f64>()
+                        .map_err(|_| anyhow!("Invalid bin edge '{edge}'"))
+                })
+                .collect::<AppResult<Vec<_>>>()?;
+            if edges.is_empty() || !edges.iter().tuple_windows().all(|(a, b)| a < b) {
+                Err(anyhow!(
+                    "Bin edges must be increasing numbers, e.g. 0,10,100"
+                ))
+            } else {
+                Ok(Bins::Edges(edges))
+            }
+        } else {
+            match s.parse::<usize>() {
+                Ok(count) if count > 0 => Ok(Bins::Count(count)),
+                _ => Err(anyhow!(
+                    "Invalid bins '{s}', use a bin count or comma separated edges"
+                )),
+            }
+        }
+    }
+}
+
+impl Display for Bins {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bins::Count(count) => write!(f, "{count} bins"),
+            Bins::Edges(edges) => write!(f, "edges {}", edges.iter().join(", ")),
+        }
+    }
+}
+
+/// Appends a `{name}_rank` (or `{name}_pct_rank`) column ranking the values of
+/// a column.
+pub fn rank(df: &DataFrame, name: &str, rank: Rank, descending: bool) -> AppResult<DataFrame> {
+    let options = |method| RankOptions { method, descending };
+    let expr = match rank {
+        Rank::Average => col(name).rank(options(RankMethod::Average), None),
+        Rank::Min => col(name).rank(options(RankMethod::Min), None),
+        Rank::Max => col(name).rank(options(RankMethod::Max), None),
+        Rank::Dense => col(name).rank(options(RankMethod::Dense), None),
+        Rank::Ordinal => col(name).rank(options(RankMethod::Ordinal), None),
+        Rank::Percent => {
+            col(name)
+                .rank(options(RankMethod::Max), None)
+                .cast(DataType::Float64)
+                / col(name).count().cast(DataType::Float64)
+        }
+    };
+    Ok(df
+        .clone()
+        .lazy()
+        .with_column(expr.alias(rank.column_name(name)))
+        .collect()?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Rank {
+    #[default]
+    Average,
+    Min,
+    Max,
+    Dense,
+    Ordinal,
+    Percent,
+}
+
+impl Rank {
+    pub fn column_name(&self, name: &str) -> String {
+        match self {
+            Rank::Percent => format!("{name}_pct_rank"),
+            _ => format!("{name}_rank"),
+        }
+    }
+}
+
+impl FromStr for Rank {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "average" => Ok(Rank::Average),
+            "min" => Ok(Rank::Min),
+            "max" => Ok(Rank::Max),
+            "dense" => Ok(Rank::Dense),
+            "ordinal" => Ok(Rank::Ordinal),
+            _ => Err(anyhow!(
+                "Invalid rank method '{s}', use average, min, max, dense, or ordinal"
+            )),
+        }
+    }
+}
+
+/// Appends a cumulative or rolling window column computed over a column.
+pub fn window(df: &DataFrame, name: &str, func: WindowFunc) -> AppResult<DataFrame> {
+    let rolling = |window_size| RollingOptionsFixedWindow {
+        window_size,
+        ..Default::default()
+    };
+    let expr = match func {
+        WindowFunc::CumSum => col(name).cum_sum(false),
+        WindowFunc::CumMin => col(name).cum_min(false),
+        WindowFunc::CumMax => col(name).cum_max(false),
+        WindowFunc::Rolling(Rolling::Mean, size) => col(name).rolling_mean(rolling(size)),
+        WindowFunc::Rolling(Rolling::Sum, size) => col(name).rolling_sum(rolling(size)),
+        WindowFunc::Rolling(Rolling::Min, size) => col(name).rolling_min(rolling(size)),
+        WindowFunc::Rolling(Rolling::Max, size) => col(name).rolling_max(rolling(size)),
+        WindowFunc::Rolling(Rolling::Std, size) => col(name).rolling_std(rolling(size)),
+    };
+    Ok(df
+        .clone()
+        .lazy()
+        .with_column(expr.alias(func.column_name(name)))
+        .collect()?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowFunc {
+    CumSum,
+    CumMin,
+    CumMax,
+    Rolling(Rolling, usize),
+}
+
+impl WindowFunc {
+    pub fn column_name(&self, name: &str) -> String {
+        match self {
+            WindowFunc::CumSum => format!("{name}_cumsum"),
+            WindowFunc::CumMin => format!("{name}_cummin"),
+            WindowFunc::CumMax => format!("{name}_cummax"),
+            WindowFunc::Rolling(func, size) => format!("{name}_rolling_{func}_{size}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rolling {
+    Mean,
+    Sum,
+    Min,
+    Max,
+    Std,
+}
+
+impl FromStr for Rolling {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mean" => Ok(Rolling::Mean),
+            "sum" => Ok(Rolling::Sum),
+            "min" => Ok(Rolling::Min),
+            "max" => Ok(Rolling::Max),
+            "std" => Ok(Rolling::Std),
+            _ => Err(anyhow!(
+                "Invalid rolling function '{s}', use mean, sum, min, max, or std"
+            )),
+        }
+    }
+}
+
+impl Display for Rolling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rolling::Mean => write!(f, "mean"),
+            Rolling::Sum => write!(f, "sum"),
+            Rolling::Min => write!(f, "min"),
+            Rolling::Max => write!(f, "max"),
+            Rolling::Std => write!(f, "std"),
+        }
+    }
+}
+
+pub fn rename(df: &DataFrame, old: &str, new: &str) -> AppResult<DataFrame> {
+    if old != new && df.schema().contains(new) {
+        return Err(anyhow!("Column '{new}' already exists"));
+    }
+    let mut df = df.clone();
+    df.rename(old, new.into())?;
+    Ok(df)
+}
+
+/// Renames every column matching `pattern`, returning the new frame and the
+/// number of renamed columns.
+pub fn rename_regex(
+    df: &DataFrame,
+    pattern: &str,
+    replacement: &str,
+) -> AppResult<(DataFrame, usize)> {
+    let re = Regex::new(pattern)?;
+    let names = df
+        .get_column_names()
+        .into_iter()
+        .map(|name| re.replace_all(name, replacement).into_owned())
+        .collect_vec();
+    if let Some(dup) = names.iter().duplicates().next() {
+        return Err(anyhow!("Renaming would produce duplicate column '{dup}'"));
+    }
+    let renamed = df
+        .get_column_names()
+        .into_iter()
+        .zip(names.iter())
+        .filter(|(old, new)| old.as_str() != new.as_str())
+        .count();
+    let df = DataFrame::new_infer_height(
+        df.columns()
+            .iter()
+            .zip(names)
+            .map(|(col, name)| col.clone().with_name(name.into()))
+            .collect(),
+    )?;
+    Ok((df, renamed))
+}
+
+pub fn drop_columns(df: &DataFrame, dropped: &[String]) -> AppResult<DataFrame> {
+    let kept = df
+        .get_column_names()
+        .into_iter()
+        .filter(|name| !dropped.iter().any(|d| d == name.as_str()))
+        .map(|name| name.as_str().to_owned())
+        .collect_vec();
+    if kept.is_empty() {
+        Err(anyhow!("Cannot drop every column"))
+    } else {
+        Ok(df.select(kept)?)
+    }
+}
+
+/// Fills the nulls of a column, returning the new frame and the number of
+/// filled values.
+pub fn fill_null(df: &DataFrame, name: &str, fill: &FillNull) -> AppResult<(DataFrame, usize)> {
+    let series = df.column(name)?.as_materialized_series();
+    let filled = match fill {
+        FillNull::Value(value) => {
+            let value = Series::new(name.into(), [value.as_str()])
+                .strict_cast(series.dtype())?
+                .new_from_index(0, series.len());
+            series.zip_with(&series.is_not_null(), &value)?
+        }
+        FillNull::Forward => series.fill_null(FillNullStrategy::Forward(None))?,
+        FillNull::Backward => series.fill_null(FillNullStrategy::Backward(None))?,
+        FillNull::Mean => series.fill_null(FillNullStrategy::Mean)?,
+        FillNull::Zero => series.fill_null(FillNullStrategy::Zero)?,
+    };
+    let count = series.null_count() - filled.null_count();
+    let mut df = df.clone();
+    df.replace(name, Column::from(filled))?;
+    Ok((df, count))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FillNull {
+    Value(String),
+    Forward,
+    Backward,
+    Mean,
+    Zero,
+}
+
+impl From<&str> for FillNull {
+    fn from(value: &str) -> Self {
+        match value {
+            "forward" => FillNull::Forward,
+            "backward" => FillNull::Backward,
+            "mean" => FillNull::Mean,
+            "zero" => FillNull::Zero,
+            _ => FillNull::Value(value.to_owned()),
+        }
+    }
+}
+
+impl Display for FillNull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FillNull::Value(value) => write!(f, "'{value}'"),
+            FillNull::Forward => write!(f, "forward"),
+            FillNull::Backward => write!(f, "backward"),
+            FillNull::Mean => write!(f, "mean"),
+            FillNull::Zero => write!(f, "zero"),
+        }
+    }
+}
+
+/// Removes duplicate rows considering only `subset` (every column when empty),
+/// returning the new frame and the number of dropped rows.
+pub fn dedup(df: &DataFrame, subset: &[String], keep: Keep) -> AppResult<(DataFrame, usize)> {
+    let deduped = df.unique_stable(
+        (!subset.is_empty()).then_some(subset),
+        match keep {
+            Keep::First => UniqueKeepStrategy::First,
+            Keep::Last => UniqueKeepStrategy::Last,
+        },
+        None,
+    )?;
+    let dropped = df.height() - deduped.height();
+    Ok((deduped, dropped))
+}
+
+/// Expands a list column into one row per element, returning the new frame and
+/// the number of rows added.
+pub fn explode(df: &DataFrame, name: &str) -> AppResult<(DataFrame, usize)> {
+    if !matches!(
+        df.column(name)?.dtype(),
+        DataType::List(_) | DataType::Array(_, _)
+    ) {
+        return Err(anyhow!("Column '{name}' is not a list column"));
+    }
+    let exploded = df.explode(
+        [name],
+        ExplodeOptions {
+            empty_as_null: true,
+            keep_nulls: true,
+        },
+    )?;
+    let added = exploded.height().saturating_sub(df.height());
+    Ok((exploded, added))
+}
+
+/// Replaces a struct column with one `{name}_{field}` column per field.
+pub fn unnest(df: &DataFrame, name: &str) -> AppResult<DataFrame> {
+    let column = df.column(name)?;
+    if !matches!(column.dtype(), DataType::Struct(_)) {
+        return Err(anyhow!("Column '{name}' is not a struct column"));
+    }
+    let fields = column
+        .as_materialized_series()
+        .struct_()?
+        .fields_as_series();
+    let mut columns = Vec::with_capacity(df.width() + fields.len());
+    for column in df.columns() {
+        if column.name().as_str() == name {
+            columns.extend(fields.iter().map(|field| {
+                Column::from(
+                    field
+                        .clone()
+                        .with_name(format!("{name}_{}", field.name()).into()),
+                )
+            }));
+        } else {
+            columns.push(column.clone());
+        }
+    }
+    if let Some(dup) = columns.iter().map(Column::name).duplicates().next() {
+        return Err(anyhow!("Unnesting would produce duplicate column '{dup}'"));
+    }
+    Ok(DataFrame::new_infer_height(columns)?)
+}
+
+/// Inserts a `{name}_{value}` indicator column after the column for each of its
+/// distinct values, returning the new frame and the number of added columns.
+pub fn dummies(df: &DataFrame, name: &str) -> AppResult<(DataFrame, usize)> {
+    let values = df.column(name)?.cast(&DataType::String)?;
+    let values = values.str()?.into_iter().collect_vec();
+    let categories = values
+        .iter()
+        .flatten()
+        .copied()
+        .unique()
+        .sorted()
+        .collect_vec();
+    if categories.len() > DUMMIES_MAX_CATEGORIES {
+        return Err(anyhow!(
+            "Column '{name}' has {} distinct values, up to {DUMMIES_MAX_CATEGORIES} are supported",
+            categories.len()
+        ));
+    }
+    let mut columns = Vec::with_capacity(df.width() + categories.len());
+    for column in df.columns() {
+        columns.push(column.clone());
+        if column.name().as_str() == name {
+            columns.extend(categories.iter().map(|category| {
+                Column::new(
+                    format!("{name}_{category}").into(),
+                    values
+                        .iter()
+                        .map(|value| u8::from(value == &Some(*category)))
+                        .collect_vec(),
+                )
+            }));
+        }
+    }
+    if let Some(dup) = columns.iter().map(Column::name).duplicates().next() {
+        return Err(anyhow!("Encoding would produce duplicate column '{dup}'"));
+    }
+    Ok((DataFrame::new_infer_height(columns)?, categories.len()))
+}
+
+/// Drops rows holding a null in any of `subset` (every column when empty),
+/// returning the new frame and the number of dropped rows.
+pub fn drop_nulls(df: &DataFrame, subset: &[String]) -> AppResult<(DataFrame, usize)> {
+    let kept = df.drop_nulls((!subset.is_empty()).then_some(subset))?;
+    let dropped = df.height() - kept.height();
+    Ok((kept, dropped))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Keep {
+    #[default]
+    First,
+    Last,
+}
+
+pub fn sample(df: &DataFrame, size: SampleSize, seed: Option<u64>) -> AppResult<DataFrame> {
+    let n = match size {
+        SampleSize::Rows(n) => n,
+        SampleSize::Percent(pct) => (df.height() as f64 * pct / 100.0).round() as usize,
+    }
+    .min(df.height());
+    Ok(df.sample_n_literal(n, false, true, seed)?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleSize {
+    Rows(usize),
+    Percent(f64),
+}
+
+impl FromStr for SampleSize {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(pct) = s.strip_suffix('%') {
+            match pct.parse::<f64>() {
+                Ok(pct) if (0.0..=100.0).contains(&pct) => Ok(SampleSize::Percent(pct)),
+                _ => Err(anyhow!("Invalid percentage '{s}'")),
+            }
+        } else {
+            s.parse()
+                .map(SampleSize::Rows)
+                .map_err(|_| anyhow!("Invalid sample size '{s}'"))
+        }
+    }
+}
+
+impl Display for SampleSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SampleSize::Rows(n) => write!(f, "{n} rows"),
+            SampleSize::Percent(pct) => write!(f, "{pct}%"),
+        }
+    }
+}
+
+pub fn slice(df: &DataFrame, slice: Slice) -> DataFrame {
+    match slice {
+        Slice::Head(n) => df.head(Some(n)),
+        Slice::Tail(n) => df.tail(Some(n)),
+        Slice::Range(start, len) => df.slice(start as i64, len),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Slice {
+    Head(usize),
+    Tail(usize),
+    Range(usize, usize),
+}
+
+impl Display for Slice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Slice::Head(n) => write!(f, "head {n}"),
+            Slice::Tail(n) => write!(f, "tail {n}"),
+            Slice::Range(start, len) => write!(f, "{len} rows from {start}"),
+        }
+    }
+}
+
+/// Row to move to with the `goto` command.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RowTarget {
+    /// Row number, counted from one.
+    Line(usize),
+    /// Position as a percentage of the rows.
+    Percent(f64),
+    /// Number of rows down (or up when negative) from the selected row.
+    Offset(isize),
+}
+
+impl RowTarget {
+    /// Position of the target in a frame of `height` rows, given the selected
+    /// row; lines are counted by position.
+    pub fn position(self, selected: usize, height: usize) -> usize {
+        let last = height.saturating_sub(1);
+        match self {
+            RowTarget::Line(line) => line.saturating_sub(1),
+            RowTarget::Percent(percent) => (last as f64 * percent / 100.0).round() as usize,
+            RowTarget::Offset(offset) => selected.saturating_add_signed(offset),
+        }
+        .min(last)
+    }
+}
+
+/// Plot drawn by the `plot` command.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Plot {
+    Scatter {
+        x: String,
+        y: String,
+        color: Option<String>,
+    },
+    /// Histogram of a column; the bucket count defaults by its type.
+    Histogram {
+        column: String,
+        buckets: Option<usize>,
+    },
+    /// Value counts of a column.
+    Bar(String),
+}
+
+/// Flips rows and columns, keeping the original column names in a `column`
+/// column and naming the new columns after row numbers.
+pub fn transpose(df: &DataFrame) -> AppResult<DataFrame> {
+    if df.height() > TRANSPOSE_MAX_ROWS {
+        return Err(anyhow!(
+            "Only frames with up to {TRANSPOSE_MAX_ROWS} rows can be transposed"
+        ));
+    }
+    let mut df = if df.dtypes().iter().all_equal() {
+        df.clone()
+    } else {
+        DataFrame::new_infer_height(
+            df.columns()
+                .iter()
+                .map(|col| col.cast(&DataType::String))
+                .collect::<Result<Vec<_>, _>>()?,
+        )?
+    };
+    let transposed = df.transpose(Some("column"), None)?;
+    Ok(DataFrame::new_infer_height(
+        transposed
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(idx, col)| {
+                if idx == 0 {
+                    col.clone()
+                } else {
+                    col.clone().with_name(idx.to_string().into())
+                }
+            })
+            .collect(),
+    )?)
+}
+
+/// Splits a string column into `{name}_1`, `{name}_2`, ... columns placed
+/// where the original was, returning the new frame and the number of columns.
+pub fn split_column(
+    df: &DataFrame,
+    name: &str,
+    delimiter: &TextPattern,
+) -> AppResult<(DataFrame, usize)> {
+    let re = delimiter.to_regex()?;
+    let parts = df
+        .column(name)?
+        .str()?
+        .into_iter()
+        .map(|value| value.map(|value| re.split(value).map(str::to_owned).collect_vec()))
+        .collect_vec();
+    let count = parts.iter().flatten().map(Vec::len).max().unwrap_or(0);
+    let mut columns = Vec::with_capacity(df.width() + count);
+    for column in df.columns() {
+        if column.name().as_str() == name {
+            columns.extend((0..count).map(|idx| {
+                Column::new(
+                    format!("{name}_{}", idx + 1).into(),
+                    parts
+                        .iter()
+                        .map(|parts| parts.as_ref().and_then(|parts| parts.get(idx).cloned()))
+                        .collect_vec(),
+                )
+            }));
+        } else {
+            columns.push(column.clone());
+        }
+    }
+    if let Some(dup) = columns.iter().map(Column::name).duplicates().next() {
+        return Err(anyhow!("Splitting would produce duplicate column '{dup}'"));
+    }
+    Ok((DataFrame::new_infer_height(columns)?, count))
+}
+
+/// Replaces every match of `pattern` in a string column, returning the new
+/// frame and the number of affected cells.
+pub fn replace(
+    df: &DataFrame,
+    name: &str,
+    pattern: &TextPattern,
+    replacement: &str,
+) -> AppResult<(DataFrame, usize)> {
+    let re = pattern.to_regex()?;
+    let mut count = 0;
+    let mut values = Vec::with_capacity(df.height());
+    for value in df.column(name)?.str()?.into_iter() {
+        values.push(value.map(|value| {
+            if re.is_match(value) {
+                count += 1;
+                match pattern {
+                    TextPattern::Literal(_) => re.replace_all(value, NoExpand(replacement)),
+                    TextPattern::Regex(_) => re.replace_all(value, replacement),
+                }
+                .into_owned()
+            } else {
+                value.to_owned()
+            }
+        }));
+    }
+    let mut df = df.clone();
+    df.replace(name, Column::new(name.into(), values))?;
+    Ok((df, count))
+}
+
+/// Cells of the string columns selected by `columns` (every string column when
+/// empty) that match `pattern`, as `(row, column index)` pairs in reading order.
+pub fn matching_cells(
+    df: &DataFrame,
+    columns: &[ColumnPattern],
+    pattern: &Regex,
+) -> AppResult<Vec<(usize, usize)>> {
+    let names = if columns.is_empty() {
+        df.get_column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect_vec()
+    } else {
+        resolve_columns(df, columns)?
+    };
+    let mut cells = Vec::new();
+    for name in names {
+        let Some(idx) = df.get_column_index(&name) else {
+            continue;
+        };
+        if let Ok(values) = df.column(&name)?.str() {
+            cells.extend(
+                values
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, value)| value.is_some_and(|value| pattern.is_match(value)))
+                    .map(|(row, _)| (row, idx)),
+            );
+        }
+    }
+    cells.sort_unstable();
+    Ok(cells)
+}
+
+/// Replaces the matches of `pattern` in the given rows of a string column,
+/// expanding `$1`-style groups in `replacement`.
+pub fn replace_cells(
+    df: &DataFrame,
+    name: &str,
+    rows: &[usize],
+    pattern: &Regex,
+    replacement: &str,
+) -> AppResult<DataFrame> {
+    let rows = rows.iter().copied().sorted_unstable().collect_vec();
+    let values = df
+        .column(name)?
+        .str()?
+        .into_iter()
+        .enumerate()
+        .map(|(row, value)| {
+            value.map(|value| {
+                if rows.binary_search(&row).is_ok() {
+                    pattern.replace_all(value, replacement).into_owned()
+                } else {
+                    value.to_owned()
+                }
+            })
+        })
+        .collect_vec();
+    let mut df = df.clone();
+    df.replace(name, Column::new(name.into(), values))?;
+    Ok(df)
+}
+
+/// Sets the cell of the column `name` at `row` to `text` parsed as the column
+/// type. Empty text sets a null, except in string columns.
+pub fn set_cell(df: &DataFrame, name: &str, row: usize, text: &str) -> AppResult<DataFrame> {
+    if row >= df.height() {
+        return Err(anyhow!("Row {row} is out of range"));
+    }
+    let series = df.column(name)?.as_materialized_series();
+    let dtype = series.dtype();
+    let invalid = || anyhow!("'{text}' is not a valid {dtype}");
+    let value = if text.is_empty() && dtype != &DataType::String {
+        Series::full_null(name.into(), 1, dtype)
+    } else if dtype == &DataType::Boolean {
+        Series::new(name.into(), [text.parse::<bool>().map_err(|_| invalid())?])
+    } else {
+        Series::new(name.into(), [text])
+            .strict_cast(dtype)
+            .map_err(|_| invalid())?
+    };
+    let mut edited = series.slice(0, row);
+    edited.append(&value)?;
+    edited.append(&series.slice(row as i64 + 1, series.len()))?;
+    let mut df = df.clone();
+    df.replace(name, edited.into())?;
+    Ok(df)
+}
+
+/// Sets several cells of the record at `row`, given as column names with
+/// their new text, leaving the frame untouched when any value is invalid.
+pub fn set_record(df: &DataFrame, row: usize, values: &[(String, String)]) -> AppResult<DataFrame> {
+    values.iter().try_fold(df.clone(), |df, (name, text)| {
+        set_cell(&df, name, row, text)
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextPattern {
+    Literal(String),
+    Regex(String),
+}
+
+impl TextPattern {
+    fn to_regex(&self) -> AppResult<Regex> {
+        match self {
+            TextPattern::Literal(lit) if lit.is_empty() => Err(anyhow!("Pattern cannot be empty")),
+            TextPattern::Literal(lit) => Ok(Regex::new(&regex::escape(lit))?),
+            TextPattern::Regex(pattern) => Ok(Regex::new(pattern)?),
+        }
+    }
+}
+
+impl From<&str> for TextPattern {
+    fn from(value: &str) -> Self {
+        match value
+            .strip_prefix('/')
+            .and_then(|value| value.strip_suffix('/'))
+            .filter(|pattern| !pattern.is_empty())
+        {
+            Some(pattern) => TextPattern::Regex(pattern.to_owned()),
+            None => TextPattern::Literal(value.to_owned()),
+        }
+    }
+}
+
+impl Display for TextPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextPattern::Literal(lit) => write!(f, "'{lit}'"),
+            TextPattern::Regex(pattern) => write!(f, "/{pattern}/"),
+        }
+    }
+}
+
+/// Stacks frames vertically, filling columns missing from a frame with nulls.
+pub fn concat(frames: &[&DataFrame]) -> AppResult<DataFrame> {
+    Ok(concat_lf_diagonal(
+        frames.iter().map(|df| (*df).clone().lazy()).collect_vec(),
+        UnionArgs::default(),
+    )?
+    .collect()?)
+}
+
+/// Joins `right` onto `left` using `(left, right)` key column pairs.
+pub fn join(
+    left: &DataFrame,
+    right: &DataFrame,
+    keys: &[(String, String)],
+    how: JoinHow,
+) -> AppResult<DataFrame> {
+    let (left_on, right_on): (Vec<_>, Vec<_>) = keys
+        .iter()
+        .map(|(l, r)| (col(l.as_str()), col(r.as_str())))
+        .unzip();
+    Ok(left
+        .clone()
+        .lazy()
+        .join(
+            right.clone().lazy(),
+            left_on,
+            right_on,
+            JoinArgs::new(match how {
+                JoinHow::Inner => JoinType::Inner,
+                JoinHow::Left => JoinType::Left,
+                JoinHow::Right => JoinType::Right,
+                JoinHow::Full => JoinType::Full,
+            }),
+        )
+        .collect()?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum JoinHow {
+    #[default]
+    Inner,
+    Left,
+    Right,
+    Full,
+}
+
+impl FromStr for JoinHow {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "inner" => Ok(JoinHow::Inner),
+            "left" => Ok(JoinHow::Left),
+            "right" => Ok(JoinHow::Right),
+            "full" | "outer" => Ok(JoinHow::Full),
+            _ => Err(anyhow!(
+                "Invalid join type '{s}', use inner, left, right, or full"
+            )),
+        }
+    }
+}
+
+impl Display for JoinHow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JoinHow::Inner => write!(f, "inner"),
+            JoinHow::Left => write!(f, "left"),
+            JoinHow::Right => write!(f, "right"),
+            JoinHow::Full => write!(f, "full"),
+        }
+    }
+}
+
+/// Name of the column telling whether a diff row was added, removed, or changed.
+pub const DIFF: &str = "\u{394}";
+const DIFF_LEFT: &str = "__diff_left";
+const DIFF_RIGHT: &str = "__diff_right";
+const DIFF_SUFFIX: &str = "__diff_new";
+
+/// Rows added, removed, or changed from `left` to `right`, matched by the `keys`
+/// columns or, without keys, by all their shared columns. Changed cells read
+/// `old → new`, and columns missing from either frame are left out.
+pub fn diff(left: &DataFrame, right: &DataFrame, keys: &[String]) -> AppResult<DataFrame> {
+    let shared = left
+        .get_column_names()
+        .into_iter()
+        .filter(|name| right.get_column_index(name).is_some())
+        .map(|name| name.to_string())
+        .collect_vec();
+    if let Some(key) = keys.iter().find(|key| !shared.contains(key)) {
+        return Err(anyhow!("Key '{key}' is not a column of both tabs"));
+    }
+    let on = if keys.is_empty() {
+        shared.clone()
+    } else {
+        keys.to_vec()
+    };
+    let values = shared
+        .iter()
+        .filter(|name| !on.contains(name))
+        .collect_vec();
+    let prepare = |df: &DataFrame, marker: &str| {
+        df.clone().lazy().select(
+            shared
+                .iter()
+                .map(|name| col(name.as_str()).cast(DataType::String))
+                .chain([lit(true).alias(marker)])
+                .collect_vec(),
+        )
+    };
+    let on_exprs = on.iter().map(|name| col(name.as_str())).collect_vec();
+    let joined = prepare(left, DIFF_LEFT).join(
+        prepare(right, DIFF_RIGHT),
+        on_exprs.clone(),
+        on_exprs.clone(),
+        JoinArgs::new(JoinType::Full)
+            .with_coalesce(JoinCoalesce::CoalesceColumns)
+            .with_suffix(Some(DIFF_SUFFIX.into())),
+    );
+
+    let added = col(DIFF_LEFT).is_null();
+    let removed = col(DIFF_RIGHT).is_null();
+    let changed = values
+        .iter()
+        .map(|name| col(name.as_str()).neq_missing(col(format!("{name}{DIFF_SUFFIX}"))))
+        .reduce(|changed, expr| changed.or(expr))
+        .unwrap_or(lit(false));
+    let status = when(added.clone())
+        .then(lit("added"))
+        .when(removed.clone())
+        .then(lit("removed"))
+        .when(changed)
+        .then(lit("changed"))
+        .otherwise(lit(NULL).cast(DataType::String))
+        .alias(DIFF);
+    let cells = values.iter().map(|name| {
+        let old = col(name.as_str());
+        let new = col(format!("{name}{DIFF_SUFFIX}"));
+        when(added.clone())
+            .then(new.clone())
+            .when(removed.clone())
+            .then(old.clone())
+            .when(old.clone().neq_missing(new.clone()))
+            .then(
+                old.clone().fill_null(lit("\u{2205}"))
+                    + lit(" \u{2192} ")
+                    + new.fill_null(lit("\u{2205}")),
+            )
+            .otherwise(old)
+            .alias(name.as_str())
+    });
+    Ok(joined
+        .select(
+            std::iter::once(status)
+                .chain(on_exprs)
+                .chain(cells)
+                .collect_vec(),
+        )
+        .filter(col(DIFF).is_not_null())
+        .collect()?)
+}
+
+/// Aggregates the frame grouped by `by`, or the whole frame when `by` is empty.
+pub fn aggregate(df: &DataFrame, by: &[String], aggs: &[Aggregation]) -> AppResult<DataFrame> {
+    let exprs = aggs.iter().map(Aggregation::expr).collect_vec();
+    let lf = df.clone().lazy();
+    Ok(if by.is_empty() {
+        lf.select(exprs)
+    } else {
+        lf.group_by_stable(by.iter().map(|name| col(name.as_str())).collect_vec())
+            .agg(exprs)
+    }
+    .collect()?)
+}
+
+/// Aggregates every numeric column into a single row, e.g. for a table footer.
+pub fn footer(df: &DataFrame, footer: Footer) -> AppResult<DataFrame> {
+    let exprs = df
+        .columns()
+        .iter()
+        .filter(|column| column.dtype().is_numeric())
+        .map(|column| {
+            let expr = col(column.name().as_str());
+            match footer {
+                Footer::Sum => expr.sum(),
+                Footer::Mean => expr.mean(),
+                Footer::Count => expr.count(),
+            }
+        })
+        .collect_vec();
+    Ok(df.clone().lazy().select(exprs).collect()?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Footer {
+    Sum,
+    Mean,
+    Count,
+}
+
+impl Footer {
+    /// The aggregate after this one, or `None` after the last.
+    pub fn next(self) -> Option<Footer> {
+        match self {
+            Footer::Sum => Some(Footer::Mean),
+            Footer::Mean => Some(Footer::Count),
+            Footer::Count => None,
+        }
+    }
+}
+
+impl FromStr for Footer {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(Footer::Sum),
+            "mean" | "avg" => Ok(Footer::Mean),
+            "count" => Ok(Footer::Count),
+            _ => Err(anyhow!("Invalid footer '{s}', use sum, mean, or count")),
+        }
+    }
+}
+
+impl Display for Footer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Footer::Sum => write!(f, "sum"),
+            Footer::Mean => write!(f, "mean"),
+            Footer::Count => write!(f, "count"),
+        }
+    }
+}
+
+/// Counts the occurrences of every value of the column `name`, most frequent
+/// first, with their share of the rows in percent.
+pub fn frequency(df: &DataFrame, name: &str) -> AppResult<DataFrame> {
+    let height = df.height().max(1) as f64;
+    Ok(df
+        .column(name)?
+        .as_materialized_series()
+        .value_counts(true, false, "count".into(), false)?
+        .lazy()
+        .with_column(
+            (col("count").cast(DataType::Float64) * lit(100.0) / lit(height)).alias("percent"),
+        )
+        .collect()?)
+}
+
+/// Pandas-style summary with a row per statistic and a column per numeric
+/// column of the frame.
+pub fn describe(df: &DataFrame) -> AppResult<DataFrame> {
+    let names = df
+        .columns()
+        .iter()
+        .filter(|column| column.dtype().is_numeric())
+        .map(|column| column.name().to_string())
+        .collect_vec();
+    if names.is_empty() {
+        return Err(anyhow!("No numeric column to describe"));
+    }
+    let exprs = names
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, name)| {
+            let expr = col(name.as_str()).cast(DataType::Float64);
+            [
+                expr.clone().count().cast(DataType::Float64),
+                expr.clone().mean(),
+                expr.clone().std(1),
+                expr.clone().min(),
+                expr.clone().quantile(lit(0.25), QuantileMethod::Linear),
+                expr.clone().median(),
+                expr.clone().quantile(lit(0.75), QuantileMethod::Linear),
+                expr.max(),
+            ]
+            .into_iter()
+            .zip(DESCRIBE_STATS)
+            .map(move |(expr, stat)| expr.alias(format!("{idx} {stat}")))
+        })
+        .collect_vec();
+    let stats = df.clone().lazy().select(exprs).collect()?;
+    let mut columns = vec![Column::new("statistic".into(), DESCRIBE_STATS)];
+    columns.extend(
+        names
+            .iter()
+            .zip(stats.columns().chunks(DESCRIBE_STATS.len()))
+            .map(|(name, stats)| {
+                Column::new(
+                    name.into(),
+                    stats
+                        .iter()
+                        .map(|stat| stat.get(0).ok().and_then(|value| value.extract::<f64>()))
+                        .collect_vec(),
+                )
+            }),
+    );
+    Ok(DataFrame::new_infer_height(columns)?)
+}
+
+/// Pearson correlation of every pair of numeric columns.
+#[derive(Debug)]
+pub struct Correlation {
+    pub names: Vec<String>,
+    /// Correlation of the columns `i` and `j` at `[i][j]`, `None` when it is
+    /// undefined (a constant column, or fewer than two shared values).
+    pub matrix: Vec<Vec<Option<f64>>>,
+}
+
+/// Correlation matrix of the numeric columns, each pair over the rows where
+/// both values are set.
+pub fn correlation(df: &DataFrame) -> AppResult<Correlation> {
+    let columns = df
+        .columns()
+        .iter()
+        .filter(|column| column.dtype().is_numeric())
+        .map(|column| {
+            Ok((
+                column.name().to_string(),
+                column.cast(&DataType::Float64)?.f64()?.iter().collect_vec(),
+            ))
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+    if columns.is_empty() {
+        return Err(anyhow!("No numeric column to correlate"));
+    }
+    let matrix = columns
+        .iter()
+        .map(|(_, a)| columns.iter().map(|(_, b)| pearson(a, b)).collect())
+        .collect();
+    Ok(Correlation {
+        names: columns.into_iter().map(|(name, _)| name).collect(),
+        matrix,
+    })
+}
+
+fn pearson(a: &[Option<f64>], b: &[Option<f64>]) -> Option<f64> {
+    let pairs = a
+        .iter()
+        .zip(b)
+        .filter_map(|(a, b)| Some(((*a)?, (*b)?)))
+        .filter(|(a, b)| a.is_finite() && b.is_finite())
+        .collect_vec();
+    if pairs.len() < 2 {
+        return None;
+    }
+    let len = pairs.len() as f64;
+    let mean_a = pairs.iter().map(|(a, _)| a).sum::<f64>() / len;
+    let mean_b = pairs.iter().map(|(_, b)| b).sum::<f64>() / len;
+    let (cov, var_a, var_b) = pairs
+        .iter()
+        .fold((0.0, 0.0, 0.0), |(cov, var_a, var_b), (a, b)| {
+            let (da, db) = (a - mean_a, b - mean_b);
+            (cov + da * db, var_a + da * da, var_b + db * db)
+        });
+    (var_a > 0.0 && var_b > 0.0).then(|| (cov / (var_a * var_b).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// Summary statistics of a single column.
+#[derive(Debug)]
+pub struct ColumnStats {
+    /// Statistic names with their values; the moments and quartiles only for
+    /// numeric columns.
+    pub stats: Vec<(String, String)>,
+    /// Most frequent values with their counts, most frequent first.
+    pub top: Vec<(String, usize)>,
+}
+
+/// Summary statistics and most frequent values of the column `name`.
+pub fn column_stats(df: &DataFrame, name: &str) -> AppResult<ColumnStats> {
+    let column = df.column(name)?;
+    let dtype = column.dtype();
+    let mut exprs = vec![
+        col(name).count().alias("count"),
+        col(name).null_count().alias("nulls"),
+        col(name).n_unique().alias("unique"),
+    ];
+    if dtype.is_numeric()
+        || dtype.is_temporal()
+        || matches!(dtype, DataType::String | DataType::Boolean)
+    {
+        exprs.extend([col(name).min().alias("min"), col(name).max().alias("max")]);
+    }
+    if dtype.is_numeric() {
+        exprs.extend([
+            col(name).mean().alias("mean"),
+            col(name).std(1).alias("std"),
+            col(name)
+                .quantile(lit(0.25), QuantileMethod::Linear)
+                .alias("25%"),
+            col(name).median().alias("50%"),
+            col(name)
+                .quantile(lit(0.75), QuantileMethod::Linear)
+                .alias("75%"),
+        ]);
+    }
+    let stats = df.clone().lazy().select(exprs).collect()?;
+    let stats = stats
+        .columns()
+        .iter()
+        .map(|column| {
+            (
+                column.name().to_string(),
+                column
+                    .get(0)
+                    .map(AnyValue::into_single_line)
+                    .unwrap_or_default(),
+            )
+        })
+        .collect();
+
+    let counts = column
+        .as_materialized_series()
+        .value_counts(true, false, "count".into(), false)?
+        .head(Some(STATS_TOP_VALUES));
+    let top = counts[0]
+        .as_materialized_series()
+        .iter()
+        .map(AnyValue::into_single_line)
+        .zip(counts[1].cast(&DataType::UInt64)?.u64()?.iter())
+        .map(|(value, count)| (value, count.unwrap_or_default() as usize))
+        .collect();
+    Ok(ColumnStats { stats, top })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregation {
+    func: AggFunc,
+    column: Option<String>,
+}
+
+impl Aggregation {
+    fn expr(&self) -> Expr {
+        let Some(column) = self.column.as_deref() else {
+            return len().alias("count");
+        };
+        let expr = col(column);
+        match self.func {
+            AggFunc::Count => expr.count(),
+            AggFunc::Sum => expr.sum(),
+            AggFunc::Mean => expr.mean(),
+            AggFunc::Median => expr.median(),
+            AggFunc::Min => expr.min(),
+            AggFunc::Max => expr.max(),
+            AggFunc::Std => expr.std(1),
+            AggFunc::First => expr.first(),
+            AggFunc::Last => expr.last(),
+            AggFunc::NUnique => expr.n_unique(),
+        }
+        .alias(self.to_string())
+    }
+}
+
+impl FromStr for Aggregation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (func, column) = s
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .ok_or(anyhow!("Invalid aggregation '{s}', expected func(column)"))?;
+        let func = match func.to_lowercase().as_str() {
+            "count" => AggFunc::Count,
+            "sum" => AggFunc::Sum,
+            "mean" | "avg" => AggFunc::Mean,
+            "median" => AggFunc::Median,
+            "min" => AggFunc::Min,
+            "max" => AggFunc::Max,
+            "std" => AggFunc::Std,
+            "first" => AggFunc::First,
+            "last" => AggFunc::Last,
+            "n_unique" => AggFunc::NUnique,
+            _ => return Err(anyhow!("Unknown aggregation function '{func}'")),
+        };
+        let column = Some(column.trim())
+            .filter(|column| !column.is_empty())
+            .map(ToOwned::to_owned);
+        if column.is_none() && func != AggFunc::Count {
+            Err(anyhow!("Aggregation '{s}' requires a column"))
+        } else {
+            Ok(Aggregation { func, column })
+        }
+    }
+}
+
+impl Display for Aggregation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let func = match self.func {
+            AggFunc::Count => "count",
+            AggFunc::Sum => "sum",
+            AggFunc::Mean => "mean",
+            AggFunc::Median => "median",
+            AggFunc::Min => "min",
+            AggFunc::Max => "max",
+            AggFunc::Std => "std",
+            AggFunc::First => "first",
+            AggFunc::Last => "last",
+            AggFunc::NUnique => "n_unique",
+        };
+        write!(f, "{func}({})", self.column.as_deref().unwrap_or_default())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AggFunc {
+    Count,
+    Sum,
+    Mean,
+    Median,
+    Min,
+    Max,
+    Std,
+    First,
+    Last,
+    NUnique,
+}
+
+/// Column name, glob (`price_*`), or regex (`/^col_\d+$/`) used to pick columns.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnPattern {
+    Name(String),
+    Glob(String),
+    Regex(String),
+}
+
+impl ColumnPattern {
+    pub fn to_regex(&self) -> AppResult<Option<Regex>> {
+        Ok(match self {
+            ColumnPattern::Name(_) => None,
+            ColumnPattern::Glob(glob) => Some(Regex::new(&format!(
+                "^{}$",
+                regex::escape(glob).replace(r"\*", ".*").replace(r"\?", ".")
+            ))?),
+            ColumnPattern::Regex(pattern) => Some(Regex::new(pattern)?),
+        })
+    }
+}
+
+impl From<&str> for ColumnPattern {
+    fn from(value: &str) -> Self {
+        if let Some(pattern) = value
+            .strip_prefix('/')
+            .and_then(|v| v.strip_suffix('/'))
+            .filter(|v| !v.is_empty())
+        {
+            ColumnPattern::Regex(pattern.to_owned())
+        } else if value.contains(['*', '?']) {
+            ColumnPattern::Glob(value.to_owned())
+        } else {
+            ColumnPattern::Name(value.to_owned())
+        }
+    }
+}
+
+/// Resolves patterns to column names in frame order, failing on unknown names
+/// or when nothing matches.
+pub fn resolve_columns(df: &DataFrame, patterns: &[ColumnPattern]) -> AppResult<Vec<String>> {
+    let names = df.get_column_names();
+    let mut selected = vec![false; names.len()];
+    for pattern in patterns {
+        if let ColumnPattern::Name(name) = pattern {
+            let idx = names
+                .iter()
+                .position(|n| n.as_str() == name)
+                .ok_or(anyhow!("Column '{name}' not found"))?;
+            selected[idx] = true;
+        } else if let Some(re) = pattern.to_regex()? {
+            for (idx, name) in names.iter().enumerate() {
+                selected[idx] |= re.is_match(name);
+            }
+        }
+    }
+    let resolved = names
+        .into_iter()
+        .zip(selected)
+        .filter(|(_, sel)| *sel)
+        .map(|(name, _)| name.to_string())
+        .collect_vec();
+    if resolved.is_empty() {
+        Err(anyhow!("No column matches the given pattern(s)"))
+    } else {
+        Ok(resolved)
+    }
+}
+
+fn escape_ident(name: &str) -> String {
+    name.replace('"', "\"\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::{df, prelude::as_struct};
+
+    use super::*;
+
+    fn ints(df: &DataFrame, name: &str) -> Vec<Option<i64>> {
+        df.column(name)
+            .unwrap()
+            .cast(&DataType::Int64)
+            .unwrap()
+            .i64()
+            .unwrap()
+            .iter()
+            .collect()
+    }
+
+    fn floats(df: &DataFrame, name: &str) -> Vec<Option<f64>> {
+        df.column(name)
+            .unwrap()
+            .cast(&DataType::Float64)
+            .unwrap()
+            .f64()
+            .unwrap()
+            .iter()
+            .collect()
+    }
+
+    fn texts(df: &DataFrame, name: &str) -> Vec<Option<String>> {
+        df.column(name)
+            .unwrap()
+            .cast(&DataType::String)
+            .unwrap()
+            .str()
+            .unwrap()
+            .iter()
+            .map(|value| value.map(str::to_owned))
+            .collect()
+    }
+
+    fn text(value: &str) -> Option<String> {
+        Some(value.to_owned())
+    }
+
+    fn names(df: &DataFrame) -> Vec<String> {
+        df.get_column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_row_numbers() {
+        let df = df!("n" => [3i64, 1, 2]).unwrap();
+        let numbered = number_rows(&df, None).unwrap();
+        let mut sorted = sort(&numbered, "n", false).unwrap();
+        assert_eq!(
+            take_row_numbers(&mut sorted).as_deref(),
+            Some(&[2, 3, 1][..])
+        );
+        assert_eq!(names(&sorted), ["n"]);
+
+        let numbered = number_rows(&sorted, Some(&[2, 3, 1])).unwrap();
+        let mut filtered = sql()
+            .execute("SELECT * FROM _ WHERE n > 1", numbered.clone())
+            .unwrap();
+        assert_eq!(
+            take_row_numbers(&mut filtered).as_deref(),
+            Some(&[3, 1][..])
+        );
+
+        let mut selected = numbered.select(["n"]).unwrap();
+        assert_eq!(take_row_numbers(&mut selected), None);
+    }
+
+    #[test]
+    fn test_mutate() {
+        let df = df!("a" => [1i64, 2, 3], "b" => [10i64, 20, 30]).unwrap();
+        let mutated = mutate(&df, "total", "a + b").unwrap();
+        assert_eq!(ints(&mutated, "total"), [Some(11), Some(22), Some(33)]);
+
+        let mutated = mutate(&df, "a", "a * 2").unwrap();
+        assert_eq!(names(&mutated), ["a", "b"]);
+        assert_eq!(ints(&mutated, "a"), [Some(2), Some(4), Some(6)]);
+
+        assert!(mutate(&df, "c", "missing + 1").is_err());
+    }
+
+    #[test]
+    fn test_drop_columns() {
+        let df = df!(
+            "id" => [1i64],
+            "price_a" => [2i64],
+            "price_b" => [3i64],
+            "name" => ["x"],
+        )
+        .unwrap();
+        let dropped = drop_columns(&df, &["name".to_owned()]).unwrap();
+        assert_eq!(names(&dropped), ["id", "price_a", "price_b"]);
+
+        let matched = resolve_columns(&df, &[ColumnPattern::from("price_*")]).unwrap();
+        assert_eq!(matched, ["price_a", "price_b"]);
+        assert_eq!(names(&drop_columns(&df, &matched).unwrap()), ["id", "name"]);
+        let matched = resolve_columns(&df, &[ColumnPattern::from("/^(id|name)$/")]).unwrap();
+        assert_eq!(matched, ["id", "name"]);
+
+        assert!(resolve_columns(&df, &[ColumnPattern::from("missing")]).is_err());
+        assert!(resolve_columns(&df, &[ColumnPattern::from("missing_*")]).is_err());
+        let all = resolve_columns(&df, &[ColumnPattern::from("*")]).unwrap();
+        assert!(drop_columns(&df, &all).is_err());
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        let df = df!("d" => [Some("31/01/2024"), None, Some("01/02/2024")]).unwrap();
+        let parsed = parse_dates(&df, "d", "%d/%m/%Y").unwrap();
+        assert_eq!(parsed.column("d").unwrap().dtype(), &DataType::Date);
+        assert_eq!(
+            texts(&parsed, "d"),
+            [text("2024-01-31"), None, text("2024-02-01")]
+        );
+
+        let df = df!("t" => ["31/01/2024 10:30"]).unwrap();
+        let parsed = parse_dates(&df, "t", "%d/%m/%Y %H:%M").unwrap();
+        assert!(matches!(
+            parsed.column("t").unwrap().dtype(),
+            DataType::Datetime(_, None)
+        ));
+
+        assert!(parse_dates(&df, "t", "%Y-%m-%d").is_err());
+    }
+
+    #[test]
+    fn test_convert_time_zone() {
+        let df = df!("t" => ["2024-01-01 12:00", "2024-07-01 12:00"]).unwrap();
+        let naive = parse_dates(&df, "t", "%Y-%m-%d %H:%M").unwrap();
+        let hours = |df: &DataFrame| {
+            let hours = df
+                .clone()
+                .lazy()
+                .select([col("t").dt().hour()])
+                .collect()
+                .unwrap();
+            ints(&hours, "t")
+        };
+
+        let zoned = convert_time_zone(&naive, "t", "UTC", "Europe/Berlin").unwrap();
+        assert!(matches!(
+            zoned.column("t").unwrap().dtype(),
+            DataType::Datetime(_, Some(tz)) if *tz == time_zone("Europe/Berlin").unwrap()
+        ));
+        assert_eq!(hours(&zoned), [Some(13), Some(14)]);
+
+        let rezoned = convert_time_zone(&zoned, "t", "Europe/Berlin", "Asia/Tokyo").unwrap();
+        assert_eq!(hours(&rezoned), [Some(21), Some(21)]);
+
+        assert!(convert_time_zone(&naive, "t", "UTC", "Mars/Olympus").is_err());
+        assert!(convert_time_zone(&df, "t", "UTC", "Europe/Berlin").is_err());
+    }
+
+    #[test]
+    fn test_fill_null() {
+        let df = df!("x" => [Some(1i64), None, Some(3), None]).unwrap();
+        let (filled, count) = fill_null(&df, "x", &FillNull::from("7")).unwrap();
+        assert_eq!(ints(&filled, "x"), [Some(1), Some(7), Some(3), Some(7)]);
+        assert_eq!(count, 2);
+
+        let (filled, _) = fill_null(&df, "x", &FillNull::Forward).unwrap();
+        assert_eq!(ints(&filled, "x"), [Some(1), Some(1), Some(3), Some(3)]);
+
+        let (filled, count) = fill_null(&df, "x", &FillNull::Backward).unwrap();
+        assert_eq!(ints(&filled, "x"), [Some(1), Some(3), Some(3), None]);
+        assert_eq!(count, 1);
+
+        let (filled, _) = fill_null(&df, "x", &FillNull::Zero).unwrap();
+        assert_eq!(ints(&filled, "x"), [Some(1), Some(0), Some(3), Some(0)]);
+
+        assert!(fill_null(&df, "x", &FillNull::from("abc")).is_err());
+    }
+
+    #[test]
+    fn test_dedup() {
+        let df = df!("k" => ["a", "b", "a"], "v" => [1i64, 2, 3]).unwrap();
+        let subset = ["k".to_owned()];
+        let (deduped, dropped) = dedup(&df, &subset, Keep::First).unwrap();
+        assert_eq!(ints(&deduped, "v"), [Some(1), Some(2)]);
+        assert_eq!(dropped, 1);
+
+        let (deduped, dropped) = dedup(&df, &subset, Keep::Last).unwrap();
+        assert_eq!(ints(&deduped, "v"), [Some(2), Some(3)]);
+        assert_eq!(dropped, 1);
+
+        let (deduped, dropped) = dedup(&df, &[], Keep::First).unwrap();
+        assert_eq!(deduped.height(), 3);
+        assert_eq!(dropped, 0);
+    }
+
+    #[test]
+    fn test_drop_nulls() {
+        let df = df!(
+            "a" => [Some(1i64), None, Some(3)],
+            "b" => [Some("x"), Some("y"), None],
+        )
+        .unwrap();
+        let (kept, dropped) = drop_nulls(&df, &[]).unwrap();
+        assert_eq!(ints(&kept, "a"), [Some(1)]);
+        assert_eq!(dropped, 2);
+
+        let (kept, dropped) = drop_nulls(&df, &["a".to_owned()]).unwrap();
+        assert_eq!(ints(&kept, "a"), [Some(1), Some(3)]);
+        assert_eq!(texts(&kept, "b"), [text("x"), None]);
+        assert_eq!(dropped, 1);
+
+        assert!(drop_nulls(&df, &["missing".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn test_explode() {
+        let df = df!(
+            "id" => [1i64, 2],
+            "xs" => [
+                Series::new("".into(), [1i64, 2, 3]),
+                Series::new("".into(), [4i64]),
+            ],
+        )
+        .unwrap();
+        let (exploded, added) = explode(&df, "xs").unwrap();
+        assert_eq!(ints(&exploded, "id"), [Some(1), Some(1), Some(1), Some(2)]);
+        assert_eq!(ints(&exploded, "xs"), [Some(1), Some(2), Some(3), Some(4)]);
+        assert_eq!(added, 2);
+
+        assert!(explode(&df, "id").is_err());
+    }
+
+    #[test]
+    fn test_unnest() {
+        let df = df!("id" => [1i64, 2], "a" => [3i64, 4], "b" => ["x", "y"])
+            .unwrap()
+            .lazy()
+            .select([col("id"), as_struct(vec![col("a"), col("b")]).alias("s")])
+            .collect()
+            .unwrap();
+        let unnested = unnest(&df, "s").unwrap();
+        assert_eq!(names(&unnested), ["id", "s_a", "s_b"]);
+        assert_eq!(ints(&unnested, "s_a"), [Some(3), Some(4)]);
+        assert_eq!(texts(&unnested, "s_b"), [text("x"), text("y")]);
+
+        assert!(unnest(&df, "id").is_err());
+        let clashing = unnested.hstack(&[df.column("s").unwrap().clone()]).unwrap();
+        assert!(unnest(&clashing, "s").is_err());
+    }
+
+    #[test]
+    fn test_dummies() {
+        let df =
+            df!("c" => [Some("x"), Some("y"), None, Some("x")], "n" => [1i64, 2, 3, 4]).unwrap();
+        let (encoded, added) = dummies(&df, "c").unwrap();
+        assert_eq!(added, 2);
+        assert_eq!(names(&encoded), ["c", "c_x", "c_y", "n"]);
+        assert_eq!(ints(&encoded, "c_x"), [Some(1), Some(0), Some(0), Some(1)]);
+        assert_eq!(ints(&encoded, "c_y"), [Some(0), Some(1), Some(0), Some(0)]);
+
+        let clashing = df!("c" => ["x"], "c_x" => [1i64]).unwrap();
+        assert!(dummies(&clashing, "c").is_err());
+    }
+
+    #[test]
+    fn test_sample() {
+        let df = df!("n" => (0..10).collect::<Vec<i64>>()).unwrap();
+        assert_eq!(sample(&df, SampleSize::Rows(3), None).unwrap().height(), 3);
+        assert_eq!(
+            sample(&df, SampleSize::Rows(20), None).unwrap().height(),
+            10
+        );
+        assert_eq!(
+            sample(&df, SampleSize::Percent(50.0), None)
+                .unwrap()
+                .height(),
+            5
+        );
+
+        let first = sample(&df, SampleSize::Rows(4), Some(7)).unwrap();
+        let second = sample(&df, SampleSize::Rows(4), Some(7)).unwrap();
+        assert!(first.equals(&second));
+        assert_eq!(first.column("n").unwrap().n_unique().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_slice() {
+        let df = df!("n" => (0..10).collect::<Vec<i64>>()).unwrap();
+        assert_eq!(ints(&slice(&df, Slice::Head(2)), "n"), [Some(0), Some(1)]);
+        assert_eq!(ints(&slice(&df, Slice::Tail(2)), "n"), [Some(8), Some(9)]);
+        assert_eq!(
+            ints(&slice(&df, Slice::Range(3, 2)), "n"),
+            [Some(3), Some(4)]
+        );
+        assert_eq!(slice(&df, Slice::Range(8, 5)).height(), 2);
+        assert_eq!(slice(&df, Slice::Head(20)).height(), 10);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let df = df!("name" => ["a", "b"], "v" => [1i64, 2]).unwrap();
+        let transposed = transpose(&df).unwrap();
+        assert_eq!(names(&transposed), ["column", "1", "2"]);
+        assert_eq!(texts(&transposed, "column"), [text("name"), text("v")]);
+        assert_eq!(texts(&transposed, "1"), [text("a"), text("1")]);
+        assert_eq!(texts(&transposed, "2"), [text("b"), text("2")]);
+
+        let tall = df!("n" => (0..=TRANSPOSE_MAX_ROWS as i64).collect::<Vec<_>>()).unwrap();
+        assert!(transpose(&tall).is_err());
+    }
+
+    #[test]
+    fn test_split_column() {
+        let df = df!("id" => [1i64, 2, 3], "s" => [Some("a-b"), Some("c"), None]).unwrap();
+        let (split, count) = split_column(&df, "s", &TextPattern::from("-")).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(names(&split), ["id", "s_1", "s_2"]);
+        assert_eq!(texts(&split, "s_1"), [text("a"), text("c"), None]);
+        assert_eq!(texts(&split, "s_2"), [text("b"), None, None]);
+
+        let df = df!("s" => ["a_b-c"]).unwrap();
+        let (split, count) = split_column(&df, "s", &TextPattern::from("/[-_]/")).unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(texts(&split, "s_3"), [text("c")]);
+
+        assert!(split_column(&df, "s", &TextPattern::from("")).is_err());
+    }
+
+    #[test]
+    fn test_replace() {
+        let df = df!("s" => [Some("foo bar"), Some("baz"), None]).unwrap();
+        let (replaced, count) = replace(&df, "s", &TextPattern::from("o"), "$").unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(texts(&replaced, "s"), [text("f$$ bar"), text("baz"), None]);
+
+        let pattern = TextPattern::from(r"/(\w+) (\w+)/");
+        let (replaced, count) = replace(&df, "s", &pattern, "$2 $1").unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(texts(&replaced, "s"), [text("bar foo"), text("baz"), None]);
+    }
+
+    #[test]
+    fn test_replace_cells() {
+        let df = df!("s" => ["aa", "ab", "ba"]).unwrap();
+        let pattern = Regex::new("a").unwrap();
+        let replaced = replace_cells(&df, "s", &[2, 1], &pattern, "A").unwrap();
+        assert_eq!(texts(&replaced, "s"), [text("aa"), text("Ab"), text("bA")]);
+    }
+
+    #[test]
+    fn test_set_cell() {
+        let df = df!(
+            "n" => [1i64, 2, 3],
+            "f" => [0.5f64, 1.5, 2.5],
+            "b" => [true, false, true],
+            "s" => ["x", "y", "z"],
+        )
+        .unwrap();
+        let edited = set_cell(&df, "n", 1, "42").unwrap();
+        assert_eq!(edited.column("n").unwrap().dtype(), &DataType::Int64);
+        assert_eq!(ints(&edited, "n"), [Some(1), Some(42), Some(3)]);
+
+        let edited = set_cell(&df, "f", 2, "2.25").unwrap();
+        assert_eq!(floats(&edited, "f"), [Some(0.5), Some(1.5), Some(2.25)]);
+
+        let edited = set_cell(&df, "n", 0, "").unwrap();
+        assert_eq!(ints(&edited, "n"), [None, Some(2), Some(3)]);
+
+        let edited = set_cell(&df, "s", 0, "").unwrap();
+        assert_eq!(texts(&edited, "s"), [text(""), text("y"), text("z")]);
+
+        let edited = set_cell(&df, "b", 0, "false").unwrap();
+        assert_eq!(edited.column("b").unwrap().dtype(), &DataType::Boolean);
+
+        assert!(set_cell(&df, "n", 0, "abc").is_err());
+        assert!(set_cell(&df, "b", 0, "yes").is_err());
+        assert!(set_cell(&df, "n", 3, "4").is_err());
+    }
+
+    #[test]
+    fn test_set_record() {
+        let df = df!("n" => [1i64, 2], "s" => ["x", "y"]).unwrap();
+        let values = [
+            ("n".to_owned(), "5".to_owned()),
+            ("s".to_owned(), "w".to_owned()),
+        ];
+        let edited = set_record(&df, 1, &values).unwrap();
+        assert_eq!(ints(&edited, "n"), [Some(1), Some(5)]);
+        assert_eq!(texts(&edited, "s"), [text("x"), text("w")]);
+
+        let values = [
+            ("s".to_owned(), "w".to_owned()),
+            ("n".to_owned(), "five".to_owned()),
+        ];
+        assert!(set_record(&df, 1, &values).is_err());
+    }
+
+    #[test]
+    fn test_concat() {
+        let first = df!("x" => [1i64], "y" => ["a"]).unwrap();
+        let second = df!("x" => [2i64], "z" => [true]).unwrap();
+        let stacked = concat(&[&first, &second]).unwrap();
+        assert_eq!(names(&stacked), ["x", "y", "z"]);
+        assert_eq!(ints(&stacked, "x"), [Some(1), Some(2)]);
+        assert_eq!(texts(&stacked, "y"), [text("a"), None]);
+    }
+
+    #[test]
+    fn test_join() {
+        let left = df!("id" => [1i64, 2], "v" => ["a", "b"]).unwrap();
+        let right = df!("id" => [2i64, 3], "v" => ["c", "d"]).unwrap();
+        let keys = [("id".to_owned(), "id".to_owned())];
+
+        let joined = join(&left, &right, &keys, JoinHow::Inner).unwrap();
+        assert_eq!(names(&joined), ["id", "v", "v_right"]);
+        assert_eq!(ints(&joined, "id"), [Some(2)]);
+        assert_eq!(texts(&joined, "v_right"), [text("c")]);
+
+        let joined = join(&left, &right, &keys, JoinHow::Left).unwrap();
+        assert_eq!(texts(&joined, "v"), [text("a"), text("b")]);
+        assert_eq!(texts(&joined, "v_right"), [None, text("c")]);
+
+        let keys = [("v".to_owned(), "missing".to_owned())];
+        assert!(join(&left, &right, &keys, JoinHow::Inner).is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let left = df!("id" => [1i64, 2, 3], "v" => ["a", "b", "c"]).unwrap();
+        let right = df!(
+            "id" => [2i64, 3, 4],
+            "v" => ["b", "x", "d"],
+            "w" => [true, false, true],
+        )
+        .unwrap();
+        let keys = ["id".to_owned()];
+        let changes = sort(&diff(&left, &right, &keys).unwrap(), "id", false).unwrap();
+        assert_eq!(names(&changes), [DIFF, "id", "v"]);
+        assert_eq!(
+            texts(&changes, DIFF),
+            [text("removed"), text("changed"), text("added")]
+        );
+        assert_eq!(texts(&changes, "id"), [text("1"), text("3"), text("4")]);
+        assert_eq!(
+            texts(&changes, "v"),
+            [text("a"), text("c \u{2192} x"), text("d")]
+        );
+
+        assert_eq!(diff(&left, &right, &[]).unwrap().height(), 4);
+        assert!(diff(&left, &right, &["w".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let df = df!("g" => ["a", "b", "a"], "n" => [1i64, 2, 3]).unwrap();
+        let aggs = [
+            "sum(n)".parse::<Aggregation>().unwrap(),
+            "count()".parse::<Aggregation>().unwrap(),
+        ];
+        let grouped = aggregate(&df, &["g".to_owned()], &aggs).unwrap();
+        assert_eq!(names(&grouped), ["g", "sum(n)", "count"]);
+        assert_eq!(texts(&grouped, "g"), [text("a"), text("b")]);
+        assert_eq!(ints(&grouped, "sum(n)"), [Some(4), Some(2)]);
+        assert_eq!(ints(&grouped, "count"), [Some(2), Some(1)]);
+
+        let total = aggregate(&df, &[], &aggs).unwrap();
+        assert_eq!(ints(&total, "sum(n)"), [Some(6)]);
+
+        assert!("sum()".parse::<Aggregation>().is_err());
+        assert!("median".parse::<Aggregation>().is_err());
+        assert!("mode(n)".parse::<Aggregation>().is_err());
+    }
+
+    #[test]
+    fn test_footer() {
+        let df = df!(
+            "s" => ["a", "b", "c"],
+            "n" => [1i64, 2, 3],
+            "f" => [Some(0.5f64), Some(1.5), None],
+        )
+        .unwrap();
+        let sums = footer(&df, Footer::Sum).unwrap();
+        assert_eq!(names(&sums), ["n", "f"]);
+        assert_eq!(ints(&sums, "n"), [Some(6)]);
+        assert_eq!(
+            floats(&footer(&df, Footer::Mean).unwrap(), "f"),
+            [Some(1.0)]
+        );
+        assert_eq!(ints(&footer(&df, Footer::Count).unwrap(), "f"), [Some(2)]);
+    }
+
+    #[test]
+    fn test_frequency() {
+        let df = df!("c" => ["a", "b", "a", "a"]).unwrap();
+        let counts = frequency(&df, "c").unwrap();
+        assert_eq!(names(&counts), ["c", "count", "percent"]);
+        assert_eq!(texts(&counts, "c"), [text("a"), text("b")]);
+        assert_eq!(ints(&counts, "count"), [Some(3), Some(1)]);
+        assert_eq!(floats(&counts, "percent"), [Some(75.0), Some(25.0)]);
+    }
+
+    #[test]
+    fn test_describe() {
+        let df = df!("s" => ["a", "b", "c", "d"], "n" => [1i64, 2, 3, 4]).unwrap();
+        let stats = describe(&df).unwrap();
+        assert_eq!(names(&stats), ["statistic", "n"]);
+        assert_eq!(texts(&stats, "statistic")[1], text("mean"));
+        let n = floats(&stats, "n");
+        assert_eq!(n[0], Some(4.0));
+        assert_eq!(n[1], Some(2.5));
+        assert_eq!(n[3], Some(1.0));
+        assert_eq!(n[4], Some(1.75));
+        assert_eq!(n[5], Some(2.5));
+        assert_eq!(n[7], Some(4.0));
+
+        assert!(describe(&df.select(["s"]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_correlation() {
+        let df = df!(
+            "s" => ["a", "b", "c", "d"],
+            "a" => [Some(1.0f64), Some(2.0), Some(3.0), None],
+            "b" => [2i64, 4, 6, 100],
+            "c" => [3i64, 2, 1, 0],
+            "d" => [5i64, 5, 5, 5],
+        )
+        .unwrap();
+        let corr = correlation(&df).unwrap();
+        assert_eq!(corr.names, ["a", "b", "c", "d"]);
+        let close = |value: Option<f64>, expected: f64| (value.unwrap() - expected).abs() < 1e-9;
+        assert!(close(corr.matrix[0][1], 1.0));
+        assert!(close(corr.matrix[0][2], -1.0));
+        assert!(close(corr.matrix[2][2], 1.0));
+        assert_eq!(corr.matrix[0][3], None);
+        assert_eq!(corr.matrix[3][3], None);
+
+        assert!(correlation(&df.select(["s"]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_cut() {
+        let df = df!("n" => [Some(1.0f64), Some(5.0), None, Some(10.0)]).unwrap();
+        let binned = cut(&df, "n", &Bins::Edges(vec![0.0, 5.0, 10.0])).unwrap();
+        let bins = texts(&binned, "n_bin");
+        assert_eq!(bins[0], bins[1]);
+        assert_ne!(bins[1], bins[3]);
+        assert_eq!(bins[2], None);
+
+        let binned = cut(&df, "n", &Bins::Count(3)).unwrap();
+        let bins = texts(&binned, "n_bin");
+        assert_eq!(bins.iter().flatten().unique().count(), 3);
+        assert_eq!(bins[2], None);
+
+        let nulls = df!("n" => [None::<f64>, None]).unwrap();
+        assert!(cut(&nulls, "n", &Bins::Count(2)).is_err());
+        assert!("0,10,5".parse::<Bins>().is_err());
+        assert!("0".parse::<Bins>().is_err());
+    }
+
+    #[test]
+    fn test_rank() {
+        let df = df!("n" => [Some(3i64), Some(1), None, Some(3)]).unwrap();
+        let ranked = rank(&df, "n", Rank::Average, false).unwrap();
+        assert_eq!(
+            floats(&ranked, "n_rank"),
+            [Some(2.5), Some(1.0), None, Some(2.5)]
+        );
+
+        let ranked = rank(&df, "n", Rank::Dense, true).unwrap();
+        assert_eq!(ints(&ranked, "n_rank"), [Some(1), Some(2), None, Some(1)]);
+
+        let ranked = rank(&df, "n", Rank::Percent, false).unwrap();
+        assert_eq!(
+            floats(&ranked, "n_pct_rank"),
+            [Some(1.0), Some(1.0 / 3.0), None, Some(1.0)]
+        );
+    }
+
+    #[test]
+    fn test_window() {
+        let df = df!("n" => [1i64, 2, 3, 4]).unwrap();
+        let summed = window(&df, "n", WindowFunc::CumSum).unwrap();
+        assert_eq!(
+            ints(&summed, "n_cumsum"),
+            [Some(1), Some(3), Some(6), Some(10)]
+        );
+
+        let func = WindowFunc::Rolling(Rolling::Mean, 2);
+        let rolled = window(&df, "n", func).unwrap();
+        assert_eq!(
+            floats(&rolled, "n_rolling_mean_2")[1..],
+            [Some(1.5), Some(2.5), Some(3.5)]
+        );
+
+        let rolled = window(&df, "n", WindowFunc::CumMax).unwrap();
+        assert_eq!(
+            ints(&rolled, "n_cummax"),
+            [Some(1), Some(2), Some(3), Some(4)]
+        );
+    }
+
+    #[test]
+    fn test_rename_regex() {
+        let df = df!("a_1" => [1i64], "a_2" => [2i64], "b" => [3i64]).unwrap();
+        let (renamed, count) = rename_regex(&df, "^a_", "x_").unwrap();
+        assert_eq!(names(&renamed), ["x_1", "x_2", "b"]);
+        assert_eq!(count, 2);
+
+        let (_, count) = rename_regex(&df, "^z", "y").unwrap();
+        assert_eq!(count, 0);
+
+        assert!(rename_regex(&df, r"_\d$", "").is_err());
+        assert!(rename_regex(&df, "(", "").is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use std::{collections::HashMap, fmt::Write};
+
+use chrono::{DateTime, Local, Utc};
+use polars::prelude::{AnyValue, TimeUnit};
+use serde::{Deserialize, Serialize};
+
+use crate::misc::config::config;
+
+/// Display of the values of a column; unset options keep the default display.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValueFormat {
+    /// Digits after the decimal point of floats.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub float_precision: Option<usize>,
+    /// Separator between the groups of three digits of numbers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thousands_separator: Option<char>,
+    /// strftime format of dates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    /// strftime format of datetimes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datetime_format: Option<String>,
+    /// Text of `true` and of `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boolean: Option<[String; 2]>,
+}
+
+/// The `[format]` section of the config: the format of every column, and the
+/// options overriding it for some columns.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatConfig {
+    #[serde(flatten)]
+    global: ValueFormat,
+    columns: HashMap<String, ValueFormat>,
+}
+
+impl FormatConfig {
+    pub fn column(&self, name: &str) -> ValueFormat {
+        match self.columns.get(name) {
+            Some(format) => format.clone().or(&self.global),
+            None => self.global.clone(),
+        }
+    }
+}
+
+impl ValueFormat {
+    /// Keeps the options set, taking the others from `fallback`.
+    fn or(self, fallback: &ValueFormat) -> ValueFormat {
+        ValueFormat {
+            float_precision: self.float_precision.or(fallback.float_precision),
+            thousands_separator: self.thousands_separator.or(fallback.thousands_separator),
+            date_format: self.date_format.or_else(|| fallback.date_format.clone()),
+            datetime_format: self
+                .datetime_format
+                .or_else(|| fallback.datetime_format.clone()),
+            boolean: self.boolean.or_else(|| fallback.boolean.clone()),
+        }
+    }
+
+    /// Text of the value, or `None` when no option applies to it (or a format
+    /// string is invalid) and the default display is used.
+    pub fn format(&self, value: &AnyValue) -> Option<String> {
+        match value {
+            AnyValue::Boolean(value) => self
+                .boolean
+                .as_ref()
+                .map(|[yes, no]| (if *value { yes } else { no }).clone()),
+            AnyValue::Float32(_) | AnyValue::Float64(_)
+                if self.float_precision.is_some() || self.thousands_separator.is_some() =>
+            {
+                let text = match self.float_precision {
+                    Some(precision) => format!("{:.precision$}", value.extract::<f64>()?),
+                    None => value.to_string(),
+                };
+                Some(match self.thousands_separator {
+                    Some(separator) => group_digits(&text, separator),
+                    None => text,
+                })
+            }
+            value if value.dtype().is_integer() => {
+                Some(group_digits(&value.to_string(), self.thousands_separator?))
+            }
+            AnyValue::Date(days) => {
+                let date = DateTime::from_timestamp(*days as i64 * 86_400, 0)?.date_naive();
+                let mut text = String::new();
+                write!(text, "{}", date.format(self.date_format.as_ref()?)).ok()?;
+                Some(text)
+            }
+            AnyValue::Datetime(value, unit, None) | AnyValue::DatetimeOwned(value, unit, None) => {
+                let format = self.datetime_format.as_ref()?;
+                let datetime = timestamp(*value, *unit)?;
+                let mut text = String::new();
+                if config().show_local_time() {
+                    write!(text, "{}", datetime.with_timezone(&Local).format(format)).ok()?;
+                } else {
+                    write!(text, "{}", datetime.naive_utc().format(format)).ok()?;
+                }
+                Some(text)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn timestamp(value: i64, unit: TimeUnit) -> Option<DateTime<Utc>> {
+    match unit {
+        TimeUnit::Nanoseconds => Some(DateTime::from_timestamp_nanos(value)),
+        TimeUnit::Microseconds => DateTime::from_timestamp_micros(value),
+        TimeUnit::Milliseconds => DateTime::from_timestamp_millis(value),
+    }
+}
+
+/// Inserts `separator` between the groups of three digits of the integer part
+/// of `number`, leaving anything but plain decimals (e.g. `NaN`, `1e21`) as is.
+fn group_digits(number: &str, separator: char) -> String {
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", number),
+    };
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+    if integer.is_empty()
+        || !integer.bytes().all(|byte| byte.is_ascii_digit())
+        || !fraction.is_none_or(|fraction| fraction.bytes().all(|byte| byte.is_ascii_digit()))
+    {
+        return number.to_owned();
+    }
+    let mut grouped = sign.to_owned();
+    for (idx, digit) in integer.chars().enumerate() {
+        if idx > 0 && (integer.len() - idx) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        grouped.push('.');
+        grouped.push_str(fraction);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_digits() {
+        assert_eq!(group_digits("1234567", ','), "1,234,567");
+        assert_eq!(group_digits("-1234.5678", ' '), "-1 234.5678");
+        assert_eq!(group_digits("123", ','), "123");
+        assert_eq!(group_digits("NaN", ','), "NaN");
+        assert_eq!(group_digits("1e21", ','), "1e21");
+    }
+
+    #[test]
+    fn test_formats_numbers() {
+        let format = ValueFormat {
+            float_precision: Some(2),
+            thousands_separator: Some(','),
+            ..Default::default()
+        };
+        assert_eq!(
+            format.format(&AnyValue::Float64(1234.5678)),
+            Some("1,234.57".to_owned())
+        );
+        assert_eq!(
+            format.format(&AnyValue::Int64(-9876543)),
+            Some("-9,876,543".to_owned())
+        );
+        assert_eq!(format.format(&AnyValue::String("1234")), None);
+        assert_eq!(ValueFormat::default().format(&AnyValue::Int32(1234)), None);
+    }
+
+    #[test]
+    fn test_formats_dates_and_booleans() {
+        let format = ValueFormat {
+            date_format: Some("%d/%m/%Y".to_owned()),
+            datetime_format: Some("%Y-%m-%d %H:%M".to_owned()),
+            boolean: Some(["\u{2713}".to_owned(), "\u{2717}".to_owned()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            format.format(&AnyValue::Date(19_000)),
+            Some("08/01/2022".to_owned())
+        );
+        assert_eq!(
+            format.format(&AnyValue::Datetime(
+                1_700_000_000_000,
+                TimeUnit::Milliseconds,
+                None
+            )),
+            Some("2023-11-14 22:13".to_owned())
+        );
+        assert_eq!(
+            format.format(&AnyValue::Boolean(false)),
+            Some("\u{2717}".to_owned())
+        );
+        let invalid = ValueFormat {
+            date_format: Some("%Q".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(invalid.format(&AnyValue::Date(19_000)), None);
+    }
+
+    #[test]
+    fn test_column_options_override_global_ones() {
+        let config = toml::from_str::<FormatConfig>(
+            r#"
+            float_precision = 2
+            thousands_separator = ","
+
+            [columns.rate]
+            float_precision = 4
+            "#,
+        )
+        .unwrap();
+        let rate = config.column("rate");
+        assert_eq!(rate.float_precision, Some(4));
+        assert_eq!(rate.thousands_separator, Some(','));
+        assert_eq!(config.column("price").float_precision, Some(2));
+    }
+}
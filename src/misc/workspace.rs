@@ -0,0 +1,194 @@
+use std::path::Path;
+
+use anyhow::anyhow;
+use serde::Deserialize;
+
+use crate::{
+    AppResult,
+    args::{Args, Format, InferSchema},
+    io::DataSource,
+};
+
+/// Sources, their reader options, and startup queries opened together from a
+/// TOML file, e.g. `tw workspace.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Workspace {
+    #[serde(default, rename = "source")]
+    pub sources: Vec<WorkspaceSource>,
+    #[serde(default, rename = "query")]
+    pub queries: Vec<WorkspaceQuery>,
+}
+
+/// File or URL of a workspace, with options overriding the command line ones.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceSource {
+    /// File path, relative to the workspace file, or http(s) URL.
+    pub path: String,
+    /// Table name used instead of the one derived from the path, for sources
+    /// holding a single table.
+    pub name: Option<String>,
+    pub format: Option<Format>,
+    pub separator: Option<char>,
+    pub quote_char: Option<char>,
+    pub no_header: Option<bool>,
+    pub ignore_errors: Option<bool>,
+    pub infer_schema: Option<InferSchema>,
+    pub infer_datetimes: Option<bool>,
+    pub truncate_ragged_lines: Option<bool>,
+    pub no_type_inference: Option<bool>,
+    pub widths: Option<String>,
+    pub sqlite_key: Option<String>,
+}
+
+/// SQL query run once the sources are loaded, opening its result in a tab.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceQuery {
+    /// Tab and table name of the result; unnamed results are not registered.
+    pub name: Option<String>,
+    pub sql: String,
+}
+
+impl Workspace {
+    /// Whether `source` is a workspace file rather than data.
+    pub fn is_workspace(source: &DataSource) -> bool {
+        match source {
+            DataSource::File(path) => path.extension().is_some_and(|ext| ext == "toml"),
+            _ => false,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> AppResult<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+            .map_err(|err| anyhow!("Invalid workspace {}: {err}", path.display()))
+    }
+
+    fn parse(contents: &str) -> AppResult<Self> {
+        let workspace: Workspace = toml::from_str(contents)?;
+        if workspace.sources.is_empty() {
+            Err(anyhow!("No [[source]] is listed"))
+        } else {
+            Ok(workspace)
+        }
+    }
+}
+
+impl WorkspaceSource {
+    /// Source of the data, with a relative path resolved against `dir`.
+    pub fn data_source(&self, dir: &Path) -> AppResult<DataSource> {
+        Ok(match self.path.parse()? {
+            DataSource::File(path) if path.is_relative() => DataSource::File(dir.join(path)),
+            source => source,
+        })
+    }
+
+    /// `args` with the options of the source applied over them.
+    pub fn args(&self, args: &Args) -> Args {
+        let mut args = args.clone();
+        if let Some(format) = &self.format {
+            args.format = Some(format.clone());
+        }
+        if let Some(separator) = self.separator {
+            args.separator = separator;
+        }
+        if let Some(quote_char) = self.quote_char {
+            args.quote_char = quote_char;
+        }
+        if let Some(no_header) = self.no_header {
+            args.no_header = no_header;
+        }
+        if let Some(ignore_errors) = self.ignore_errors {
+            args.ignore_errors = ignore_errors;
+        }
+        if let Some(infer_schema) = self.infer_schema {
+            args.infer_schema = infer_schema;
+        }
+        if let Some(infer_datetimes) = self.infer_datetimes {
+            args.infer_datetimes = infer_datetimes;
+        }
+        if let Some(truncate_ragged_lines) = self.truncate_ragged_lines {
+            args.truncate_ragged_lines = truncate_ragged_lines;
+        }
+        if let Some(no_type_inference) = self.no_type_inference {
+            args.no_type_inference = no_type_inference;
+        }
+        if let Some(widths) = &self.widths {
+            args.widths = widths.to_owned();
+        }
+        if let Some(sqlite_key) = &self.sqlite_key {
+            args.sqlite_key = Some(sqlite_key.to_owned());
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use clap::Parser;
+
+    use super::Workspace;
+    use crate::{
+        args::{Args, Format},
+        io::DataSource,
+    };
+
+    #[test]
+    fn test_parses_sources_and_queries() {
+        let workspace = Workspace::parse(
+            r#"
+            [[source]]
+            path = "sales.csv"
+            separator = ";"
+            no_header = true
+
+            [[source]]
+            path = "https://example.com/users.parquet"
+            name = "users"
+            format = "parquet"
+
+            [[query]]
+            name = "big_sales"
+            sql = "SELECT * FROM sales WHERE amount > 1000"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(workspace.sources.len(), 2);
+        assert_eq!(workspace.sources[1].name.as_deref(), Some("users"));
+        assert_eq!(workspace.queries.len(), 1);
+        assert_eq!(workspace.queries[0].name.as_deref(), Some("big_sales"));
+    }
+
+    #[test]
+    fn test_rejects_unknown_options_and_empty_workspaces() {
+        assert!(Workspace::parse("[[source]]\npath = \"a.csv\"\ndelimiter = \";\"").is_err());
+        assert!(Workspace::parse("[[query]]\nsql = \"SELECT 1\"").is_err());
+    }
+
+    #[test]
+    fn test_source_options_override_args() {
+        let workspace = Workspace::parse(
+            "[[source]]\npath = \"data/a.txt\"\nformat = \"tsv\"\nno_header = true",
+        )
+        .unwrap();
+        let source = &workspace.sources[0];
+        let args = source.args(&Args::parse_from(["tw", "--separator", "|"]));
+        assert!(matches!(args.format, Some(Format::Tsv)));
+        assert!(args.no_header);
+        assert_eq!(args.separator, '|');
+        assert!(matches!(
+            source.data_source(Path::new("/work")).unwrap(),
+            DataSource::File(path) if path == Path::new("/work/data/a.txt")
+        ));
+    }
+
+    #[test]
+    fn test_detects_workspace_files() {
+        assert!(Workspace::is_workspace(&"ws.toml".parse().unwrap()));
+        assert!(!Workspace::is_workspace(&"data.csv".parse().unwrap()));
+        assert!(!Workspace::is_workspace(&DataSource::Stdin));
+    }
+}
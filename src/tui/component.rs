@@ -1,4 +1,4 @@
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::{buffer::Buffer, layout::Rect};
 
 use crate::handler::message::Message;
@@ -21,6 +21,9 @@ pub trait Component {
     fn handle(&mut self, event: KeyEvent) -> bool {
         false
     }
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        false
+    }
     fn update(&mut self, action: &Message, focus_state: FocusState) {}
     fn tick(&mut self) {}
 }
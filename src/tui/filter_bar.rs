@@ -0,0 +1,133 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{text::Line, widgets::Widget};
+
+use crate::{
+    handler::message::Message,
+    tui::{
+        component::Component,
+        tag_line::{Tag, TagLine},
+        widgets::block::Block,
+    },
+};
+
+use super::widgets::input::Input;
+
+/// Input for stacking persistent row filters; `Tab` / `Shift+Tab` select a filter
+/// chip and `Delete` removes it.
+#[derive(Debug)]
+pub struct FilterBar {
+    input: Input,
+    count: usize,
+    selected: Option<usize>,
+}
+
+impl FilterBar {
+    pub fn new(count: usize) -> Self {
+        Self {
+            input: Default::default(),
+            count,
+            selected: None,
+        }
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    fn select_next(&mut self) {
+        self.selected = match self.selected {
+            _ if self.count == 0 => None,
+            Some(idx) if idx + 1 < self.count => Some(idx + 1),
+            Some(_) => None,
+            None => Some(0),
+        };
+    }
+
+    fn select_prev(&mut self) {
+        self.selected = match self.selected {
+            _ if self.count == 0 => None,
+            Some(0) => None,
+            Some(idx) => Some(idx - 1),
+            None => Some(self.count - 1),
+        };
+    }
+
+    fn remove(&mut self, idx: usize) {
+        Message::PaneRemoveFilter(idx).enqueue();
+        self.count -= 1;
+        self.selected = (self.count > 0).then(|| idx.min(self.count - 1));
+    }
+}
+
+impl Component for FilterBar {
+    fn render(
+        &mut self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        focus_state: super::component::FocusState,
+    ) {
+        let area = {
+            let block = Block::default().title("Filter");
+            let inner = block.inner(area);
+            block.render(area, buf);
+            inner
+        };
+        self.input.render(area, buf, focus_state);
+    }
+
+    fn handle(&mut self, event: KeyEvent) -> bool {
+        match (event.code, event.modifiers) {
+            (KeyCode::Tab, KeyModifiers::NONE) => {
+                self.select_next();
+                true
+            }
+            (KeyCode::BackTab, _) => {
+                self.select_prev();
+                true
+            }
+            (KeyCode::Delete, KeyModifiers::NONE) if self.selected.is_some() => {
+                if let Some(idx) = self.selected {
+                    self.remove(idx);
+                }
+                true
+            }
+            (KeyCode::Backspace, KeyModifiers::NONE)
+                if self.input.value().is_empty() && self.count > 0 =>
+            {
+                self.remove(self.count - 1);
+                true
+            }
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                Message::PaneDismissModal.enqueue();
+                if !self.input.value().trim().is_empty() {
+                    Message::PaneAddFilter(self.input.value().trim().to_owned()).enqueue();
+                }
+                true
+            }
+            (KeyCode::Esc, KeyModifiers::NONE) => {
+                Message::PaneDismissModal.enqueue();
+                true
+            }
+            _ => {
+                self.selected = None;
+                self.input.handle(event)
+            }
+        }
+    }
+}
+
+/// Chip list of persistent filters, marking the selected one for removal.
+pub fn filter_chips<'a>(filters: &'a [String], selected: Option<usize>) -> Line<'a> {
+    filters
+        .iter()
+        .enumerate()
+        .fold(TagLine::new().left_aligned(), |line, (idx, expr)| {
+            let key = if selected == Some(idx) {
+                "✕".to_owned()
+            } else {
+                (idx + 1).to_string()
+            };
+            line.tag(Tag::new(key, expr.as_str()))
+        })
+        .into()
+}
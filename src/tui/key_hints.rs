@@ -0,0 +1,486 @@
+use ratatui::{
+    layout::{Constraint, Flex, Layout},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Widget},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    misc::{
+        config::theme,
+        keybindings::{Context, keybindings},
+    },
+    tui::{component::Component, widgets::block::Block},
+};
+
+/// Keys of a part of the interface, listed by the help and the key hints.
+#[derive(Debug)]
+pub struct KeyGroup {
+    pub title: &'static str,
+    pub keys: &'static [(&'static str, &'static str)],
+}
+
+/// Every built-in key, grouped as shown in the help.
+pub const KEY_GROUPS: &[KeyGroup] = &[
+    KeyGroup {
+        title: "Navigation",
+        keys: &[
+            ("h j k l / arrows", "Navigate"),
+            ("Ctrl + p / Ctrl + n", "Previous / next row"),
+            ("15j / 100G", "Repeat a motion / go to row"),
+            ("b / w", "Previous / next column"),
+            ("[ / ]", "Previous / next page of columns"),
+            ("_ / $", "First / last column"),
+            ("Ctrl + u / Ctrl + d", "Move half page up / down"),
+            (
+                "Ctrl + b / Ctrl + f or PgUp / PgDn",
+                "Move full page up / down",
+            ),
+            ("Home or g", "Move to first row"),
+            ("End or G", "Move to last row"),
+            ("R", "Move to a random row"),
+            ("C", "Go to column by name"),
+            ("m{a-z} / '", "Mark row / jump to mark"),
+            ("Ctrl + o / Ctrl + i or Tab", "Back / forward in jump list"),
+        ],
+    },
+    KeyGroup {
+        title: "View",
+        keys: &[
+            ("Enter", "Open sheet (record view)"),
+            ("e", "Toggle Auto-Fit"),
+            ("o", "Cycle cell overflow"),
+            ("#", "Toggle original row numbers"),
+            ("F", "Cycle footer aggregate"),
+            ("M", "Toggle numeric heatmap"),
+            ("z", "Toggle null marker"),
+            ("T", "Toggle dtype badges"),
+            ("X", "Hide / show columns"),
+            ("i", "Show DataFrame info"),
+        ],
+    },
+    KeyGroup {
+        title: "Search",
+        keys: &[
+            ("/", "Fuzzy search"),
+            ("?", "Exact search"),
+            ("n / N", "Next / previous match"),
+            ("\\", "Persistent filter"),
+        ],
+    },
+    KeyGroup {
+        title: "Column Selection (c)",
+        keys: COLUMN,
+    },
+    KeyGroup {
+        title: "Visual Selection (V)",
+        keys: VISUAL,
+    },
+    KeyGroup {
+        title: "Commands",
+        keys: &[
+            (":", "Command palette"),
+            (".", "Repeat last data command"),
+            ("F1 / F2", "Help / key hints for the current mode"),
+            ("q{a-z} / @{a-z}", "Record / replay a macro"),
+            ("u / Ctrl + r", "Undo / redo data operation"),
+            ("F5", "Reload file of the tab"),
+        ],
+    },
+    KeyGroup {
+        title: "Tabs",
+        keys: &[
+            ("H / L", "Previous / next tab"),
+            ("Alt + H / Alt + L", "Move tab left / right"),
+            ("W / Ctrl + w", "Split view / switch focus"),
+            ("t", "Show tab panel"),
+            ("t then J / K", "Move tab down / up"),
+            ("t then r", "Rename tab"),
+            ("t then /", "Filter tabs"),
+        ],
+    },
+    KeyGroup {
+        title: "Quit",
+        keys: &[("x", "Close current view / tab"), ("Q", "Quit application")],
+    },
+];
+
+const TABLE: &[(&str, &str)] = &[
+    ("j / k", "Next / previous row"),
+    ("h / l", "Scroll left / right"),
+    ("b / w", "Previous / next column"),
+    ("g / G", "First / last row"),
+    ("15j / 100G", "Repeat / go to row"),
+    ("Enter", "Open sheet"),
+    ("c", "Column selection mode"),
+    ("V", "Visual row selection"),
+    ("X", "Hide / show columns"),
+    ("m / '", "Mark row / list marks"),
+    ("/ / ?", "Fuzzy / exact search"),
+    ("\\", "Persistent filter"),
+    ("u / Ctrl + r", "Undo / redo"),
+    ("t", "Tab panel"),
+    (":", "Command palette"),
+    ("F1", "Help"),
+];
+
+const COLUMN: &[(&str, &str)] = &[
+    ("h / l", "Previous / next column"),
+    ("j / k", "Next / previous row"),
+    ("s / S", "Sort ascending / descending"),
+    ("y / Y", "Copy cell / column"),
+    ("p", "Histogram"),
+    ("v", "Value counts"),
+    ("I / D", "Column info / statistics"),
+    ("< / >", "Move column"),
+    ("+ / - / =", "Widen / narrow / reset"),
+    ("x", "Hide column"),
+    ("i / Enter", "Edit cell"),
+    ("E", "Edit cell in $EDITOR"),
+    ("B", "Hex viewer"),
+    ("Esc", "Leave column mode"),
+];
+
+const VISUAL: &[(&str, &str)] = &[
+    ("j / k", "Extend the selection"),
+    ("y", "Copy rows"),
+    ("d", "Delete rows"),
+    (":", "agg or export the rows"),
+    ("Esc", "Leave visual mode"),
+];
+
+const MARK: &[(&str, &str)] = &[("a-z", "Mark the row with the letter")];
+
+/// Popup in the bottom right corner listing the keys of the current mode.
+#[derive(Debug)]
+pub struct KeyHints {
+    title: &'static str,
+    hints: Vec<(String, String)>,
+}
+
+impl KeyHints {
+    pub fn table() -> Self {
+        Self::new("Keys", TABLE).with_bindings(Context::Table)
+    }
+
+    pub fn column() -> Self {
+        Self::new("Column Keys", COLUMN)
+    }
+
+    pub fn visual() -> Self {
+        Self::new("Visual Keys", VISUAL)
+    }
+
+    pub fn mark() -> Self {
+        Self::new("Mark", MARK)
+    }
+
+    /// Mappings of the leader key, from the keybindings file.
+    pub fn leader() -> Self {
+        Self::new("Leader", &[]).with_bindings(Context::Leader)
+    }
+
+    fn new(title: &'static str, hints: &[(&str, &str)]) -> Self {
+        Self {
+            title,
+            hints: hints
+                .iter()
+                .map(|(key, desc)| (key.to_string(), desc.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Adds the keys bound in the keybindings file for `context`.
+    fn with_bindings(mut self, context: Context) -> Self {
+        self.hints.extend(keybindings().list(context));
+        self
+    }
+}
+
+impl Component for KeyHints {
+    fn render(
+        &mut self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        _focus_state: super::component::FocusState,
+    ) {
+        let key_width = self
+            .hints
+            .iter()
+            .map(|(key, _)| key.width())
+            .max()
+            .unwrap_or_default();
+        let lines = self
+            .hints
+            .iter()
+            .map(|(key, desc)| {
+                Line::from(vec![
+                    Span::styled(format!("{key:<key_width$}"), theme().text().bold()),
+                    Span::styled(format!("  {desc}"), theme().text()),
+                ])
+            })
+            .collect::<Vec<_>>();
+        let width = lines.iter().map(Line::width).max().unwrap_or_default() as u16 + 2;
+        let height = lines.len() as u16 + 2;
+        let [area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::End)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::End)
+            .areas(area);
+        Clear.render(area, buf);
+        let block = Block::default().title(self.title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use polars::df;
+
+    use super::*;
+    use crate::{
+        app::App,
+        tui::{
+            pane::{Pane, TableDescription},
+            tabs::Tabs,
+        },
+    };
+
+    /// Keys handled only in a state the test does not set up (after a search,
+    /// with a split view), or opening `$EDITOR` or saving the config file.
+    const UNCHECKED: &[&str] = &["n", "N", "Ctrl + w", "E", "z", "T"];
+
+    /// App with a single tab, after typing `prelude` to enter a mode.
+    fn app(prelude: &str) -> App {
+        let df = df!("n" => [1i64, 2, 3], "s" => ["a", "b", "c"]).unwrap();
+        let pane = Pane::new(df, TableDescription::Table("keys".to_owned()));
+        let mut app = App::new(Tabs::from_iter([pane]));
+        for event in keys(prelude) {
+            assert!(app.handle(event), "prelude '{prelude}' not handled");
+        }
+        app
+    }
+
+    /// Events typed for a key as the hints spell it, e.g. `Ctrl + u`, `15j`,
+    /// `m{a-z}`, or `h j k l`.
+    fn keys(text: &str) -> Vec<KeyEvent> {
+        let mut events = Vec::new();
+        let mut modifiers = KeyModifiers::NONE;
+        for token in text.replace("{a-z}", "a").split_whitespace() {
+            let code = match token {
+                "Ctrl" => {
+                    modifiers |= KeyModifiers::CONTROL;
+                    continue;
+                }
+                "Alt" => {
+                    modifiers |= KeyModifiers::ALT;
+                    continue;
+                }
+                "+" if !modifiers.is_empty() => continue,
+                "arrows" => {
+                    events.extend(
+                        [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right]
+                            .map(|code| KeyEvent::new(code, KeyModifiers::NONE)),
+                    );
+                    continue;
+                }
+                "a-z" => Some(KeyCode::Char('a')),
+                "Enter" => Some(KeyCode::Enter),
+                "Esc" => Some(KeyCode::Esc),
+                "Tab" => Some(KeyCode::Tab),
+                "PgUp" => Some(KeyCode::PageUp),
+                "PgDn" => Some(KeyCode::PageDown),
+                "Home" => Some(KeyCode::Home),
+                "End" => Some(KeyCode::End),
+                _ => token
+                    .strip_prefix('F')
+                    .and_then(|num| num.parse().ok())
+                    .map(KeyCode::F),
+            };
+            match code {
+                Some(code) => events.push(KeyEvent::new(code, modifiers)),
+                None => {
+                    for (idx, char) in token.chars().enumerate() {
+                        let mut modifiers = if idx == 0 {
+                            modifiers
+                        } else {
+                            KeyModifiers::NONE
+                        };
+                        if char.is_ascii_uppercase() {
+                            modifiers |= KeyModifiers::SHIFT;
+                        }
+                        events.push(KeyEvent::new(KeyCode::Char(char), modifiers));
+                    }
+                }
+            }
+            modifiers = KeyModifiers::NONE;
+        }
+        events
+    }
+
+    /// Every key of a hint with the events typing it: `t then J / K` gives
+    /// `J` typed as `t J` and `K` typed as `t K`.
+    fn alternatives(hint: &str) -> Vec<(&str, Vec<KeyEvent>)> {
+        hint.split(" or ")
+            .flat_map(|hint| {
+                let (prefix, rest) = hint.rsplit_once(" then ").unwrap_or(("", hint));
+                rest.split(" / ")
+                    .map(move |key| (key, [keys(prefix), keys(key)].concat()))
+            })
+            .collect()
+    }
+
+    fn assert_handled(prelude: &str, hints: &[(&str, &str)]) {
+        for (hint, desc) in hints {
+            for (key, events) in alternatives(hint) {
+                if UNCHECKED.contains(&key) {
+                    continue;
+                }
+                let mut app = app(prelude);
+                for event in events {
+                    assert!(
+                        app.handle(event),
+                        "'{key}' of '{hint}' ({desc}) is not handled after '{prelude}'"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_listed_keys_are_handled() {
+        for group in KEY_GROUPS {
+            let prelude = match group.title {
+                "Column Selection (c)" => "c",
+                "Visual Selection (V)" => "V",
+                _ => "",
+            };
+            assert_handled(prelude, group.keys);
+        }
+        assert_handled("", TABLE);
+        assert_handled("c", COLUMN);
+        assert_handled("V", VISUAL);
+        assert_handled("m", MARK);
+    }
+
+    #[test]
+    fn test_unbound_key_is_not_handled() {
+        assert!(!app("").handle(KeyEvent::new(KeyCode::F(12), KeyModifiers::NONE)));
+        assert_eq!(
+            keys("Ctrl + q{a-z}"),
+            [
+                KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL),
+                KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
+            ]
+        );
+    }
+
+    /// Keys typing a listed hint in the mode, up to the `{a-z}` a key may take.
+    fn listed(hints: &[&[(&str, &str)]]) -> HashSet<KeyEvent> {
+        hints
+            .iter()
+            .flat_map(|hints| hints.iter())
+            .flat_map(|(hint, _)| hint.split(" or "))
+            .filter(|hint| !hint.contains(" then "))
+            .flat_map(|hint| hint.split(" / "))
+            .flat_map(|key| keys(key.split("{a-z}").next().unwrap_or_default()))
+            .collect()
+    }
+
+    /// Every key that can be typed without a key combination beyond one
+    /// modifier.
+    fn typeable() -> Vec<KeyEvent> {
+        let mut events = Vec::new();
+        for c in ' '..='~' {
+            let shift = if c.is_ascii_uppercase() {
+                KeyModifiers::SHIFT
+            } else {
+                KeyModifiers::NONE
+            };
+            events.push(KeyEvent::new(KeyCode::Char(c), shift));
+            if c.is_ascii_alphabetic() {
+                events.push(KeyEvent::new(KeyCode::Char(c), shift | KeyModifiers::ALT));
+            }
+            if c.is_ascii_lowercase() {
+                events.push(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+            }
+        }
+        events.extend(
+            [
+                KeyCode::Enter,
+                KeyCode::Esc,
+                KeyCode::Tab,
+                KeyCode::BackTab,
+                KeyCode::Backspace,
+                KeyCode::Delete,
+                KeyCode::Insert,
+                KeyCode::Up,
+                KeyCode::Down,
+                KeyCode::Left,
+                KeyCode::Right,
+                KeyCode::Home,
+                KeyCode::End,
+                KeyCode::PageUp,
+                KeyCode::PageDown,
+            ]
+            .into_iter()
+            .chain((1..=12).map(KeyCode::F))
+            .map(|code| KeyEvent::new(code, KeyModifiers::NONE)),
+        );
+        events
+    }
+
+    #[test]
+    fn test_handled_keys_are_listed() {
+        let groups = |skipped: &[&str]| {
+            KEY_GROUPS
+                .iter()
+                .filter(|group| !skipped.contains(&group.title))
+                .map(|group| group.keys)
+                .collect::<Vec<_>>()
+        };
+        let table = [
+            groups(&["Column Selection (c)", "Visual Selection (V)"]),
+            vec![TABLE],
+        ]
+        .concat();
+        let mut unlisted = Vec::new();
+        for (prelude, hints) in [
+            ("", table.clone()),
+            ("c", [table.clone(), vec![COLUMN]].concat()),
+            ("V", [table.clone(), vec![VISUAL]].concat()),
+        ] {
+            let listed = listed(&hints);
+            for event in typeable() {
+                let skipped = matches!(event.code, KeyCode::Char('z' | 'T' | 'E'))
+                    && event.modifiers.difference(KeyModifiers::SHIFT).is_empty();
+                // counts take any digit, and keys the app takes whatever the
+                // modifiers are also take Alt
+                let is_listed = listed.contains(&event)
+                    || matches!(event.code, KeyCode::Char('0'..='9'))
+                    || event.modifiers.contains(KeyModifiers::ALT)
+                        && listed.contains(&KeyEvent::new(
+                            event.code,
+                            event.modifiers.difference(KeyModifiers::ALT),
+                        ));
+                if !skipped && !is_listed && app(prelude).handle(event) {
+                    unlisted.push(format!(
+                        "{prelude:?} {:?} {:?}",
+                        event.code, event.modifiers
+                    ));
+                }
+            }
+        }
+        assert!(
+            unlisted.is_empty(),
+            "handled but not listed:\n{}",
+            unlisted.join("\n")
+        );
+    }
+}
@@ -1,5 +1,7 @@
 pub mod component;
 pub mod error_popup;
+pub mod filter_bar;
+pub mod key_hints;
 pub mod pane;
 pub mod pickers;
 pub mod plots;
@@ -7,6 +9,7 @@ pub mod popups;
 pub mod schema;
 pub mod search_bar;
 pub mod sheet;
+pub mod substitute_bar;
 pub mod tab_switcher;
 pub mod table;
 pub mod tabs;
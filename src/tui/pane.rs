@@ -1,36 +1,69 @@
-use crossterm::event::{KeyCode, KeyModifiers};
+use std::{collections::VecDeque, sync::Arc};
+
+use anyhow::anyhow;
+use crossterm::event::{KeyCode, KeyModifiers, MouseEvent};
 
 use itertools::{FoldWhile, Itertools};
-use polars::frame::DataFrame;
+use polars::{frame::DataFrame, prelude::AnyValue};
 use rand::RngExt;
-use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::{
+    layout::{Constraint, Layout, Margin, Rect},
+    widgets::Widget,
+};
+use regex::Regex;
 use unicode_width::UnicodeWidthStr;
 
-use super::{search_bar::SearchBar, sheet::Sheet};
+use super::{
+    filter_bar::{FilterBar, filter_chips},
+    key_hints::KeyHints,
+    search_bar::SearchBar,
+    sheet::Sheet,
+    substitute_bar::SubstituteBar,
+};
 use crate::{
     AppResult,
     handler::message::Message,
     misc::{
         config::config,
-        external_editor::edit_in_external_editor,
+        external_editor::{edit_in_external_editor, edit_text_in_external_editor},
+        file_watch::FileWatch,
+        iter_ext::ZipItersExt,
         non_empty_stack::NonEmptyStack,
-        polars_ext::DataFrameExt,
+        osc52::CopyToClipboardOsc52,
+        polars_ext::{AnyValueExt, DataFrameExt},
+        search,
         sql::{TableSource, sql},
+        time_axis,
+        transform::{
+            self, Aggregation, Bins, ColumnPattern, FillNull, Footer, Keep, Plot, Rank, RowTarget,
+            SampleSize, Slice, TextPattern, WindowFunc,
+        },
         type_ext::UnwrapOrEnqueueError,
     },
     tui::{
         component::{Component, FocusState},
-        plots::{histogram_plot::HistogramPlot, scatter_plot::ScatterPlot},
+        plots::{
+            correlation_plot::CorrelationPlot, histogram_plot::HistogramPlot,
+            scatter_plot::ScatterPlot,
+        },
         popups::{
+            cell_editor::CellEditor,
             column_caster::ColumnCaster,
+            column_info::ColumnInfo,
+            column_stats::ColumnStats,
+            column_visibility::ColumnVisibility,
             data_frame_info::DataFrameInfo,
             exporter::Exporter,
+            go_to_column::GoToColumn,
             go_to_line::GoToLine,
-            histogram_builder::{self, HistogramBuilder},
+            hex_viewer::HexViewer,
+            histogram_builder::{self, DEFAULT_BUCKET_COUNT, HistogramBuilder},
             inline_query_picker::{InlineQueryPicker, QueryType},
+            marks::Marks,
             multi_step_overlay::MultiStepOverlay,
             scatter_plot_builder::{self, ScatterPlotBuilder},
             table_registerer::TableRegisterer,
+            transform_preview::TransformPreview,
         },
         search_bar::Searcher,
         table::Table,
@@ -42,6 +75,54 @@ pub struct Pane {
     tstack: NonEmptyStack<Table>,
     dstack: NonEmptyStack<TableDescription>,
     modal: Option<Modal>,
+    matches: Option<SearchMatches>,
+    filters: Option<Filters>,
+    /// States before the latest data operations, oldest first.
+    undo: VecDeque<Snapshot>,
+    /// States taken back by undo, most recent last.
+    redo: Vec<Snapshot>,
+    /// Whether the next letter marks the selected row.
+    marking: bool,
+    /// Whether the keys of the current mode are listed over the table.
+    key_hints: bool,
+    /// Whether the gutter numbers the rows by their order in the frame first
+    /// loaded, kept through sorts and filters, rather than by position.
+    original_numbers: bool,
+    /// File reloaded whenever it changes, when auto-reload is on.
+    watch: Option<FileWatch>,
+}
+
+/// Number of data operations that can be undone.
+const UNDO_LIMIT: usize = 50;
+
+/// Ranges a numeric column coloring a scatter plot is split into; integer
+/// columns with at most this many values color by value instead.
+const SCATTER_COLOR_BINS: usize = 8;
+
+/// Frames of a pane as they were before a data operation.
+#[derive(Debug)]
+struct Snapshot {
+    tstack: NonEmptyStack<Table>,
+    dstack: NonEmptyStack<TableDescription>,
+    filters: Option<Filters>,
+}
+
+/// Rows matched by the last committed search, kept for `n`/`N` navigation.
+#[derive(Debug)]
+struct SearchMatches {
+    /// Matching row indices of the searched frame in ascending order.
+    rows: Vec<usize>,
+    /// Stack level of the searched frame; the search result sits right above it.
+    level: usize,
+}
+
+/// Persistent row filters, stacked on the frame they filter.
+#[derive(Debug, Clone)]
+struct Filters {
+    /// SQL conditions, all of which a row has to satisfy.
+    exprs: Vec<String>,
+    /// Stack level of the unfiltered frame; the filtered frame sits right above it.
+    level: usize,
 }
 
 impl Pane {
@@ -58,6 +139,14 @@ impl Pane {
             ),
             dstack: NonEmptyStack::new(description),
             modal: None,
+            matches: None,
+            filters: None,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            marking: false,
+            key_hints: false,
+            original_numbers: false,
+            watch: None,
         }
     }
 
@@ -80,11 +169,14 @@ impl Pane {
     pub fn show_sheet(&mut self) {
         if let Some(row) = self.tstack.last().selected() {
             let sections = self.tstack.last().data_frame().get_sheet_sections(row);
-            self.modal = Some(Modal::Sheet(Sheet::new(row, sections)));
+            self.modal = Some(Modal::Sheet(
+                Sheet::new(row, sections).with_section(self.tstack.last().selected_column()),
+            ));
         }
     }
 
     fn show_fuzzy_search(&mut self) {
+        self.matches = None;
         let tbl = self.tstack.last().to_owned();
         self.tstack.push(tbl);
         self.dstack
@@ -95,6 +187,7 @@ impl Pane {
     }
 
     fn show_exact_search(&mut self) {
+        self.matches = None;
         let tbl = self.tstack.last().to_owned();
         self.tstack.push(tbl);
         self.dstack
@@ -104,6 +197,14 @@ impl Pane {
         )));
     }
 
+    fn show_filter_bar(&mut self) {
+        let count = self
+            .active_filters()
+            .map(<[String]>::len)
+            .unwrap_or_default();
+        self.modal = Some(Modal::FilterBar(FilterBar::new(count)));
+    }
+
     fn show_data_frame_info(&mut self) {
         match &self.dstack.last() {
             TableDescription::Table(desc) => {
@@ -119,6 +220,24 @@ impl Pane {
             | TableDescription::Order(_)
             | TableDescription::Select(_)
             | TableDescription::Cast(_)
+            | TableDescription::Rename(_)
+            | TableDescription::Drop(_)
+            | TableDescription::FillNull(_)
+            | TableDescription::Dedup(_)
+            | TableDescription::DropNulls(_)
+            | TableDescription::Explode(_)
+            | TableDescription::Unnest(_)
+            | TableDescription::Sample(_)
+            | TableDescription::Slice(_)
+            | TableDescription::Aggregate(_)
+            | TableDescription::Frequency(_)
+            | TableDescription::Describe(_)
+            | TableDescription::Transpose(_)
+            | TableDescription::Join(_)
+            | TableDescription::Concat(_)
+            | TableDescription::Diff(_)
+            | TableDescription::Split(_)
+            | TableDescription::Replace(_)
             | TableDescription::Search(_)
             | TableDescription::FuzzySearch(_) => {
                 self.modal = Some(Modal::DataFrameInfo(DataFrameInfo::new(
@@ -135,15 +254,37 @@ impl Pane {
         y_label: String,
         group_by: Option<&str>,
     ) -> AppResult<()> {
+        self.modal = Some(Modal::ScatterPlot(scatter_plot(
+            self.tstack.last().data_frame(),
+            x_label,
+            y_label,
+            group_by,
+        )?));
+        Ok(())
+    }
+
+    /// Shows `plot` of the current frame, or of its rows matching `condition`,
+    /// without pushing the filtered frame.
+    fn plot(&mut self, plot: &Plot, condition: Option<&str>) -> AppResult<()> {
         let df = self.tstack.last().data_frame();
-        let plot = if let Some(group_by) = group_by {
-            let (data, groups) = df.scatter_plot_data_grouped(&x_label, &y_label, group_by)?;
-            ScatterPlot::new(x_label, y_label, data)?.with_groups(groups)
-        } else {
-            let data = df.scatter_plot_data(&x_label, &y_label)?;
-            ScatterPlot::new(x_label, y_label, data)?
+        let df = match condition {
+            Some(condition) => {
+                sql().execute(&format!("SELECT * FROM _ WHERE {condition}"), df.clone())?
+            }
+            None => df.clone(),
         };
-        self.modal = Some(Modal::ScatterPlot(plot));
+        self.modal = Some(match plot {
+            Plot::Scatter { x, y, color } => Modal::ScatterPlot(scatter_plot(
+                &df,
+                x.to_owned(),
+                y.to_owned(),
+                color.as_deref(),
+            )?),
+            Plot::Histogram { column, buckets } => {
+                Modal::HistogramPlot(histogram_plot(&df, column, *buckets)?)
+            }
+            Plot::Bar(column) => Modal::HistogramPlot(bar_plot(&df, column)?),
+        });
         Ok(())
     }
 
@@ -154,24 +295,489 @@ impl Pane {
         )));
     }
 
+    /// Pushes the result of the select, filter, or order typed into the inline
+    /// prompt.
+    fn inline_query(&mut self, query_type: QueryType, value: &str) -> AppResult<()> {
+        let df = sql().execute(
+            &query_type.query(value),
+            self.tstack.last().data_frame().clone(),
+        )?;
+        let (description, toast) = match query_type {
+            QueryType::Select => (
+                TableDescription::Select(value.to_owned()),
+                format!("Column selection '{value}' occurred"),
+            ),
+            QueryType::Filter => (
+                TableDescription::Filter(value.to_owned()),
+                format!("Filter '{value}' applied"),
+            ),
+            QueryType::Order => (
+                TableDescription::Order(value.to_owned()),
+                format!("Data frame ordered by '{value}'"),
+            ),
+        };
+        self.push_data_frame(df, description);
+        Message::AppShowToast(toast).enqueue();
+        Ok(())
+    }
+
     fn show_go_to_line_with_value(&mut self, value: usize) {
         if let Some(selected) = self.tstack.last().selected() {
             self.modal = Some(Modal::GoToLine(GoToLine::new(selected).with_value(value)))
         }
     }
 
+    /// Selects the row numbered `line`, by its original number when the gutter
+    /// shows those and by position otherwise.
+    fn go_to_line(&mut self, line: usize) {
+        if !self.original_numbers {
+            self.select(line.saturating_sub(1));
+        } else if let Some(row) = self.tstack.last().row_position(line) {
+            self.select(row);
+        }
+    }
+
+    /// Moves to the row of `target` and, when given, the best match of `column`.
+    fn go_to(&mut self, column: Option<&str>, target: RowTarget) -> AppResult<()> {
+        if let Some(column) = column {
+            self.go_to_column(column)?;
+        }
+        let table = self.tstack.last();
+        let row = match target {
+            RowTarget::Line(line) if self.original_numbers => table
+                .row_position(line)
+                .ok_or(anyhow!("Row {line} is not in the current view"))?,
+            target => target.position(
+                table.selected().unwrap_or_default(),
+                table.data_frame().height(),
+            ),
+        };
+        self.tstack.last_mut().jump(row);
+        Ok(())
+    }
+
+    fn cycle_footer(&mut self) {
+        let footer = match self.tstack.last().footer() {
+            Some(footer) => footer.next(),
+            None => Some(Footer::Sum),
+        };
+        self.set_footer(footer);
+    }
+
+    fn set_footer(&mut self, footer: Option<Footer>) {
+        self.tstack.last_mut().set_footer(footer);
+        Message::AppShowToast(match footer {
+            Some(footer) => format!("Footer: {footer}"),
+            None => "Footer hidden".to_owned(),
+        })
+        .enqueue();
+    }
+
+    fn toggle_original_numbers(&mut self) {
+        self.original_numbers = !self.original_numbers;
+        Message::AppShowToast(if self.original_numbers {
+            "Rows numbered in their original order".to_owned()
+        } else {
+            "Rows numbered by position".to_owned()
+        })
+        .enqueue();
+    }
+
+    fn show_go_to_column(&mut self) {
+        self.modal = Some(Modal::GoToColumn(GoToColumn::default()));
+    }
+
+    fn go_to_column(&mut self, query: &str) -> AppResult<()> {
+        let df = self.tstack.last().data_frame();
+        let names = df.get_column_names();
+        match search::best_match(names.iter().map(|name| name.as_str()), query) {
+            Some(idx) => {
+                let table = self.tstack.last_mut();
+                if table.selected_column().is_some() {
+                    table.select_column(idx);
+                } else {
+                    table.scroll_to_column(idx);
+                }
+                Ok(())
+            }
+            // keep scrolling quietly while the name is being typed
+            None if matches!(self.modal, Some(Modal::GoToColumn(_))) => Ok(()),
+            None => Err(anyhow!("No column matches '{query}'")),
+        }
+    }
+
+    fn show_column_visibility(&mut self) {
+        let table = self.tstack.last();
+        self.modal = Some(Modal::ColumnVisibility(ColumnVisibility::new(
+            table
+                .data_frame()
+                .get_column_names()
+                .into_iter()
+                .map(|name| (name.to_string(), table.is_column_hidden(name)))
+                .collect::<Vec<_>>(),
+        )));
+    }
+
+    fn hide_columns(&mut self, patterns: &[ColumnPattern]) -> AppResult<()> {
+        let table = self.tstack.last_mut();
+        let names = transform::resolve_columns(table.data_frame(), patterns)?;
+        let remaining = table
+            .data_frame()
+            .get_column_names()
+            .into_iter()
+            .filter(|name| !table.is_column_hidden(name) && !names.iter().any(|n| n == *name))
+            .count();
+        if remaining == 0 {
+            return Err(anyhow!("At least one column must stay visible"));
+        }
+        for name in &names {
+            table.set_column_hidden(name, true);
+        }
+        Message::AppShowToast(format!("{} column(s) hidden", names.len())).enqueue();
+        Ok(())
+    }
+
+    /// Shows the matching hidden columns, or every hidden column without patterns.
+    fn unhide_columns(&mut self, patterns: &[ColumnPattern]) -> AppResult<()> {
+        let table = self.tstack.last_mut();
+        if patterns.is_empty() {
+            table.show_all_columns();
+            Message::AppShowToast("All columns shown".to_owned()).enqueue();
+        } else {
+            let names = transform::resolve_columns(table.data_frame(), patterns)?;
+            for name in &names {
+                table.set_column_hidden(name, false);
+            }
+            Message::AppShowToast(format!("{} column(s) shown", names.len())).enqueue();
+        }
+        Ok(())
+    }
+
+    fn set_column_width(&mut self, name: &str, width: Option<u16>) -> AppResult<()> {
+        let table = self.tstack.last_mut();
+        table.data_frame().column(name)?;
+        table.set_column_width(name, width);
+        Ok(())
+    }
+
+    fn hide_selected_column(&self) {
+        if let Some(name) = self.selected_column_name() {
+            Message::PaneHideColumns(vec![ColumnPattern::Name(name)]).enqueue();
+        }
+    }
+
+    /// Name of the column highlighted in column selection mode.
+    fn selected_column_name(&self) -> Option<String> {
+        let table = self.tstack.last();
+        table
+            .selected_column()
+            .and_then(|idx| table.data_frame().columns().get(idx))
+            .map(|column| column.name().to_string())
+    }
+
+    fn sort_selected_column(&self, descending: bool) {
+        if let Some(name) = self.selected_column_name() {
+            Message::PaneSort(name, descending).enqueue();
+        }
+    }
+
+    fn sort(&mut self, name: &str, descending: bool) -> AppResult<()> {
+        let df = transform::sort(&self.numbered_data_frame()?, name, descending)?;
+        self.checkpoint();
+        self.push_sorted(df, name.to_owned(), descending);
+        Ok(())
+    }
+
+    /// Sorts by the column `name` ascending, then descending, then not at all,
+    /// as its header is clicked again and again.
+    fn cycle_sort(&mut self, name: &str) -> AppResult<()> {
+        match self
+            .tstack
+            .last()
+            .sort()
+            .filter(|(column, _)| *column == name)
+            .map(|(_, descending)| descending)
+        {
+            Some(false) => {
+                self.checkpoint();
+                self.pop_data_frame();
+                let df = transform::sort(&self.numbered_data_frame()?, name, true)?;
+                self.push_sorted(df, name.to_owned(), true);
+            }
+            Some(true) => {
+                self.checkpoint();
+                self.pop_data_frame();
+            }
+            None => {
+                let df = transform::sort(&self.numbered_data_frame()?, name, false)?;
+                self.checkpoint();
+                self.push_sorted(df, name.to_owned(), false);
+            }
+        }
+        Ok(())
+    }
+
+    /// Pushes `df`, the numbered frame sorted by the column `name`, without a
+    /// checkpoint.
+    fn push_sorted(&mut self, mut df: DataFrame, name: String, descending: bool) {
+        let numbers = transform::take_row_numbers(&mut df);
+        let mut table = self.tstack.last().clone_with_data_frame(df);
+        table.set_row_numbers(numbers);
+        table.set_sort(name.clone(), descending);
+        self.tstack.push(table);
+        self.dstack.push(TableDescription::Order(if descending {
+            format!("{name} DESC")
+        } else {
+            name
+        }));
+    }
+
+    fn copy_selected_column(&self) -> AppResult<()> {
+        if let Some(name) = self.selected_column_name() {
+            let column = self.tstack.last().data_frame().column(&name)?;
+            std::iter::once(name.clone())
+                .chain(
+                    column
+                        .as_materialized_series()
+                        .iter()
+                        .map(|value| value.into_single_line()),
+                )
+                .join("\n")
+                .copy_to_clipboard_via_osc52();
+            Message::AppShowToast(format!("Column '{name}' copied to clipboard")).enqueue();
+        }
+        Ok(())
+    }
+
+    fn show_cell_editor(&mut self) -> AppResult<()> {
+        let table = self.tstack.last();
+        if let Some((row, col)) = table.selected_cell()
+            && let Some(column) = table.data_frame().columns().get(col)
+        {
+            self.modal = Some(Modal::CellEditor(CellEditor::new(
+                row,
+                column.name().to_string(),
+                column.get(row)?.into_single_line(),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Opens the active cell in the hex viewer; values other than binary ones
+    /// are shown as the bytes of their text.
+    fn show_hex_viewer(&mut self) -> AppResult<()> {
+        let table = self.tstack.last();
+        if let Some((row, col)) = table.selected_cell()
+            && let Some(column) = table.data_frame().columns().get(col)
+        {
+            let bytes = match column.get(row)? {
+                AnyValue::Binary(bytes) => bytes.to_vec(),
+                AnyValue::BinaryOwned(bytes) => bytes,
+                value => value.into_multi_line().into_bytes(),
+            };
+            self.modal = Some(Modal::HexViewer(HexViewer::new(
+                format!(
+                    "{} at row {} ({} bytes)",
+                    column.name(),
+                    row + 1,
+                    bytes.len()
+                ),
+                bytes,
+            )));
+        }
+        Ok(())
+    }
+
+    fn edit_cell_in_external_editor(&mut self) -> AppResult<()> {
+        let table = self.tstack.last();
+        let Some((row, col)) = table.selected_cell() else {
+            return Ok(());
+        };
+        let Some(column) = table.data_frame().columns().get(col) else {
+            return Ok(());
+        };
+        let name = column.name().to_string();
+        match edit_text_in_external_editor(&column.get(row)?.into_multi_line())? {
+            Some(value) => self.edit_cell(row, &name, &value),
+            None => {
+                Message::AppShowToast(format!("Cell of '{name}' unchanged")).enqueue();
+                Ok(())
+            }
+        }
+    }
+
+    fn edit_cell(&mut self, row: usize, name: &str, value: &str) -> AppResult<()> {
+        let df = transform::set_cell(self.tstack.last().data_frame(), name, row, value)?;
+        self.replace_data_frame(df);
+        self.modal = None;
+        Ok(())
+    }
+
+    fn edit_record(&mut self, row: usize, values: &[(String, String)]) -> AppResult<()> {
+        let df = transform::set_record(self.tstack.last().data_frame(), row, values)?;
+        let sections = df.get_sheet_sections(row);
+        self.replace_data_frame(df);
+        if let Some(Modal::Sheet(sheet)) = &mut self.modal {
+            sheet.set(row, sections);
+        }
+        Message::AppShowToast(format!(
+            "{} value(s) of row {} saved",
+            values.len(),
+            row + 1
+        ))
+        .enqueue();
+        Ok(())
+    }
+
+    fn copy_selected_cell(&self) -> AppResult<()> {
+        let table = self.tstack.last();
+        if let Some((row, col)) = table.selected_cell()
+            && let Some(column) = table.data_frame().columns().get(col)
+        {
+            column
+                .get(row)?
+                .into_multi_line()
+                .copy_to_clipboard_via_osc52();
+            Message::AppShowToast(format!(
+                "Cell of '{}' at row {} copied to clipboard",
+                column.name(),
+                row + 1
+            ))
+            .enqueue();
+        }
+        Ok(())
+    }
+
+    fn show_selected_column_histogram(&mut self) -> AppResult<()> {
+        if let Some(name) = self.selected_column_name() {
+            self.modal = Some(Modal::HistogramPlot(histogram_plot(
+                self.tstack.last().data_frame(),
+                &name,
+                None,
+            )?));
+        }
+        Ok(())
+    }
+
+    fn show_selected_column_stats(&mut self) {
+        if let Some(name) = self.selected_column_name() {
+            self.modal = Some(Modal::ColumnStats(ColumnStats::new(
+                self.tstack.last().data_frame().clone(),
+                name,
+            )));
+        }
+    }
+
+    fn show_selected_column_info(&mut self) -> AppResult<()> {
+        let table = self.tstack.last();
+        if let Some(column) = table
+            .selected_column()
+            .and_then(|col| table.data_frame().columns().get(col))
+        {
+            self.modal = Some(Modal::ColumnInfo(ColumnInfo::new(column)?));
+        }
+        Ok(())
+    }
+
+    fn set_mark(&mut self, mark: char) {
+        if let Some(row) = self.tstack.last_mut().set_mark(mark) {
+            Message::AppShowToast(format!("Row {} marked as '{mark}'", row + 1)).enqueue();
+        }
+    }
+
+    fn show_marks(&mut self) {
+        let table = self.tstack.last();
+        let df = table.data_frame();
+        let marks = table
+            .marks()
+            .iter()
+            .map(|(mark, row)| {
+                let preview = df
+                    .columns()
+                    .iter()
+                    .filter_map(|column| column.get(*row).ok())
+                    .map(AnyValueExt::into_single_line)
+                    .join(" | ");
+                (*mark, *row, preview)
+            })
+            .collect();
+        self.modal = Some(Modal::Marks(Marks::new(marks)));
+    }
+
     fn show_exporter(&mut self) {
         self.modal = Some(Modal::Exporter(Exporter::new(
-            self.tstack.last().data_frame().clone().into(),
+            self.selection_or_data_frame().into(),
         )))
     }
 
+    /// Rows of the visual selection, or the whole frame without one.
+    fn selection_or_data_frame(&self) -> DataFrame {
+        let table = self.tstack.last();
+        match table.visual_selection() {
+            Some(rows) => table.data_frame().slice(*rows.start() as i64, rows.count()),
+            None => table.data_frame().clone(),
+        }
+    }
+
+    fn copy_visual_selection(&mut self) {
+        let df = self.selection_or_data_frame();
+        std::iter::once(df.get_column_names().into_iter().join("\t"))
+            .chain(
+                df.columns()
+                    .iter()
+                    .map(|col| col.as_materialized_series().iter())
+                    .zip_iters()
+                    .map(|values| {
+                        values
+                            .into_iter()
+                            .map(|value| value.into_single_line())
+                            .join("\t")
+                    }),
+            )
+            .join("\n")
+            .copy_to_clipboard_via_osc52();
+        self.tstack.last_mut().toggle_visual_selection();
+        Message::AppShowToast(format!("{} row(s) copied to clipboard", df.height())).enqueue();
+    }
+
+    fn delete_visual_selection(&mut self) -> AppResult<()> {
+        let table = self.tstack.last();
+        if let Some(rows) = table.visual_selection() {
+            let df = self.numbered_data_frame()?;
+            let (start, end) = (*rows.start(), *rows.end());
+            let kept = df
+                .slice(0, start)
+                .vstack(&df.slice(end as i64 + 1, df.height()))?;
+            self.push_numbered(
+                kept,
+                TableDescription::Drop(format!("rows {} to {}", start + 1, end + 1)),
+            );
+            self.select(start);
+            Message::AppShowToast(format!("{} row(s) deleted", end - start + 1)).enqueue();
+        }
+        Ok(())
+    }
+
     fn show_histogram(&mut self, col: &str, buckets: usize) -> AppResult<()> {
-        self.modal = Some(Modal::HistogramPlot(HistogramPlot::new(
-            self.tstack
-                .last()
-                .data_frame()
-                .histogram_plot_data(col, buckets)?,
+        self.modal = Some(Modal::HistogramPlot(histogram_plot(
+            self.tstack.last().data_frame(),
+            col,
+            Some(buckets),
+        )?));
+        Ok(())
+    }
+
+    fn show_bar_plot(&mut self, col: &str) -> AppResult<()> {
+        self.modal = Some(Modal::HistogramPlot(bar_plot(
+            self.tstack.last().data_frame(),
+            col,
+        )?));
+        Ok(())
+    }
+
+    fn show_correlation(&mut self) -> AppResult<()> {
+        self.modal = Some(Modal::CorrelationPlot(CorrelationPlot::new(
+            transform::correlation(&self.selection_or_data_frame())?,
         )));
         Ok(())
     }
@@ -200,15 +806,508 @@ impl Pane {
         )))
     }
 
+    fn mutate(&mut self, name: &str, expr: &str) -> AppResult<()> {
+        let df = transform::mutate(self.tstack.last().data_frame(), name, expr)?;
+        self.replace_data_frame(df);
+        Message::AppShowToast(format!("Column '{name}' computed from '{expr}'")).enqueue();
+        Ok(())
+    }
+
+    fn rename_column(&mut self, old: &str, new: &str) -> AppResult<()> {
+        let df = transform::rename(self.tstack.last().data_frame(), old, new)?;
+        self.push_same_rows(df, TableDescription::Rename(format!("'{old}' to '{new}'")));
+        Message::AppShowToast(format!("Column '{old}' renamed to '{new}'")).enqueue();
+        Ok(())
+    }
+
+    fn rename_columns(&mut self, pattern: &str, replacement: &str) -> AppResult<()> {
+        let (df, count) =
+            transform::rename_regex(self.tstack.last().data_frame(), pattern, replacement)?;
+        self.push_same_rows(
+            df,
+            TableDescription::Rename(format!("/{pattern}/ to '{replacement}'")),
+        );
+        Message::AppShowToast(format!("{count} column(s) renamed")).enqueue();
+        Ok(())
+    }
+
+    fn drop_columns(&mut self, patterns: &[ColumnPattern]) -> AppResult<()> {
+        let df = self.tstack.last().data_frame();
+        let dropped = transform::resolve_columns(df, patterns)?;
+        let df = transform::drop_columns(df, &dropped)?;
+        self.push_same_rows(df, TableDescription::Drop(dropped.join(", ")));
+        Message::AppShowToast(format!("{} column(s) dropped", dropped.len())).enqueue();
+        Ok(())
+    }
+
+    fn fill_null(&mut self, column: &str, fill: &FillNull) -> AppResult<()> {
+        let (df, count) = transform::fill_null(self.tstack.last().data_frame(), column, fill)?;
+        self.push_same_rows(
+            df,
+            TableDescription::FillNull(format!("'{column}' with {fill}")),
+        );
+        Message::AppShowToast(format!("{count} null(s) filled in '{column}'")).enqueue();
+        Ok(())
+    }
+
+    fn dedup(&mut self, patterns: &[ColumnPattern], keep: Keep) -> AppResult<()> {
+        let df = self.tstack.last().data_frame();
+        let subset = if patterns.is_empty() {
+            Vec::new()
+        } else {
+            transform::resolve_columns(df, patterns)?
+        };
+        let on = if subset.is_empty() {
+            df.get_column_names()
+                .into_iter()
+                .map(|name| name.to_string())
+                .collect_vec()
+        } else {
+            subset.clone()
+        };
+        let (df, dropped) = transform::dedup(&self.numbered_data_frame()?, &on, keep)?;
+        self.push_numbered(
+            df,
+            TableDescription::Dedup(if subset.is_empty() {
+                "all columns".to_owned()
+            } else {
+                subset.join(", ")
+            }),
+        );
+        Message::AppShowToast(format!("{dropped} duplicate row(s) dropped")).enqueue();
+        Ok(())
+    }
+
+    fn drop_nulls(&mut self, patterns: &[ColumnPattern]) -> AppResult<()> {
+        let df = self.tstack.last().data_frame();
+        let subset = if patterns.is_empty() {
+            Vec::new()
+        } else {
+            transform::resolve_columns(df, patterns)?
+        };
+        let (df, dropped) = transform::drop_nulls(&self.numbered_data_frame()?, &subset)?;
+        self.push_numbered(
+            df,
+            TableDescription::DropNulls(if subset.is_empty() {
+                "all columns".to_owned()
+            } else {
+                subset.join(", ")
+            }),
+        );
+        Message::AppShowToast(format!("{dropped} row(s) with nulls dropped")).enqueue();
+        Ok(())
+    }
+
+    fn explode(&mut self, name: &str) -> AppResult<()> {
+        let (df, added) = transform::explode(self.tstack.last().data_frame(), name)?;
+        self.push_data_frame(df, TableDescription::Explode(name.to_owned()));
+        Message::AppShowToast(format!("'{name}' exploded into {added} more row(s)")).enqueue();
+        Ok(())
+    }
+
+    fn unnest(&mut self, name: &str) -> AppResult<()> {
+        let df = transform::unnest(self.tstack.last().data_frame(), name)?;
+        self.push_data_frame(df, TableDescription::Unnest(name.to_owned()));
+        Ok(())
+    }
+
+    fn dummies(&mut self, name: &str) -> AppResult<()> {
+        let (df, count) = transform::dummies(self.tstack.last().data_frame(), name)?;
+        self.replace_data_frame(df);
+        Message::AppShowToast(format!("{count} indicator column(s) created from '{name}'"))
+            .enqueue();
+        Ok(())
+    }
+
+    fn sample(&mut self, size: SampleSize, seed: Option<u64>) -> AppResult<()> {
+        let df = transform::sample(self.tstack.last().data_frame(), size, seed)?;
+        Message::TabsAddPane(
+            df,
+            TableDescription::Sample(format!("{size} of {}", self.title())),
+        )
+        .enqueue();
+        Ok(())
+    }
+
+    fn slice(&mut self, slice: Slice) -> AppResult<()> {
+        let df = transform::slice(&self.numbered_data_frame()?, slice);
+        self.push_numbered(df, TableDescription::Slice(slice.to_string()));
+        Ok(())
+    }
+
+    fn aggregate(&mut self, by: &[String], aggs: &[Aggregation]) -> AppResult<()> {
+        let df = transform::aggregate(&self.selection_or_data_frame(), by, aggs)?;
+        let aggs = aggs.iter().join(", ");
+        Message::TabsAddPane(
+            df,
+            TableDescription::Aggregate(if by.is_empty() {
+                aggs
+            } else {
+                format!("{aggs} by {}", by.join(", "))
+            }),
+        )
+        .enqueue();
+        Ok(())
+    }
+
+    fn frequency(&mut self, name: &str) -> AppResult<()> {
+        let df = transform::frequency(&self.selection_or_data_frame(), name)?;
+        Message::TabsAddPane(
+            df,
+            TableDescription::Frequency(format!("{name} of {}", self.title())),
+        )
+        .enqueue();
+        Ok(())
+    }
+
+    fn describe(&mut self) -> AppResult<()> {
+        let df = transform::describe(&self.selection_or_data_frame())?;
+        Message::TabsAddPane(df, TableDescription::Describe(self.title().to_owned())).enqueue();
+        Ok(())
+    }
+
+    fn transpose(&mut self) -> AppResult<()> {
+        let df = transform::transpose(self.tstack.last().data_frame())?;
+        self.push_data_frame(df, TableDescription::Transpose(self.title().to_owned()));
+        Ok(())
+    }
+
+    fn split_column(&mut self, name: &str, delimiter: &TextPattern) -> AppResult<()> {
+        let (df, count) =
+            transform::split_column(self.tstack.last().data_frame(), name, delimiter)?;
+        self.push_same_rows(
+            df,
+            TableDescription::Split(format!("{name} by {delimiter}")),
+        );
+        Message::AppShowToast(format!("'{name}' split into {count} column(s)")).enqueue();
+        Ok(())
+    }
+
+    fn parse_dates(&mut self, name: &str, fmt: &str) -> AppResult<()> {
+        let df = transform::parse_dates(self.tstack.last().data_frame(), name, fmt)?;
+        self.replace_data_frame(df);
+        Message::AppShowToast(format!("Column '{name}' parsed with '{fmt}'")).enqueue();
+        Ok(())
+    }
+
+    fn convert_time_zone(&mut self, name: &str, from: &str, to: &str) -> AppResult<()> {
+        let df = transform::convert_time_zone(self.tstack.last().data_frame(), name, from, to)?;
+        self.replace_data_frame(df);
+        Message::AppShowToast(format!("Column '{name}' converted from {from} to {to}")).enqueue();
+        Ok(())
+    }
+
+    fn replace(&mut self, name: &str, pattern: &TextPattern, replacement: &str) -> AppResult<()> {
+        let (df, count) =
+            transform::replace(self.tstack.last().data_frame(), name, pattern, replacement)?;
+        if count == 0 {
+            Message::AppShowToast(format!("No cell in '{name}' matches {pattern}")).enqueue();
+        } else {
+            self.modal = Some(Modal::TransformPreview(TransformPreview::new(
+                df,
+                TableDescription::Replace(format!("{pattern} with '{replacement}' in '{name}'")),
+                format!("Replace {count} cell(s) in '{name}'?"),
+            )));
+        }
+        Ok(())
+    }
+
+    fn show_substitute_bar(
+        &mut self,
+        pattern: &str,
+        replacement: &str,
+        columns: &[ColumnPattern],
+    ) -> AppResult<()> {
+        let df = self.tstack.last().data_frame().clone();
+        let pattern = Regex::new(pattern)?;
+        let cells = transform::matching_cells(&df, columns, &pattern)?;
+        if cells.is_empty() {
+            Message::AppShowToast(format!("No cell matches /{pattern}/")).enqueue();
+        } else {
+            self.push_same_rows(
+                df.clone(),
+                TableDescription::Replace(format!("/{pattern}/ with '{replacement}'")),
+            );
+            self.modal = Some(Modal::SubstituteBar(SubstituteBar::new(
+                df,
+                pattern,
+                replacement.to_owned(),
+                cells,
+            )));
+            self.sync_substitute_bar();
+        }
+        Ok(())
+    }
+
+    /// Mirrors the progress of the substitute bar onto the table, closing the bar
+    /// once every match is handled.
+    fn sync_substitute_bar(&mut self) {
+        let Some(Modal::SubstituteBar(substitute_bar)) = &mut self.modal else {
+            return;
+        };
+        let table = self.tstack.last_mut();
+        if let Some(df) = substitute_bar.take_update() {
+            table.replace_data_frame(df);
+        }
+        if let Some((row, _)) = substitute_bar.current() {
+            table.select(row);
+        }
+        table.set_highlight(substitute_bar.highlight());
+        if substitute_bar.is_done() {
+            let (replaced, total) = (substitute_bar.replaced(), substitute_bar.total());
+            self.modal = None;
+            if replaced == 0 {
+                self.pop_data_frame();
+            }
+            Message::AppShowToast(format!("Replaced {replaced} of {total} match(es)")).enqueue();
+        }
+    }
+
+    fn cut(&mut self, name: &str, bins: &Bins) -> AppResult<()> {
+        let df = transform::cut(self.tstack.last().data_frame(), name, bins)?;
+        self.replace_data_frame(df);
+        Message::AppShowToast(format!("Column '{name}_bin' created with {bins}")).enqueue();
+        Ok(())
+    }
+
+    fn rank(&mut self, name: &str, rank: Rank, descending: bool) -> AppResult<()> {
+        let df = transform::rank(self.tstack.last().data_frame(), name, rank, descending)?;
+        self.replace_data_frame(df);
+        Message::AppShowToast(format!("Column '{}' created", rank.column_name(name))).enqueue();
+        Ok(())
+    }
+
+    fn window(&mut self, name: &str, func: WindowFunc) -> AppResult<()> {
+        let df = transform::window(self.tstack.last().data_frame(), name, func)?;
+        self.replace_data_frame(df);
+        Message::AppShowToast(format!("Column '{}' created", func.column_name(name))).enqueue();
+        Ok(())
+    }
+
+    /// Persistent filters of the frame on top of the stack, when it is the
+    /// filtered frame.
+    pub fn active_filters(&self) -> Option<&[String]> {
+        self.filters
+            .as_ref()
+            .filter(|filters| filters.level + 1 == self.tstack.len_without_base())
+            .map(|filters| filters.exprs.as_slice())
+    }
+
+    fn add_filter(&mut self, expr: &str) -> AppResult<()> {
+        let (level, mut exprs) = match (self.active_filters(), &self.filters) {
+            (Some(exprs), Some(filters)) => (filters.level, exprs.to_vec()),
+            _ => (self.tstack.len_without_base(), Vec::new()),
+        };
+        exprs.push(expr.to_owned());
+        self.apply_filters(Filters { exprs, level })?;
+        Message::AppShowToast(format!("Filter '{expr}' applied")).enqueue();
+        Ok(())
+    }
+
+    fn remove_filter(&mut self, idx: usize) -> AppResult<()> {
+        let Some(filters) = self
+            .filters
+            .as_ref()
+            .filter(|_| self.active_filters().is_some())
+        else {
+            return Ok(());
+        };
+        let mut exprs = filters.exprs.clone();
+        let level = filters.level;
+        if idx < exprs.len() {
+            let expr = exprs.remove(idx);
+            if exprs.is_empty() {
+                self.checkpoint();
+                self.pop_data_frame();
+            } else {
+                self.apply_filters(Filters { exprs, level })?;
+            }
+            Message::AppShowToast(format!("Filter '{expr}' removed")).enqueue();
+        }
+        Ok(())
+    }
+
+    /// Filters the frame at the level of `filters` into the frame right above it,
+    /// pushing that frame unless the filters are already active.
+    fn apply_filters(&mut self, filters: Filters) -> AppResult<()> {
+        let df = match self.tstack.get(filters.level) {
+            Some(table) => transform::number_rows(table.data_frame(), table.row_numbers())?,
+            None => DataFrame::default(),
+        };
+        let condition = filters
+            .exprs
+            .iter()
+            .map(|expr| format!("({expr})"))
+            .join(" AND ");
+        let mut df = sql().execute(&format!("SELECT * FROM _ WHERE {condition}"), df)?;
+        let numbers = transform::take_row_numbers(&mut df);
+        let description = TableDescription::Filter(filters.exprs.join(" AND "));
+        if self.active_filters().is_some() {
+            self.replace_data_frame(df);
+            *self.dstack.last_mut() = description;
+        } else {
+            self.push_data_frame(df, description);
+        }
+        self.tstack.last_mut().set_row_numbers(numbers);
+        self.filters = Some(filters);
+        Ok(())
+    }
+
+    /// Records the current frames for undo, dropping what was undone.
+    fn checkpoint(&mut self) {
+        if self.undo.len() == UNDO_LIMIT {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(Snapshot {
+            tstack: self.tstack.clone(),
+            dstack: self.dstack.clone(),
+            filters: self.filters.clone(),
+        });
+        self.redo.clear();
+    }
+
+    /// Puts the frames of `snapshot` in place, returning the replaced ones.
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        self.modal = None;
+        self.matches = None;
+        Snapshot {
+            tstack: std::mem::replace(&mut self.tstack, snapshot.tstack),
+            dstack: std::mem::replace(&mut self.dstack, snapshot.dstack),
+            filters: std::mem::replace(&mut self.filters, snapshot.filters),
+        }
+    }
+
+    fn undo(&mut self) {
+        match self.undo.pop_back() {
+            Some(snapshot) => {
+                let current = self.restore(snapshot);
+                self.redo.push(current);
+                Message::AppShowToast("Undone".to_owned()).enqueue();
+            }
+            None => Message::AppShowToast("Nothing to undo".to_owned()).enqueue(),
+        }
+    }
+
+    fn redo(&mut self) {
+        match self.redo.pop() {
+            Some(snapshot) => {
+                let current = self.restore(snapshot);
+                self.undo.push_back(current);
+                Message::AppShowToast("Redone".to_owned()).enqueue();
+            }
+            None => Message::AppShowToast("Nothing to redo".to_owned()).enqueue(),
+        }
+    }
+
+    /// Replaces the frame on top of the stack, keeping the previous one for undo.
+    fn replace_data_frame(&mut self, df: DataFrame) {
+        self.checkpoint();
+        self.tstack.last_mut().replace_data_frame(df);
+    }
+
     fn push_data_frame(&mut self, df: DataFrame, description: TableDescription) {
+        self.checkpoint();
         self.tstack
             .push(self.tstack.last().clone_with_data_frame(df));
         self.dstack.push(description);
     }
 
+    /// Pushes `df`, holding the rows of the current frame in the same order,
+    /// keeping their numbers.
+    fn push_same_rows(&mut self, df: DataFrame, description: TableDescription) {
+        let numbers = self.tstack.last().row_numbers().map(Arc::from);
+        self.push_data_frame(df, description);
+        self.tstack.last_mut().set_row_numbers(numbers);
+    }
+
+    /// Current frame with a column numbering its rows, to sort or filter them
+    /// before [`Self::push_numbered`].
+    fn numbered_data_frame(&self) -> AppResult<DataFrame> {
+        let table = self.tstack.last();
+        transform::number_rows(table.data_frame(), table.row_numbers())
+    }
+
+    /// Pushes `df`, rows taken from [`Self::numbered_data_frame`], keeping the
+    /// numbers of those rows.
+    fn push_numbered(&mut self, mut df: DataFrame, description: TableDescription) {
+        let numbers = transform::take_row_numbers(&mut df);
+        self.push_data_frame(df, description);
+        self.tstack.last_mut().set_row_numbers(numbers);
+    }
+
     fn pop_data_frame(&mut self) {
         self.tstack.pop();
         self.dstack.pop();
+        if self
+            .matches
+            .as_ref()
+            .is_some_and(|matches| matches.level > self.tstack.len_without_base())
+        {
+            self.matches = None;
+        }
+        if self
+            .filters
+            .as_ref()
+            .is_some_and(|filters| filters.level >= self.tstack.len_without_base())
+        {
+            self.filters = None;
+        }
+    }
+
+    /// Position of the selected row among the last search matches and the
+    /// number of matches, when the selected row is a match.
+    pub fn match_status(&self) -> Option<(usize, usize)> {
+        let matches = self.matches.as_ref()?;
+        let selected = self.tstack.last().selected()?;
+        let level = self.tstack.len_without_base();
+        if level == matches.level + 1 {
+            Some((selected + 1, self.tstack.last().data_frame().height()))
+        } else if level == matches.level {
+            matches
+                .rows
+                .binary_search(&selected)
+                .ok()
+                .map(|pos| (pos + 1, matches.rows.len()))
+        } else {
+            None
+        }
+    }
+
+    fn select_match(&mut self, forward: bool) {
+        let Some(matches) = self.matches.as_ref() else {
+            return;
+        };
+        let level = self.tstack.len_without_base();
+        let table = self.tstack.last_mut();
+        let selected = table.selected().unwrap_or_default();
+        if level == matches.level + 1 {
+            let len = table.data_frame().height();
+            if len > 0 {
+                table.jump(if forward {
+                    (selected + 1) % len
+                } else {
+                    (selected + len - 1) % len
+                });
+            }
+        } else if level == matches.level {
+            let target = if forward {
+                matches
+                    .rows
+                    .iter()
+                    .find(|row| **row > selected)
+                    .or(matches.rows.first())
+            } else {
+                matches
+                    .rows
+                    .iter()
+                    .rev()
+                    .find(|row| **row < selected)
+                    .or(matches.rows.last())
+            };
+            if let Some(row) = target {
+                table.jump(*row);
+            }
+        }
     }
 
     fn select(&mut self, idx: usize) {
@@ -223,10 +1322,173 @@ impl Pane {
         self.modal.take();
     }
 
+    /// Reads the tab's file again, dropping the operations stacked on it but
+    /// keeping the selected row and column where they still exist.
+    fn reload(&mut self) -> AppResult<()> {
+        let df = sql().reload(self.file_table()?)?;
+        if let Some(watch) = self.watch.as_mut() {
+            watch.take_reloaded();
+        }
+        self.checkpoint();
+        self.replace_with_reloaded(df);
+        Message::AppShowToast(format!(
+            "Reloaded '{}' ({} rows)",
+            self.title(),
+            self.tstack.last().data_frame().height()
+        ))
+        .enqueue();
+        Ok(())
+    }
+
+    /// SQL table of the file the tab was opened from.
+    fn file_table(&self) -> AppResult<&str> {
+        match self.dstack.base() {
+            TableDescription::Table(name) => Ok(name),
+            _ => Err(anyhow!("Only tabs of files can be reloaded")),
+        }
+    }
+
+    /// Puts `df` in place of the base frame, dropping the operations stacked on
+    /// it. A cursor on the last row follows rows appended to the file.
+    fn replace_with_reloaded(&mut self, df: DataFrame) {
+        let table = self.tstack.last();
+        let selected = table.selected();
+        let on_last_row = selected.is_some_and(|row| row + 1 == table.data_frame().height());
+        let selected_column = table.selected_column();
+        while self.tstack.len_without_base() > 0 {
+            self.pop_data_frame();
+        }
+        let table = self.tstack.last_mut();
+        let height = df.height();
+        table.replace_data_frame(df);
+        match selected {
+            Some(_) if on_last_row => table.select(height.saturating_sub(1)),
+            Some(row) => table.select(row),
+            None => (),
+        }
+        if let Some(column) = selected_column {
+            table.select_column(column);
+        }
+    }
+
+    /// Starts reloading the tab's file whenever it changes, or stops.
+    fn toggle_auto_reload(&mut self) -> AppResult<()> {
+        if self.watch.take().is_some() {
+            Message::AppShowToast("Auto-reload off".to_owned()).enqueue();
+            return Ok(());
+        }
+        let name = self.file_table()?;
+        let source = sql()
+            .schema()
+            .get(name)
+            .map(|info| (info.source().clone(), info.reader().cloned()));
+        let watch = match source {
+            Some((TableSource::File(path), Some(reader))) => FileWatch::new(path, reader),
+            _ => return Err(anyhow!("Only tabs of local files can be auto-reloaded")),
+        };
+        Message::AppShowToast(format!(
+            "Auto-reload on, watching {}",
+            watch.path().display()
+        ))
+        .enqueue();
+        self.watch = Some(watch);
+        Ok(())
+    }
+
+    /// Puts the frame the watched file was read into after its latest change in
+    /// place, as an undoable step. Waits for open modals to close and for the
+    /// operations stacked on the file's frame to be closed first, as reloading
+    /// would drop them.
+    fn check_watched_file(&mut self) {
+        let Some(watch) = self.watch.as_mut() else {
+            return;
+        };
+        let stacked = self.tstack.len_without_base() > 0;
+        match watch.update() {
+            Ok(true) if stacked => Message::AppShowToast(format!(
+                "'{}' changed, reloading once back on its table or with F5",
+                watch.path().display()
+            ))
+            .enqueue(),
+            Ok(_) => (),
+            Err(err) => Message::AppShowError(err.to_string()).enqueue(),
+        }
+        if stacked || self.modal.is_some() {
+            return;
+        }
+        let Some(df) = watch.take_reloaded() else {
+            return;
+        };
+        match self.file_table() {
+            Ok(name) => sql().set_reloaded(name, &df),
+            Err(err) => {
+                Message::AppShowError(err.to_string()).enqueue();
+                return;
+            }
+        }
+        self.checkpoint();
+        self.replace_with_reloaded(df);
+    }
+
+    /// Copy of the pane with its frames, filters, and view, but without its undo
+    /// history or open modal.
+    pub fn duplicate(&self) -> Pane {
+        Pane {
+            tstack: self.tstack.clone(),
+            dstack: self.dstack.clone(),
+            modal: None,
+            matches: None,
+            filters: self.filters.clone(),
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            marking: false,
+            key_hints: self.key_hints,
+            original_numbers: self.original_numbers,
+            watch: None,
+        }
+    }
+
+    /// Whether keys go to the table, with no popup or prompt open.
+    pub fn is_browsing(&self) -> bool {
+        self.modal.is_none() && !self.marking
+    }
+
     pub fn title(&self) -> &str {
         self.dstack.base().description()
     }
 
+    /// Renames the tab, moving its SQL table to the new name or, for tabs
+    /// without one (e.g. query results), registering its frame under it.
+    pub fn rename(&mut self, name: &str) -> AppResult<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("Tab name cannot be empty"));
+        }
+        let info = match self.dstack.base() {
+            TableDescription::Table(old) => {
+                let info = sql().schema().get(old).cloned();
+                if info.is_some() {
+                    sql().unregister(old);
+                }
+                info
+            }
+            _ => None,
+        };
+        let name = sql().register(
+            name,
+            self.tstack.base().data_frame().clone(),
+            info.as_ref()
+                .map(|info| info.source().clone())
+                .unwrap_or(TableSource::User),
+        );
+        if let Some(reader) = info.as_ref().and_then(|info| info.reader()) {
+            sql().set_reader(&name, reader.clone());
+        }
+        Message::AppShowToast(format!("Tab renamed to '{name}'")).enqueue();
+        *self.dstack.base_mut() = TableDescription::Table(name);
+        Ok(())
+    }
+
     pub fn history(&self, mut width: usize) -> String {
         self.dstack
             .iter()
@@ -264,9 +1526,21 @@ impl Component for Pane {
         buf: &mut ratatui::prelude::Buffer,
         focus_state: super::component::FocusState,
     ) {
-        self.tstack
-            .last_mut()
-            .set_gutter_visibility(config().show_table_row_numbers());
+        let table = self.tstack.last_mut();
+        table.set_original_numbers(self.original_numbers);
+        table.set_gutter_visibility(config().show_table_row_numbers());
+        let area = if let Some(filters) = self.active_filters() {
+            let [chips_area, area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+            let selected = match &self.modal {
+                Some(Modal::FilterBar(filter_bar)) => filter_bar.selected(),
+                _ => None,
+            };
+            filter_chips(filters, selected).render(chips_area, buf);
+            area
+        } else {
+            area
+        };
         match &mut self.modal {
             Some(Modal::Sheet(sheet_state)) => {
                 if let Some(row) = self.tstack.last().selected()
@@ -285,10 +1559,26 @@ impl Component for Pane {
                 self.tstack.last_mut().render(table_area, buf, focus_state);
                 search_bar_state.render(search_area, buf, focus_state);
             }
+            Some(Modal::FilterBar(filter_bar)) => {
+                let [filter_area, table_area] =
+                    Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+                self.tstack.last_mut().render(table_area, buf, focus_state);
+                filter_bar.render(filter_area, buf, focus_state);
+            }
+            Some(Modal::SubstituteBar(substitute_bar)) => {
+                let [table_area, substitute_area] =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Length(4)]).areas(area);
+                self.tstack.last_mut().render(table_area, buf, focus_state);
+                substitute_bar.render(substitute_area, buf, focus_state);
+            }
             Some(Modal::GoToLine(state)) => {
                 self.tstack.last_mut().render(area, buf, focus_state);
                 state.render(area, buf, focus_state);
             }
+            Some(Modal::GoToColumn(state)) => {
+                self.tstack.last_mut().render(area, buf, focus_state);
+                state.render(area, buf, focus_state);
+            }
             Some(Modal::DataFrameInfo(data_frame_info)) => {
                 self.tstack
                     .last_mut()
@@ -301,6 +1591,12 @@ impl Component for Pane {
                     .render(area, buf, FocusState::NotFocused);
                 state.render(area, buf, focus_state);
             }
+            Some(Modal::CorrelationPlot(state)) => {
+                self.tstack
+                    .last_mut()
+                    .render(area, buf, FocusState::NotFocused);
+                state.render(area, buf, focus_state);
+            }
             Some(Modal::HistogramPlot(state)) => {
                 self.tstack
                     .last_mut()
@@ -343,18 +1639,106 @@ impl Component for Pane {
                     .render(area, buf, FocusState::NotFocused);
                 state.render(area, buf, focus_state);
             }
+            Some(Modal::TransformPreview(state)) => {
+                self.tstack
+                    .last_mut()
+                    .render(area, buf, FocusState::NotFocused);
+                state.render(area, buf, focus_state);
+            }
+            Some(Modal::ColumnVisibility(state)) => {
+                self.tstack
+                    .last_mut()
+                    .render(area, buf, FocusState::NotFocused);
+                state.render(area, buf, focus_state);
+            }
+            Some(Modal::ColumnStats(state)) => {
+                self.tstack
+                    .last_mut()
+                    .render(area, buf, FocusState::NotFocused);
+                state.render(area, buf, focus_state);
+            }
+            Some(Modal::CellEditor(state)) => {
+                self.tstack
+                    .last_mut()
+                    .render(area, buf, FocusState::NotFocused);
+                state.render(area, buf, focus_state);
+            }
+            Some(Modal::Marks(state)) => {
+                self.tstack
+                    .last_mut()
+                    .render(area, buf, FocusState::NotFocused);
+                state.render(area, buf, focus_state);
+            }
+            Some(Modal::HexViewer(state)) => {
+                self.tstack
+                    .last_mut()
+                    .render(area, buf, FocusState::NotFocused);
+                state.render(area, buf, focus_state);
+            }
+            Some(Modal::ColumnInfo(state)) => {
+                self.tstack
+                    .last_mut()
+                    .render(area, buf, FocusState::NotFocused);
+                state.render(area, buf, focus_state);
+            }
             None => self.tstack.last_mut().render(area, buf, focus_state),
         }
+        let table = self.tstack.last();
+        let key_hints = if self.marking {
+            Some(KeyHints::mark())
+        } else if self.key_hints && self.modal.is_none() {
+            Some(if table.selected_column().is_some() {
+                KeyHints::column()
+            } else if table.visual_selection().is_some() {
+                KeyHints::visual()
+            } else {
+                KeyHints::table()
+            })
+        } else {
+            None
+        };
+        if let Some(mut key_hints) = key_hints {
+            key_hints.render(area, buf, focus_state);
+        }
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        match &mut self.modal {
+            Some(Modal::Sheet(sheet)) => {
+                sheet.handle_mouse(event) || self.tstack.last_mut().handle_mouse(event)
+            }
+            None
+            | Some(Modal::SearchBar(_))
+            | Some(Modal::FilterBar(_))
+            | Some(Modal::SubstituteBar(_)) => self.tstack.last_mut().handle_mouse(event),
+            _ => false,
+        }
     }
 
     fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        if std::mem::take(&mut self.marking) {
+            if let KeyCode::Char(mark) = event.code
+                && mark.is_ascii_lowercase()
+            {
+                self.set_mark(mark);
+            }
+            return true;
+        }
         (match &mut self.modal {
             Some(Modal::SearchBar(search_bar)) => {
+                if event.code == KeyCode::Esc {
+                    self.matches = None;
+                }
                 search_bar.handle(event) || self.tstack.last_mut().handle(event)
             }
             Some(Modal::Sheet(sheet)) => {
                 sheet.handle(event) || self.tstack.last_mut().handle(event)
             }
+            Some(Modal::FilterBar(filter_bar)) => filter_bar.handle(event),
+            Some(Modal::SubstituteBar(substitute_bar)) => {
+                substitute_bar.handle(event) || self.tstack.last_mut().handle(event)
+            }
+            Some(Modal::GoToColumn(go_to_column)) => go_to_column.handle(event),
             Some(Modal::GoToLine(go_to_line)) => go_to_line.handle(event),
             Some(Modal::DataFrameInfo(data_frame_info)) => data_frame_info.handle(event),
             Some(Modal::Exporter(exporter)) => exporter.handle(event),
@@ -362,22 +1746,63 @@ impl Component for Pane {
             Some(Modal::HistogramBuilder(histogram_builder)) => histogram_builder.handle(event),
             Some(Modal::InlineQueryPicker(query_picker)) => query_picker.handle(event),
             Some(Modal::ScatterPlot(scatter_plot)) => scatter_plot.handle(event),
+            Some(Modal::CorrelationPlot(correlation_plot)) => correlation_plot.handle(event),
             Some(Modal::TableRegisterer(table_registerer)) => table_registerer.handle(event),
             Some(Modal::ScatterPlotBuilder(scatter_plot_builder)) => {
                 scatter_plot_builder.handle(event)
             }
             Some(Modal::ColumnCaster(column_caster)) => column_caster.handle(event),
+            Some(Modal::TransformPreview(preview)) => preview.handle(event),
+            Some(Modal::ColumnVisibility(column_visibility)) => column_visibility.handle(event),
+            Some(Modal::ColumnStats(column_stats)) => column_stats.handle(event),
+            Some(Modal::CellEditor(cell_editor)) => cell_editor.handle(event),
+            Some(Modal::Marks(marks)) => marks.handle(event),
+            Some(Modal::HexViewer(hex_viewer)) => hex_viewer.handle(event),
+            Some(Modal::ColumnInfo(column_info)) => column_info.handle(event),
 
             None => self.tstack.last_mut().handle(event),
         }) || (match (event.code, event.modifiers) {
             (KeyCode::Enter, KeyModifiers::NONE) => {
-                self.show_sheet();
+                if self.tstack.last().selected_column().is_some() {
+                    self.show_cell_editor().unwrap_or_enqueue_error();
+                } else {
+                    self.show_sheet();
+                }
                 true
             }
             (KeyCode::Char('e'), KeyModifiers::NONE) => {
                 self.tstack.last_mut().toggle_view_mode();
                 true
             }
+            (KeyCode::Char('M'), KeyModifiers::SHIFT) => {
+                let heatmap = self.tstack.last_mut().toggle_heatmap();
+                Message::AppShowToast(format!("Heatmap {}", if heatmap { "on" } else { "off" }))
+                    .enqueue();
+                true
+            }
+            (KeyCode::Char('z'), KeyModifiers::NONE) => {
+                config().toggle_show_null_marker();
+                config().store().unwrap_or_enqueue_error();
+                true
+            }
+            (KeyCode::Char('T'), KeyModifiers::SHIFT) => {
+                config().toggle_show_dtype_badges();
+                config().store().unwrap_or_enqueue_error();
+                true
+            }
+            (KeyCode::Char('F'), KeyModifiers::SHIFT) => {
+                self.cycle_footer();
+                true
+            }
+            (KeyCode::Char('#'), _) => {
+                self.toggle_original_numbers();
+                true
+            }
+            (KeyCode::Char('o'), KeyModifiers::NONE) => {
+                let overflow = self.tstack.last_mut().cycle_overflow();
+                Message::AppShowToast(format!("Cell overflow: {overflow}")).enqueue();
+                true
+            }
             (KeyCode::Char('1'), KeyModifiers::NONE) => {
                 self.show_go_to_line_with_value(1);
                 true
@@ -414,6 +1839,110 @@ impl Component for Pane {
                 self.show_go_to_line_with_value(9);
                 true
             }
+            (KeyCode::Char('c'), KeyModifiers::NONE) => {
+                self.tstack.last_mut().toggle_column_selection();
+                true
+            }
+            (KeyCode::Char('C'), KeyModifiers::SHIFT) => {
+                Message::PaneShowGoToColumn.enqueue();
+                true
+            }
+            (KeyCode::Char('V'), KeyModifiers::SHIFT) => {
+                self.tstack.last_mut().toggle_visual_selection();
+                true
+            }
+            (KeyCode::Char('y'), KeyModifiers::NONE)
+                if self.tstack.last().visual_selection().is_some() =>
+            {
+                self.copy_visual_selection();
+                true
+            }
+            (KeyCode::Char('d'), KeyModifiers::NONE)
+                if self.tstack.last().visual_selection().is_some() =>
+            {
+                self.delete_visual_selection().unwrap_or_enqueue_error();
+                true
+            }
+            (KeyCode::Char('s'), KeyModifiers::NONE)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                self.sort_selected_column(false);
+                true
+            }
+            (KeyCode::Char('S'), KeyModifiers::SHIFT)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                self.sort_selected_column(true);
+                true
+            }
+            (KeyCode::Char('y'), KeyModifiers::NONE)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                self.copy_selected_cell().unwrap_or_enqueue_error();
+                true
+            }
+            (KeyCode::Char('Y'), KeyModifiers::SHIFT)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                self.copy_selected_column().unwrap_or_enqueue_error();
+                true
+            }
+            (KeyCode::Char('x'), KeyModifiers::NONE)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                self.hide_selected_column();
+                true
+            }
+            (KeyCode::Char('X'), KeyModifiers::SHIFT) => {
+                self.show_column_visibility();
+                true
+            }
+            (KeyCode::Char('p'), KeyModifiers::NONE)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                self.show_selected_column_histogram()
+                    .unwrap_or_enqueue_error();
+                true
+            }
+            (KeyCode::Char('v'), KeyModifiers::NONE)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                if let Some(name) = self.selected_column_name() {
+                    Message::PaneFrequency(name).enqueue();
+                }
+                true
+            }
+            (KeyCode::Char('D'), KeyModifiers::SHIFT)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                self.show_selected_column_stats();
+                true
+            }
+            (KeyCode::Char('I'), KeyModifiers::SHIFT)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                self.show_selected_column_info().unwrap_or_enqueue_error();
+                true
+            }
+            (KeyCode::Char('i'), KeyModifiers::NONE)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                self.show_cell_editor().unwrap_or_enqueue_error();
+                true
+            }
+            (KeyCode::Char('B'), KeyModifiers::SHIFT)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                self.show_hex_viewer().unwrap_or_enqueue_error();
+                true
+            }
+            (KeyCode::Char('E'), KeyModifiers::SHIFT)
+                if self.tstack.last().selected_column().is_some() =>
+            {
+                self.edit_cell_in_external_editor()
+                    .unwrap_or_enqueue_error();
+                true
+            }
             (KeyCode::Char('i'), KeyModifiers::NONE) => {
                 self.show_data_frame_info();
                 true
@@ -422,6 +1951,26 @@ impl Component for Pane {
                 self.show_fuzzy_search();
                 true
             }
+            (KeyCode::Char('m'), KeyModifiers::NONE) => {
+                self.marking = true;
+                true
+            }
+            (KeyCode::Char('\''), KeyModifiers::NONE) => {
+                self.show_marks();
+                true
+            }
+            (KeyCode::Char('\\'), KeyModifiers::NONE) => {
+                self.show_filter_bar();
+                true
+            }
+            (KeyCode::F(2), KeyModifiers::NONE) => {
+                self.key_hints = !self.key_hints;
+                true
+            }
+            (KeyCode::F(5), KeyModifiers::NONE) => {
+                Message::PaneReload.enqueue();
+                true
+            }
             (KeyCode::Char('R'), KeyModifiers::SHIFT)
                 if !matches!(self.modal, Some(Modal::GoToLine(_))) =>
             {
@@ -433,10 +1982,39 @@ impl Component for Pane {
                 self.show_exact_search();
                 true
             }
-            (KeyCode::Char('q'), KeyModifiers::NONE) if self.tstack.len_without_base() > 0 => {
+            (KeyCode::Char('n'), KeyModifiers::NONE) if self.matches.is_some() => {
+                self.select_match(true);
+                true
+            }
+            (KeyCode::Char('N'), KeyModifiers::SHIFT) if self.matches.is_some() => {
+                self.select_match(false);
+                true
+            }
+            (KeyCode::Char('x'), KeyModifiers::NONE) if self.tstack.len_without_base() > 0 => {
+                self.checkpoint();
                 self.pop_data_frame();
                 true
             }
+            (KeyCode::Char('u'), KeyModifiers::NONE) => {
+                self.undo();
+                true
+            }
+            (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+                self.redo();
+                true
+            }
+            (KeyCode::Char('o'), KeyModifiers::CONTROL) => {
+                if !self.tstack.last_mut().jump_back() {
+                    Message::AppShowToast("No older jump".to_owned()).enqueue();
+                }
+                true
+            }
+            (KeyCode::Char('i'), KeyModifiers::CONTROL) | (KeyCode::Tab, KeyModifiers::NONE) => {
+                if !self.tstack.last_mut().jump_forward() {
+                    Message::AppShowToast("No newer jump".to_owned()).enqueue();
+                }
+                true
+            }
             _ => false,
         })
     }
@@ -456,6 +2034,13 @@ impl Component for Pane {
             Message::PaneShowInlineOrder if focus_state.is_focused() => {
                 self.show_inline_query_picker(QueryType::Order)
             }
+            Message::PaneShowFilterBar if focus_state.is_focused() => self.show_filter_bar(),
+            Message::PaneAddFilter(expr) if focus_state.is_focused() => {
+                self.add_filter(expr).unwrap_or_enqueue_error()
+            }
+            Message::PaneRemoveFilter(idx) if focus_state.is_focused() => {
+                self.remove_filter(*idx).unwrap_or_enqueue_error()
+            }
             Message::PaneShowExporter if focus_state.is_focused() => self.show_exporter(),
             Message::PaneShowScatterPlotBuilder if focus_state.is_focused() => {
                 self.show_scatter_plot_builder()
@@ -466,9 +2051,18 @@ impl Component for Pane {
             Message::PaneShowHistogram(col, buckets) if focus_state.is_focused() => {
                 self.show_histogram(col, *buckets).unwrap_or_enqueue_error()
             }
+            Message::PaneShowCorrelation if focus_state.is_focused() => {
+                self.show_correlation().unwrap_or_enqueue_error()
+            }
+            Message::PaneShowBarPlot(col) if focus_state.is_focused() => {
+                self.show_bar_plot(col).unwrap_or_enqueue_error()
+            }
             Message::PaneShowScatterPlot(x, y, grp) if focus_state.is_focused() => self
                 .show_scatter_plot(x.to_owned(), y.to_owned(), grp.as_deref())
                 .unwrap_or_enqueue_error(),
+            Message::PanePlot(plot, condition) if focus_state.is_focused() => self
+                .plot(plot, condition.as_deref())
+                .unwrap_or_enqueue_error(),
             Message::PaneShowTableRegisterer if focus_state.is_focused() => {
                 self.show_table_registerer()
             }
@@ -477,9 +2071,133 @@ impl Component for Pane {
                 self.push_data_frame(df.clone(), desc.clone())
             }
             Message::PanePopDataFrame if focus_state.is_focused() => self.pop_data_frame(),
+            Message::PaneCycleSort(name) if focus_state.is_focused() => {
+                self.cycle_sort(name).unwrap_or_enqueue_error()
+            }
+            Message::PaneSort(name, descending) if focus_state.is_focused() => {
+                self.sort(name, *descending).unwrap_or_enqueue_error()
+            }
+            Message::PaneInlineQuery(query_type, value) if focus_state.is_focused() => self
+                .inline_query(*query_type, value)
+                .unwrap_or_enqueue_error(),
             Message::PaneTableSelect(idx) if focus_state.is_focused() => self.select(*idx),
+            Message::PaneTableMove(motion, count) if focus_state.is_focused() => {
+                self.tstack.last_mut().move_by(*motion, *count)
+            }
+            Message::PaneTableJump(idx) if focus_state.is_focused() => {
+                self.tstack.last_mut().jump(*idx)
+            }
+            Message::PaneRecordJump(from) if focus_state.is_focused() => {
+                self.tstack.last_mut().record_jump(*from)
+            }
+            Message::PaneGoToLine(line) if focus_state.is_focused() => self.go_to_line(*line),
+            Message::PaneGoTo(column, target) if focus_state.is_focused() => self
+                .go_to(column.as_deref(), *target)
+                .unwrap_or_enqueue_error(),
+            Message::PaneShowGoToColumn if focus_state.is_focused() => self.show_go_to_column(),
+            Message::PaneGoToColumn(query) if focus_state.is_focused() => {
+                self.go_to_column(query).unwrap_or_enqueue_error()
+            }
             Message::PaneShowTableInfo if focus_state.is_focused() => self.show_data_frame_info(),
             Message::PaneShowColumnCaster if focus_state.is_focused() => self.show_column_caster(),
+            Message::PaneShowColumnVisibility if focus_state.is_focused() => {
+                self.show_column_visibility()
+            }
+            Message::PaneHideColumns(patterns) if focus_state.is_focused() => {
+                self.hide_columns(patterns).unwrap_or_enqueue_error()
+            }
+            Message::PaneUnhideColumns(patterns) if focus_state.is_focused() => {
+                self.unhide_columns(patterns).unwrap_or_enqueue_error()
+            }
+            Message::PaneSetFooter(footer) if focus_state.is_focused() => self.set_footer(*footer),
+            Message::PaneSetColumnWidth(name, width) if focus_state.is_focused() => self
+                .set_column_width(name, *width)
+                .unwrap_or_enqueue_error(),
+            Message::PaneMutate(name, expr) if focus_state.is_focused() => {
+                self.mutate(name, expr).unwrap_or_enqueue_error()
+            }
+            Message::PaneRenameColumn(old, new) if focus_state.is_focused() => {
+                self.rename_column(old, new).unwrap_or_enqueue_error()
+            }
+            Message::PaneRenameColumns(pattern, replacement) if focus_state.is_focused() => self
+                .rename_columns(pattern, replacement)
+                .unwrap_or_enqueue_error(),
+            Message::PaneDropColumns(patterns) if focus_state.is_focused() => {
+                self.drop_columns(patterns).unwrap_or_enqueue_error()
+            }
+            Message::PaneFillNull(column, fill) if focus_state.is_focused() => {
+                self.fill_null(column, fill).unwrap_or_enqueue_error()
+            }
+            Message::PaneDedup(patterns, keep) if focus_state.is_focused() => {
+                self.dedup(patterns, *keep).unwrap_or_enqueue_error()
+            }
+            Message::PaneDropNulls(patterns) if focus_state.is_focused() => {
+                self.drop_nulls(patterns).unwrap_or_enqueue_error()
+            }
+            Message::PaneExplode(name) if focus_state.is_focused() => {
+                self.explode(name).unwrap_or_enqueue_error()
+            }
+            Message::PaneUnnest(name) if focus_state.is_focused() => {
+                self.unnest(name).unwrap_or_enqueue_error()
+            }
+            Message::PaneEditCell(row, name, value) if focus_state.is_focused() => {
+                self.edit_cell(*row, name, value).unwrap_or_enqueue_error()
+            }
+            Message::PaneEditRecord(row, values) if focus_state.is_focused() => {
+                self.edit_record(*row, values).unwrap_or_enqueue_error()
+            }
+            Message::PaneDummies(name) if focus_state.is_focused() => {
+                self.dummies(name).unwrap_or_enqueue_error()
+            }
+            Message::PaneSample(size, seed) if focus_state.is_focused() => {
+                self.sample(*size, *seed).unwrap_or_enqueue_error()
+            }
+            Message::PaneSlice(slice) if focus_state.is_focused() => {
+                self.slice(*slice).unwrap_or_enqueue_error()
+            }
+            Message::PaneAggregate(by, aggs) if focus_state.is_focused() => {
+                self.aggregate(by, aggs).unwrap_or_enqueue_error()
+            }
+            Message::PaneFrequency(name) if focus_state.is_focused() => {
+                self.frequency(name).unwrap_or_enqueue_error()
+            }
+            Message::PaneDescribe if focus_state.is_focused() => {
+                self.describe().unwrap_or_enqueue_error()
+            }
+            Message::PaneReload if focus_state.is_focused() => {
+                self.reload().unwrap_or_enqueue_error()
+            }
+            Message::PaneToggleAutoReload if focus_state.is_focused() => {
+                self.toggle_auto_reload().unwrap_or_enqueue_error()
+            }
+            Message::PaneTranspose if focus_state.is_focused() => {
+                self.transpose().unwrap_or_enqueue_error()
+            }
+            Message::PaneSplit(name, delimiter) if focus_state.is_focused() => {
+                self.split_column(name, delimiter).unwrap_or_enqueue_error()
+            }
+            Message::PaneParseDates(name, fmt) if focus_state.is_focused() => {
+                self.parse_dates(name, fmt).unwrap_or_enqueue_error()
+            }
+            Message::PaneConvertTimeZone(name, from, to) if focus_state.is_focused() => self
+                .convert_time_zone(name, from, to)
+                .unwrap_or_enqueue_error(),
+            Message::PaneReplace(name, pattern, replacement) if focus_state.is_focused() => self
+                .replace(name, pattern, replacement)
+                .unwrap_or_enqueue_error(),
+            Message::PaneSubstitute(pattern, replacement, columns) if focus_state.is_focused() => {
+                self.show_substitute_bar(pattern, replacement, columns)
+                    .unwrap_or_enqueue_error()
+            }
+            Message::PaneCut(name, bins) if focus_state.is_focused() => {
+                self.cut(name, bins).unwrap_or_enqueue_error()
+            }
+            Message::PaneRank(name, rank, descending) if focus_state.is_focused() => self
+                .rank(name, *rank, *descending)
+                .unwrap_or_enqueue_error(),
+            Message::PaneWindow(name, func) if focus_state.is_focused() => {
+                self.window(name, *func).unwrap_or_enqueue_error()
+            }
             Message::PaneShowSearch if focus_state.is_focused() => {
                 self.show_exact_search();
             }
@@ -500,10 +2218,24 @@ impl Component for Pane {
     }
 
     fn tick(&mut self) {
+        self.check_watched_file();
         match &mut self.modal {
             Some(Modal::SearchBar(search_bar)) => {
-                if let Some(df) = search_bar.searcher().latest() {
+                if let Some((df, rows)) = search_bar.searcher().latest() {
+                    let level = self.tstack.len_without_base().saturating_sub(1);
+                    let numbers = self.tstack.get(level).map(|searched| {
+                        rows.iter()
+                            .map(|row| searched.row_number(*row as usize))
+                            .collect()
+                    });
                     self.tstack.last_mut().set_data_frame(df);
+                    self.tstack.last_mut().set_row_numbers(numbers);
+                    self.tstack.last_mut().set_highlight(search_bar.highlight());
+                    self.matches =
+                        (!search_bar.searcher().pattern().is_empty()).then(|| SearchMatches {
+                            rows: rows.into_iter().map(|row| row as usize).sorted().collect(),
+                            level,
+                        });
                     *self.description_mut() = match search_bar.searcher() {
                         Searcher::Fuzzy(_) => {
                             TableDescription::FuzzySearch(search_bar.value().to_owned())
@@ -514,36 +2246,107 @@ impl Component for Pane {
                     };
                 }
             }
+            Some(Modal::SubstituteBar(_)) => self.sync_substitute_bar(),
             Some(Modal::Sheet(_)) => (),
+            Some(Modal::FilterBar(_)) => (),
             Some(Modal::DataFrameInfo(_)) => (),
             Some(Modal::ScatterPlot(_)) => (),
+            Some(Modal::CorrelationPlot(_)) => (),
             Some(Modal::HistogramPlot(_)) => (),
             Some(Modal::InlineQueryPicker(_)) => (),
             Some(Modal::GoToLine(_)) => (),
+            Some(Modal::GoToColumn(_)) => (),
             Some(Modal::Exporter(_)) => (),
             Some(Modal::HistogramBuilder(_)) => (),
             Some(Modal::ScatterPlotBuilder(_)) => (),
             Some(Modal::TableRegisterer(_)) => (),
             Some(Modal::ColumnCaster(_)) => (),
+            Some(Modal::TransformPreview(_)) => (),
+            Some(Modal::ColumnVisibility(_)) => (),
+            Some(Modal::ColumnStats(_)) => (),
+            Some(Modal::CellEditor(_)) => (),
+            Some(Modal::Marks(_)) => (),
+            Some(Modal::HexViewer(_)) => (),
+            Some(Modal::ColumnInfo(_)) => (),
             None => (),
         }
     }
 }
 
+/// Scatter plot of `y_label` against `x_label`, colored by `group_by`, with a
+/// time axis when `x_label` holds dates or datetimes.
+fn scatter_plot(
+    df: &DataFrame,
+    x_label: String,
+    y_label: String,
+    group_by: Option<&str>,
+) -> AppResult<ScatterPlot> {
+    let x_time = time_axis::seconds_per_unit(df.column(&x_label)?.dtype());
+    let plot = if let Some(group_by) = group_by {
+        let column = df.column(group_by)?;
+        let binned = column.dtype().is_float()
+            || (column.dtype().is_integer() && column.n_unique()? > SCATTER_COLOR_BINS);
+        if binned {
+            let (data, groups) =
+                df.scatter_plot_data_binned(&x_label, &y_label, group_by, SCATTER_COLOR_BINS)?;
+            ScatterPlot::new(x_label, y_label, data)?
+                .with_groups(groups)
+                .with_gradient()
+        } else {
+            let (data, groups) = df.scatter_plot_data_grouped(&x_label, &y_label, group_by)?;
+            ScatterPlot::new(x_label, y_label, data)?.with_groups(groups)
+        }
+        .with_color_by(group_by.to_owned())
+    } else {
+        let data = df.scatter_plot_data(&x_label, &y_label)?;
+        ScatterPlot::new(x_label, y_label, data)?
+    };
+    Ok(match x_time {
+        Some(seconds_per_unit) => plot.with_time_x(seconds_per_unit),
+        None => plot,
+    })
+}
+
+/// Histogram of `col`, with `DEFAULT_BUCKET_COUNT` buckets for numeric columns
+/// and a bar per value otherwise unless `buckets` is given.
+fn histogram_plot(df: &DataFrame, col: &str, buckets: Option<usize>) -> AppResult<HistogramPlot> {
+    let buckets = match buckets {
+        Some(buckets) => buckets,
+        None if df.column(col)?.dtype().is_numeric() => DEFAULT_BUCKET_COUNT,
+        None => 0,
+    };
+    Ok(HistogramPlot::new(df.histogram_plot_data(col, buckets)?))
+}
+
+fn bar_plot(df: &DataFrame, col: &str) -> AppResult<HistogramPlot> {
+    Ok(HistogramPlot::new(df.bar_plot_data(col)?).with_title(format!("Value Counts of {col}")))
+}
+
 #[derive(Debug)]
 pub enum Modal {
     Sheet(Sheet),
     SearchBar(SearchBar),
+    FilterBar(FilterBar),
+    SubstituteBar(SubstituteBar),
     DataFrameInfo(DataFrameInfo),
     ScatterPlot(ScatterPlot),
     HistogramPlot(HistogramPlot),
+    CorrelationPlot(CorrelationPlot),
     InlineQueryPicker(InlineQueryPicker),
     GoToLine(GoToLine),
+    GoToColumn(GoToColumn),
     Exporter(Exporter),
     HistogramBuilder(HistogramBuilder),
     ScatterPlotBuilder(ScatterPlotBuilder),
     TableRegisterer(TableRegisterer),
     ColumnCaster(ColumnCaster),
+    TransformPreview(TransformPreview),
+    ColumnVisibility(ColumnVisibility),
+    ColumnStats(ColumnStats),
+    CellEditor(CellEditor),
+    Marks(Marks),
+    HexViewer(HexViewer),
+    ColumnInfo(ColumnInfo),
 }
 
 impl Modal {
@@ -551,16 +2354,27 @@ impl Modal {
         match self {
             Modal::Sheet(sheet) => sheet,
             Modal::SearchBar(search_bar) => search_bar,
+            Modal::FilterBar(filter_bar) => filter_bar,
+            Modal::SubstituteBar(substitute_bar) => substitute_bar,
             Modal::DataFrameInfo(data_frame_info) => data_frame_info,
             Modal::ScatterPlot(scatter_plot_state) => scatter_plot_state,
+            Modal::CorrelationPlot(correlation_plot) => correlation_plot,
             Modal::HistogramPlot(histogram_plot_state) => histogram_plot_state,
             Modal::InlineQueryPicker(query_picker) => query_picker,
             Modal::GoToLine(go_to_line) => go_to_line,
+            Modal::GoToColumn(go_to_column) => go_to_column,
             Modal::Exporter(exporter) => exporter,
             Modal::HistogramBuilder(histogram_builder) => histogram_builder,
             Modal::ScatterPlotBuilder(scatter_plot_builder) => scatter_plot_builder,
             Modal::TableRegisterer(table_registerer) => table_registerer,
             Modal::ColumnCaster(column_caster) => column_caster,
+            Modal::TransformPreview(preview) => preview,
+            Modal::ColumnVisibility(column_visibility) => column_visibility,
+            Modal::ColumnStats(column_stats) => column_stats,
+            Modal::CellEditor(cell_editor) => cell_editor,
+            Modal::Marks(marks) => marks,
+            Modal::HexViewer(hex_viewer) => hex_viewer,
+            Modal::ColumnInfo(column_info) => column_info,
         }
     }
 }
@@ -573,6 +2387,24 @@ pub enum TableDescription {
     Order(String),
     Select(String),
     Cast(String),
+    Rename(String),
+    Drop(String),
+    FillNull(String),
+    Dedup(String),
+    DropNulls(String),
+    Explode(String),
+    Unnest(String),
+    Sample(String),
+    Slice(String),
+    Aggregate(String),
+    Frequency(String),
+    Describe(String),
+    Transpose(String),
+    Join(String),
+    Concat(String),
+    Diff(String),
+    Split(String),
+    Replace(String),
     Search(String),
     FuzzySearch(String),
 }
@@ -585,6 +2417,24 @@ impl TableDescription {
             TableDescription::Order(_) => "Order",
             TableDescription::Select(_) => "Select",
             TableDescription::Cast(_) => "Cast",
+            TableDescription::Rename(_) => "Rename",
+            TableDescription::Drop(_) => "Drop",
+            TableDescription::FillNull(_) => "Fill Null",
+            TableDescription::Dedup(_) => "Dedup",
+            TableDescription::DropNulls(_) => "Drop Nulls",
+            TableDescription::Explode(_) => "Explode",
+            TableDescription::Unnest(_) => "Unnest",
+            TableDescription::Sample(_) => "Sample",
+            TableDescription::Slice(_) => "Slice",
+            TableDescription::Aggregate(_) => "Aggregate",
+            TableDescription::Frequency(_) => "Frequency",
+            TableDescription::Describe(_) => "Describe",
+            TableDescription::Transpose(_) => "Transpose",
+            TableDescription::Join(_) => "Join",
+            TableDescription::Concat(_) => "Concat",
+            TableDescription::Diff(_) => "Diff",
+            TableDescription::Split(_) => "Split",
+            TableDescription::Replace(_) => "Replace",
             TableDescription::Search(_) => "Search",
             TableDescription::FuzzySearch(_) => "Fuzzy Search",
         }
@@ -597,6 +2447,24 @@ impl TableDescription {
             | TableDescription::Order(desc)
             | TableDescription::Select(desc)
             | TableDescription::Cast(desc)
+            | TableDescription::Rename(desc)
+            | TableDescription::Drop(desc)
+            | TableDescription::FillNull(desc)
+            | TableDescription::Dedup(desc)
+            | TableDescription::DropNulls(desc)
+            | TableDescription::Explode(desc)
+            | TableDescription::Unnest(desc)
+            | TableDescription::Sample(desc)
+            | TableDescription::Slice(desc)
+            | TableDescription::Aggregate(desc)
+            | TableDescription::Frequency(desc)
+            | TableDescription::Describe(desc)
+            | TableDescription::Transpose(desc)
+            | TableDescription::Join(desc)
+            | TableDescription::Concat(desc)
+            | TableDescription::Diff(desc)
+            | TableDescription::Split(desc)
+            | TableDescription::Replace(desc)
             | TableDescription::Search(desc)
             | TableDescription::FuzzySearch(desc) => desc,
         }
@@ -46,6 +46,12 @@ where
             items,
         }
     }
+
+    /// Replaces the item at `idx`, keeping the query and the selection.
+    pub fn set_item(&mut self, idx: usize, item: T) {
+        self.strings[idx] = item.to_string();
+        self.items[idx] = item;
+    }
 }
 
 impl<T> SearchPicker<T> {
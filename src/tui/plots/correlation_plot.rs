@@ -0,0 +1,212 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, Widget},
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    handler::message::Message,
+    misc::{config::theme, transform::Correlation},
+    tui::{
+        component::Component,
+        table::heat_color,
+        tag_line::{Tag, TagLine},
+        widgets::block::Block,
+    },
+};
+
+/// Width of a cell of the matrix.
+const CELL_WIDTH: usize = 7;
+/// Widest the row labels get.
+const LABEL_WIDTH: usize = 16;
+
+/// Correlation matrix drawn as a heatmap, with a cursor reading exact values.
+#[derive(Debug)]
+pub struct CorrelationPlot {
+    correlation: Correlation,
+    /// Row and column of the cursor.
+    cursor: (usize, usize),
+    /// First row and column in view.
+    offset: (usize, usize),
+}
+
+impl CorrelationPlot {
+    pub fn new(correlation: Correlation) -> Self {
+        Self {
+            correlation,
+            cursor: (0, 0),
+            offset: (0, 0),
+        }
+    }
+
+    fn move_cursor(&mut self, rows: isize, cols: isize) {
+        let last = self.correlation.names.len().saturating_sub(1);
+        self.cursor = (
+            self.cursor.0.saturating_add_signed(rows).min(last),
+            self.cursor.1.saturating_add_signed(cols).min(last),
+        );
+    }
+
+    fn value(&self, row: usize, col: usize) -> Option<f64> {
+        self.correlation
+            .matrix
+            .get(row)
+            .and_then(|values| values.get(col))
+            .copied()
+            .flatten()
+    }
+}
+
+/// Keeps `cursor` within the `len` items in view from `offset`.
+fn scrolled(offset: usize, cursor: usize, len: usize) -> usize {
+    offset
+        .min(cursor)
+        .max(cursor.saturating_sub(len.saturating_sub(1)))
+}
+
+/// `text` cut or padded to `width` columns.
+fn fit(text: &str, width: usize) -> String {
+    let mut used = 0;
+    let text = text
+        .chars()
+        .take_while(|c| {
+            used += c.width().unwrap_or(0);
+            used <= width
+        })
+        .collect::<String>();
+    format!("{text:width$}")
+}
+
+impl Component for CorrelationPlot {
+    fn render(
+        &mut self,
+        _area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        _focus_state: crate::tui::component::FocusState,
+    ) {
+        let area = buf.area.inner(Margin::new(7, 3));
+        Widget::render(Clear, area, buf);
+        let (row, col) = self.cursor;
+        let names = &self.correlation.names;
+        let value = self
+            .value(row, col)
+            .map(|value| format!(" {value:.4} "))
+            .unwrap_or(" undefined ".to_owned());
+        let block = Block::default()
+            .title("Correlation")
+            .title_alignment(Alignment::Center)
+            .bottom(
+                TagLine::default()
+                    .mono_color()
+                    .centered()
+                    .tag(Tag::new(
+                        format!(" {} \u{d7} {} ", names[row], names[col]),
+                        value,
+                    ))
+                    .tag(Tag::new(" Move ", " h j k l ")),
+            );
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let label_width = names
+            .iter()
+            .map(|name| name.width())
+            .max()
+            .unwrap_or_default()
+            .min(LABEL_WIDTH)
+            + 1;
+        let rows = (inner.height as usize).saturating_sub(1).max(1);
+        let cols = ((inner.width as usize).saturating_sub(label_width) / CELL_WIDTH).max(1);
+        self.offset = (
+            scrolled(self.offset.0, row, rows),
+            scrolled(self.offset.1, col, cols),
+        );
+        let visible_cols = (self.offset.1..names.len()).take(cols);
+
+        let header = std::iter::once(Span::raw(" ".repeat(label_width)))
+            .chain(visible_cols.clone().map(|idx| {
+                let style = theme().header(idx);
+                let style = if idx == col {
+                    style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+                } else {
+                    style
+                };
+                Span::styled(format!(" {}", fit(&names[idx], CELL_WIDTH - 1)), style)
+            }))
+            .collect::<Vec<_>>();
+        let mut lines = vec![Line::from(header)];
+        lines.extend((self.offset.0..names.len()).take(rows).map(|idx| {
+            let label_style = theme().header(idx);
+            let label_style = if idx == row {
+                label_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            } else {
+                label_style
+            };
+            Line::from(
+                std::iter::once(Span::styled(
+                    fit(&names[idx], label_width - 1) + " ",
+                    label_style,
+                ))
+                .chain(visible_cols.clone().map(|other| {
+                    let style = match self.value(idx, other) {
+                        Some(value) => Style::new()
+                            .fg(Color::White)
+                            .bg(heat_color((value + 1.0) / 2.0)),
+                        None => theme().subtext(),
+                    };
+                    let style = if (idx, other) == self.cursor {
+                        style.add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                    } else {
+                        style
+                    };
+                    let text = self
+                        .value(idx, other)
+                        .map(|value| format!("{value:+.2}"))
+                        .unwrap_or("\u{b7}".to_owned());
+                    Span::styled(format!("{text:^CELL_WIDTH$}"), style)
+                }))
+                .collect::<Vec<_>>(),
+            )
+        }));
+        for (idx, line) in lines.into_iter().take(inner.height as usize).enumerate() {
+            line.render(
+                Rect {
+                    y: inner.y + idx as u16,
+                    height: 1,
+                    ..inner
+                },
+                buf,
+            );
+        }
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        match (event.code, event.modifiers) {
+            (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
+                self.move_cursor(-1, 0);
+                true
+            }
+            (KeyCode::Down, KeyModifiers::NONE) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
+                self.move_cursor(1, 0);
+                true
+            }
+            (KeyCode::Left, KeyModifiers::NONE) | (KeyCode::Char('h'), KeyModifiers::NONE) => {
+                self.move_cursor(0, -1);
+                true
+            }
+            (KeyCode::Right, KeyModifiers::NONE) | (KeyCode::Char('l'), KeyModifiers::NONE) => {
+                self.move_cursor(0, 1);
+                true
+            }
+            (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
+                Message::PaneDismissModal.enqueue();
+                true
+            }
+            (KeyCode::Enter, KeyModifiers::NONE) => true,
+            _ => false,
+        }
+    }
+}
@@ -19,6 +19,7 @@ use crate::{
 
 #[derive(Debug)]
 pub struct HistogramPlot {
+    title: String,
     offset: usize,
     bars: Vec<Bar<'static>>,
     max_value: u64,
@@ -27,12 +28,17 @@ pub struct HistogramPlot {
 impl HistogramPlot {
     pub fn new(data: Vec<(String, u64)>) -> Self {
         Self {
+            title: "Histogram Plot".to_owned(),
             offset: 0,
             max_value: data.iter().map(|(_, v)| *v).max().unwrap_or_default(),
             bars: bars_from_data(data),
         }
     }
 
+    pub fn with_title(self, title: String) -> Self {
+        Self { title, ..self }
+    }
+
     fn scroll_up(&mut self) {
         self.offset = self.offset.saturating_sub(1);
     }
@@ -53,7 +59,7 @@ impl Component for HistogramPlot {
         Widget::render(Clear, area, buf);
         let area = {
             let blk = Block::default()
-                .title("Histogram Plot")
+                .title(self.title.as_str())
                 .title_alignment(Alignment::Center)
                 .bottom(
                     TagLine::default()
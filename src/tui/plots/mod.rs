@@ -1,2 +1,3 @@
+pub mod correlation_plot;
 pub mod histogram_plot;
 pub mod scatter_plot;
@@ -3,6 +3,7 @@ use crossterm::event::{KeyCode, KeyModifiers};
 use itertools::Itertools;
 use ratatui::{
     layout::{Alignment, Constraint, Margin},
+    style::Style,
     symbols::Marker,
     text::Span,
     widgets::{Axis, Chart, Clear, Dataset, GraphType, LegendPosition, Padding, Widget},
@@ -11,10 +12,13 @@ use ratatui::{
 use crate::{
     AppResult,
     handler::message::Message,
-    misc::{config::theme, ragged_vec::RaggedVec},
-    tui::{component::Component, widgets::block::Block},
+    misc::{config::theme, ragged_vec::RaggedVec, time_axis::time_axis},
+    tui::{component::Component, table::heat_color, widgets::block::Block},
 };
 
+/// Columns of the plot per label of a time axis.
+const X_LABEL_SPACING: u16 = 16;
+
 #[derive(Debug)]
 pub struct ScatterPlot {
     data: RaggedVec<(f64, f64)>,
@@ -23,6 +27,12 @@ pub struct ScatterPlot {
     x_label: String,
     y_label: String,
     groups: Option<Vec<String>>,
+    /// Column coloring the points, named in the title.
+    color_by: Option<String>,
+    /// Whether the groups are ranges of values, colored along a gradient.
+    gradient: bool,
+    /// Seconds in a unit of x when it holds dates or datetimes.
+    x_time: Option<f64>,
 }
 
 impl ScatterPlot {
@@ -45,6 +55,9 @@ impl ScatterPlot {
             x_label,
             y_label,
             groups: None,
+            color_by: None,
+            gradient: false,
+            x_time: None,
         })
     }
 
@@ -54,6 +67,49 @@ impl ScatterPlot {
             ..self
         }
     }
+
+    pub fn with_color_by(self, color_by: String) -> Self {
+        Self {
+            color_by: Some(color_by),
+            ..self
+        }
+    }
+
+    /// Colors the groups along a gradient instead of the theme chart colors.
+    pub fn with_gradient(self) -> Self {
+        Self {
+            gradient: true,
+            ..self
+        }
+    }
+
+    /// Plots the points as time series lines over an x axis of dates or
+    /// datetimes counted in units of `seconds_per_unit`.
+    pub fn with_time_x(self, seconds_per_unit: f64) -> Self {
+        let mut data = RaggedVec::new();
+        for points in self.data.iter() {
+            data.push(points.iter().copied().sorted_by(|a, b| a.0.total_cmp(&b.0)));
+        }
+        Self {
+            data,
+            x_time: Some(seconds_per_unit),
+            ..self
+        }
+    }
+
+    fn style(&self, idx: usize) -> Style {
+        if self.gradient {
+            let last = self
+                .groups
+                .as_ref()
+                .map_or(1, Vec::len)
+                .saturating_sub(1)
+                .max(1);
+            Style::new().fg(heat_color(idx as f64 / last as f64)).bold()
+        } else {
+            theme().graph(idx)
+        }
+    }
 }
 
 impl Component for ScatterPlot {
@@ -72,8 +128,12 @@ impl Component for ScatterPlot {
             .map(|(i, v)| {
                 let ds = Dataset::default()
                     .marker(Marker::Dot)
-                    .graph_type(GraphType::Scatter)
-                    .style(theme().graph(i))
+                    .graph_type(if self.x_time.is_some() {
+                        GraphType::Line
+                    } else {
+                        GraphType::Scatter
+                    })
+                    .style(self.style(i))
                     .data(v);
                 if let Some(g) = &self.groups {
                     ds.name(g[i].as_str())
@@ -83,15 +143,37 @@ impl Component for ScatterPlot {
             })
             .collect_vec();
 
+        let title = if self.x_time.is_some() {
+            "Time Series"
+        } else {
+            "Scatter Plot"
+        };
+        let title = match &self.color_by {
+            Some(color_by) => format!("{title} (color: {color_by})"),
+            None => title.to_owned(),
+        };
+        let (x_bounds, x_labels) = match self.x_time {
+            Some(seconds_per_unit) => time_axis(
+                self.x_bounds[0],
+                self.x_bounds[1],
+                seconds_per_unit,
+                (area.width / X_LABEL_SPACING).clamp(2, 8) as usize,
+            ),
+            None => (
+                self.x_bounds,
+                self.x_bounds.map(|f| format!("{f:.2}")).to_vec(),
+            ),
+        };
         let chart = Chart::new(ds)
             .x_axis(
                 Axis::default()
                     .title(Span::styled(&self.x_label, theme().text()))
-                    .bounds(self.x_bounds)
+                    .bounds(x_bounds)
                     .style(theme().text())
                     .labels(
-                        self.x_bounds
-                            .map(|f| Span::styled(format!("{f:.2}"), theme().text())),
+                        x_labels
+                            .into_iter()
+                            .map(|label| Span::styled(label, theme().text())),
                     ),
             )
             .y_axis(
@@ -107,7 +189,7 @@ impl Component for ScatterPlot {
             .style(theme().text())
             .block(
                 Block::default()
-                    .title("Scatter Plot")
+                    .title(title)
                     .title_alignment(Alignment::Center)
                     .padding(Padding::new(1, 2, 0, 0))
                     .into_widget(),
@@ -0,0 +1,75 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Flex, Layout},
+    widgets::{Clear, Widget},
+};
+
+use crate::{
+    handler::message::Message,
+    tui::{
+        component::Component,
+        widgets::{block::Block, input::Input},
+    },
+};
+
+/// Edits the value of a single cell; `Enter` writes it into the frame.
+#[derive(Debug)]
+pub struct CellEditor {
+    row: usize,
+    column: String,
+    input: Input,
+}
+
+impl CellEditor {
+    pub fn new(row: usize, column: String, value: String) -> Self {
+        Self {
+            row,
+            column,
+            input: Input::default().with_value(value),
+        }
+    }
+}
+
+impl Component for CellEditor {
+    fn render(
+        &mut self,
+        _area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        focus_state: crate::tui::component::FocusState,
+    ) {
+        let [area] = Layout::horizontal([Constraint::Length(64)])
+            .flex(Flex::Center)
+            .areas(buf.area);
+        let [_, area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Length(3)]).areas(area);
+        Clear.render(area, buf);
+        let area = {
+            let block =
+                Block::default().title(format!("Edit '{}' at row {}", self.column, self.row + 1));
+            let inner = block.inner(area);
+            block.render(area, buf);
+            inner
+        };
+        self.input.render(area, buf, focus_state);
+    }
+
+    fn handle(&mut self, event: KeyEvent) -> bool {
+        self.input.handle(event)
+            || match (event.code, event.modifiers) {
+                (KeyCode::Enter, KeyModifiers::NONE) => {
+                    Message::PaneEditCell(
+                        self.row,
+                        self.column.clone(),
+                        self.input.value().to_owned(),
+                    )
+                    .enqueue();
+                    true
+                }
+                (KeyCode::Esc, KeyModifiers::NONE) => {
+                    Message::PaneDismissModal.enqueue();
+                    true
+                }
+                _ => false,
+            }
+    }
+}
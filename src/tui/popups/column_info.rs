@@ -0,0 +1,100 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use polars::prelude::Column;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Widget, Wrap},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    AppResult,
+    handler::message::Message,
+    misc::config::theme,
+    tui::{component::Component, widgets::block::Block},
+};
+
+/// Width of the labels in front of the values.
+const LABEL_WIDTH: usize = 10;
+
+/// Full name, data type, null count, and distinct count of a column.
+#[derive(Debug)]
+pub struct ColumnInfo {
+    name: String,
+    dtype: String,
+    nulls: usize,
+    distinct: usize,
+}
+
+impl ColumnInfo {
+    pub fn new(column: &Column) -> AppResult<Self> {
+        Ok(Self {
+            name: column.name().to_string(),
+            dtype: column.dtype().to_string(),
+            nulls: column.null_count(),
+            distinct: column.n_unique()?,
+        })
+    }
+}
+
+impl Component for ColumnInfo {
+    fn render(
+        &mut self,
+        _area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        _focus_state: crate::tui::component::FocusState,
+    ) {
+        let lines = [
+            ("Name", self.name.clone()),
+            ("Type", self.dtype.clone()),
+            ("Nulls", self.nulls.to_string()),
+            ("Distinct", self.distinct.to_string()),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (label, value))| {
+            Line::from(vec![
+                Span::styled(format!("{label:LABEL_WIDTH$}"), theme().header(idx)),
+                Span::styled(value, theme().text()),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+        let max_width = buf.area.width.saturating_sub(4).max(1);
+        let width = (LABEL_WIDTH + self.name.width().max(self.dtype.width()) + 2)
+            .max(32)
+            .min(max_width as usize) as u16;
+        let paragraph = Paragraph::new(lines)
+            .style(theme().text())
+            .wrap(Wrap { trim: false });
+        let height = paragraph.line_count(width.saturating_sub(2)) as u16 + 2;
+
+        let [area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(buf.area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        Clear.render(area, buf);
+        paragraph
+            .block(
+                Block::default()
+                    .title("Column")
+                    .title_alignment(Alignment::Center)
+                    .into_widget(),
+            )
+            .render(area, buf);
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        match (event.code, event.modifiers) {
+            (KeyCode::Esc, KeyModifiers::NONE)
+            | (KeyCode::Char('q'), KeyModifiers::NONE)
+            | (KeyCode::Char('I'), KeyModifiers::SHIFT) => {
+                Message::PaneDismissModal.enqueue();
+                true
+            }
+            _ => false,
+        }
+    }
+}
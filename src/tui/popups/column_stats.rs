@@ -0,0 +1,112 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use polars::frame::DataFrame;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout},
+    text::Span,
+    widgets::{Clear, Row, Table, Widget},
+};
+
+use crate::{
+    handler::message::Message,
+    misc::{
+        config::theme,
+        transform::{self, ColumnStats as Stats},
+    },
+    tui::{component::Component, widgets::block::Block},
+};
+
+/// Summary statistics of a column, computed on the first render.
+#[derive(Debug)]
+pub struct ColumnStats {
+    df: DataFrame,
+    name: String,
+    stats: Option<Stats>,
+}
+
+impl ColumnStats {
+    pub fn new(df: DataFrame, name: String) -> Self {
+        Self {
+            df,
+            name,
+            stats: None,
+        }
+    }
+}
+
+impl Component for ColumnStats {
+    fn render(
+        &mut self,
+        _area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        _focus_state: crate::tui::component::FocusState,
+    ) {
+        if self.stats.is_none() {
+            match transform::column_stats(&self.df, &self.name) {
+                Ok(stats) => self.stats = Some(stats),
+                Err(err) => {
+                    Message::PaneDismissModal.enqueue();
+                    Message::AppShowError(err.to_string()).enqueue();
+                    return;
+                }
+            }
+        }
+        let Some(stats) = &self.stats else {
+            return;
+        };
+
+        let total = self.df.height().max(1);
+        let rows = stats
+            .stats
+            .iter()
+            .enumerate()
+            .map(|(idx, (label, value))| {
+                Row::new([
+                    Span::styled(label.as_str(), theme().header(idx)),
+                    Span::styled(value.as_str(), theme().text()),
+                ])
+            })
+            .chain((!stats.top.is_empty()).then(|| {
+                Row::new([Span::styled("Top Values", theme().table_header())]).top_margin(1)
+            }))
+            .chain(stats.top.iter().map(|(value, count)| {
+                Row::new([
+                    Span::styled(value.as_str(), theme().text()),
+                    Span::styled(
+                        format!("{count} ({:.1}%)", *count as f64 * 100.0 / total as f64),
+                        theme().text(),
+                    ),
+                ])
+            }))
+            .collect::<Vec<_>>();
+        let height = rows.len() as u16 + u16::from(!stats.top.is_empty()) + 2;
+
+        let [area] = Layout::horizontal([Constraint::Length(56)])
+            .flex(Flex::Center)
+            .areas(buf.area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        Clear.render(area, buf);
+        Table::new(rows, [Constraint::Length(20), Constraint::Fill(1)])
+            .style(theme().text())
+            .block(
+                Block::default()
+                    .title(self.name.as_str())
+                    .title_alignment(Alignment::Center)
+                    .into_widget(),
+            )
+            .render(area, buf);
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        match (event.code, event.modifiers) {
+            (KeyCode::Esc, KeyModifiers::NONE)
+            | (KeyCode::Char('q'), KeyModifiers::NONE)
+            | (KeyCode::Char('D'), KeyModifiers::SHIFT) => {
+                Message::PaneDismissModal.enqueue();
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt::Display;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{
+    handler::message::Message,
+    misc::transform::ColumnPattern,
+    tui::{component::Component, pickers::search_picker::SearchPicker},
+};
+
+/// Lists the columns with their visibility; `Enter` hides or shows the
+/// selected one.
+#[derive(Debug)]
+pub struct ColumnVisibility {
+    picker: SearchPicker<ColumnEntry>,
+}
+
+impl ColumnVisibility {
+    /// Takes the column names paired with whether they are hidden.
+    pub fn new(columns: impl IntoIterator<Item = (String, bool)>) -> Self {
+        Self {
+            picker: SearchPicker::new(
+                columns
+                    .into_iter()
+                    .map(|(name, hidden)| ColumnEntry { name, hidden })
+                    .collect(),
+            )
+            .with_title("Columns"),
+        }
+    }
+
+    fn toggle(&mut self) {
+        let visible = self
+            .picker
+            .items()
+            .iter()
+            .filter(|entry| !entry.hidden)
+            .count();
+        if let Some(idx) = self.picker.selected()
+            && let Some(entry) = self.picker.selected_item()
+        {
+            let pattern = vec![ColumnPattern::Name(entry.name.clone())];
+            if entry.hidden {
+                Message::PaneUnhideColumns(pattern).enqueue();
+            } else {
+                Message::PaneHideColumns(pattern).enqueue();
+                if visible == 1 {
+                    // the pane refuses to hide the last visible column
+                    return;
+                }
+            }
+            let entry = ColumnEntry {
+                name: entry.name.clone(),
+                hidden: !entry.hidden,
+            };
+            self.picker.set_item(idx, entry);
+        }
+    }
+}
+
+impl Component for ColumnVisibility {
+    fn render(
+        &mut self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        focus_state: crate::tui::component::FocusState,
+    ) {
+        self.picker.render(area, buf, focus_state);
+    }
+
+    fn handle(&mut self, event: KeyEvent) -> bool {
+        match (event.code, event.modifiers) {
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                self.toggle();
+                true
+            }
+            (KeyCode::Esc, KeyModifiers::NONE) => {
+                Message::PaneDismissModal.enqueue();
+                true
+            }
+            _ => self.picker.handle(event),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ColumnEntry {
+    name: String,
+    hidden: bool,
+}
+
+impl Display for ColumnEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mark = if self.hidden { '○' } else { '●' };
+        write!(f, "{mark} {}", self.name)
+    }
+}
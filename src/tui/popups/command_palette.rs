@@ -2,10 +2,10 @@ use std::fmt::Display;
 
 use crossterm::event::{KeyCode, KeyModifiers};
 use strum::IntoEnumIterator;
-use strum_macros::{EnumIter, IntoStaticStr};
+use strum_macros::{EnumIter, EnumString, IntoStaticStr};
 
 use crate::{
-    handler::message::Message,
+    handler::{command, message::Message},
     misc::{config::config, type_ext::UnwrapOrEnqueueError},
     tui::{component::Component, pickers::search_picker::SearchPicker},
 };
@@ -13,12 +13,27 @@ use crate::{
 #[derive(Debug)]
 pub struct CommandPalette {
     picker: SearchPicker<Command>,
+    tab_names: Vec<String>,
 }
 
-impl Default for CommandPalette {
-    fn default() -> Self {
+impl CommandPalette {
+    pub fn new(tab_names: Vec<String>) -> Self {
         Self {
             picker: SearchPicker::new(Command::all()),
+            tab_names,
+        }
+    }
+
+    pub fn with_text(mut self, text: impl AsRef<str>) -> Self {
+        self.picker.set_text(text);
+        self
+    }
+
+    fn complete(&mut self) {
+        if let Some(text) = command::complete_tab_name(self.picker.text(), &self.tab_names) {
+            self.picker.set_text(text);
+        } else if let Some(keyword) = self.picker.selected_item().and_then(|item| item.keyword()) {
+            self.picker.set_text(format!("{keyword} "));
         }
     }
 }
@@ -58,40 +73,36 @@ impl Component for CommandPalette {
         } else {
             match (event.code, event.modifiers) {
                 (KeyCode::Enter, KeyModifiers::NONE) => {
-                    Message::AppDismissOverlay.enqueue();
-                    if let Some(item) = self.picker.selected_item() {
-                        match item {
-                            Command::Cast => Message::PaneShowColumnCaster.enqueue(),
-                            Command::Info => Message::PaneShowTableInfo.enqueue(),
-                            Command::Export => Message::PaneShowExporter.enqueue(),
-                            Command::Filter => Message::PaneShowInlineFilter.enqueue(),
-                            Command::Histogram => Message::PaneShowHistogramBuilder.enqueue(),
-                            Command::Import => Message::AppShowImporter.enqueue(),
-                            Command::Order => Message::PaneShowInlineOrder.enqueue(),
-                            Command::Query => Message::AppShowSqlQuery.enqueue(),
-                            Command::Quit => Message::Quit.enqueue(),
-                            Command::Register => Message::PaneShowTableRegisterer.enqueue(),
-                            Command::ScatterPlot => Message::PaneShowScatterPlotBuilder.enqueue(),
-                            Command::Schema => Message::AppShowSchema.enqueue(),
-                            Command::Select => Message::PaneShowInlineSelect.enqueue(),
-                            Command::Sort => Message::PaneShowInlineOrder.enqueue(),
-                            Command::ThemeSelector => Message::AppShowThemeSelector.enqueue(),
-                            Command::ToggleBorders => {
-                                config().toggle_show_table_borders();
-                                config().store().unwrap_or_enqueue_error();
+                    if self.picker.text().trim() == "!!" {
+                        Message::AppDismissOverlay.enqueue();
+                        Message::AppRepeatCommand(false).enqueue();
+                    } else if let Some(result) = command::parse(self.picker.text()) {
+                        Message::AppDismissOverlay.enqueue();
+                        match result {
+                            Ok(message) => {
+                                message.enqueue();
+                                Message::AppRememberCommand(self.picker.text().trim().to_owned())
+                                    .enqueue();
                             }
-                            Command::ToggleRowNumbers => {
-                                config().toggle_show_table_row_numbers();
-                                config().store().unwrap_or_enqueue_error();
-                            }
-                            Command::FuzzySearch => Message::PaneShowFuzzySearch.enqueue(),
-                            Command::Search => Message::PaneShowSearch.enqueue(),
-                            Command::Edit => Message::PaneEditInExternalEditor.enqueue(),
-                            Command::ReloadConfig => Message::AppReloadConfig.enqueue(),
+                            Err(err) => Message::AppShowError(err.to_string()).enqueue(),
                         }
+                    } else if let Some(keyword) =
+                        self.picker.selected_item().and_then(|item| item.keyword())
+                    {
+                        self.picker.set_text(format!("{keyword} "));
+                    } else if let Some(item) = self.picker.selected_item() {
+                        Message::AppDismissOverlay.enqueue();
+                        item.execute();
+                        Message::AppRememberCommand(item.to_string()).enqueue();
+                    } else {
+                        Message::AppDismissOverlay.enqueue();
                     }
                     true
                 }
+                (KeyCode::Tab, KeyModifiers::NONE) => {
+                    self.complete();
+                    true
+                }
                 (KeyCode::Esc, KeyModifiers::NONE) => {
                     Message::AppDismissOverlay.enqueue();
                     true
@@ -102,35 +113,232 @@ impl Component for CommandPalette {
     }
 }
 
-#[derive(Debug, Clone, Copy, EnumIter, IntoStaticStr)]
-enum Command {
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter, EnumString, IntoStaticStr)]
+#[strum(ascii_case_insensitive)]
+pub enum Command {
+    Aggregate,
+    Bar,
     Cast,
+    Column,
+    Concat,
+    Correlation,
+    CumMax,
+    CumMin,
+    CumSum,
+    Cut,
+    Dedup,
+    Describe,
+    Diff,
+    Drop,
+    DropNulls,
+    Dummies,
+    Duplicate,
     Edit,
+    Explode,
     Export,
+    FillNull,
     Filter,
+    FilterBar,
+    Footer,
+    Frequency,
     FuzzySearch,
+    GoTo,
+    Head,
+    Help,
+    Hide,
     Histogram,
     Import,
     Info,
+    Join,
+    Mutate,
     Order,
+    ParseDates,
+    PercentRank,
+    Plot,
     Query,
     Quit,
+    Rank,
     Register,
+    Reload,
     ReloadConfig,
+    Rename,
+    RenameTab,
+    Replace,
+    Rolling,
+    Sample,
     ScatterPlot,
     Search,
     Schema,
     Select,
+    Show,
+    Slice,
     Sort,
+    Split,
+    Substitute,
+    TabCloseRight,
+    TabOnly,
+    Tail,
+    Theme,
     ThemeSelector,
+    TimeZone,
+    ToggleAutoReload,
     ToggleBorders,
+    ToggleDtypeBadges,
+    ToggleLocalTime,
+    ToggleNullMarker,
     ToggleRowNumbers,
+    ToggleStripes,
+    Transpose,
+    Unnest,
+    Width,
 }
 
 impl Command {
     fn all() -> Vec<Command> {
         Command::iter().collect()
     }
+
+    /// Runs the command; commands expecting arguments do nothing.
+    pub fn execute(&self) {
+        match self {
+            Command::Cast => Message::PaneShowColumnCaster.enqueue(),
+            Command::Dedup => Message::PaneDedup(Vec::new(), Default::default()).enqueue(),
+            Command::DropNulls => Message::PaneDropNulls(Vec::new()).enqueue(),
+            Command::Correlation => Message::PaneShowCorrelation.enqueue(),
+            Command::Describe => Message::PaneDescribe.enqueue(),
+            Command::Duplicate => Message::TabsDuplicate.enqueue(),
+            Command::TabCloseRight => Message::TabsCloseRight.enqueue(),
+            Command::TabOnly => Message::TabsCloseOthers.enqueue(),
+            Command::Info => Message::PaneShowTableInfo.enqueue(),
+            Command::Export => Message::PaneShowExporter.enqueue(),
+            Command::Filter => Message::PaneShowInlineFilter.enqueue(),
+            Command::FilterBar => Message::PaneShowFilterBar.enqueue(),
+            Command::Hide => Message::PaneShowColumnVisibility.enqueue(),
+            Command::Histogram => Message::PaneShowHistogramBuilder.enqueue(),
+            Command::Help => Message::AppShowHelp.enqueue(),
+            Command::Import => Message::AppShowImporter.enqueue(),
+            Command::Aggregate
+            | Command::Bar
+            | Command::Column
+            | Command::Concat
+            | Command::CumMax
+            | Command::CumMin
+            | Command::CumSum
+            | Command::Cut
+            | Command::Diff
+            | Command::Drop
+            | Command::Dummies
+            | Command::Explode
+            | Command::FillNull
+            | Command::Footer
+            | Command::Frequency
+            | Command::GoTo
+            | Command::Head
+            | Command::Join
+            | Command::Mutate
+            | Command::ParseDates
+            | Command::PercentRank
+            | Command::Plot
+            | Command::Rank
+            | Command::Rename
+            | Command::RenameTab
+            | Command::Rolling
+            | Command::Replace
+            | Command::Sample
+            | Command::Slice
+            | Command::Split
+            | Command::Substitute
+            | Command::Tail
+            | Command::Theme
+            | Command::Unnest
+            | Command::Width
+            | Command::TimeZone => (),
+            Command::Order => Message::PaneShowInlineOrder.enqueue(),
+            Command::Query => Message::AppShowSqlQuery.enqueue(),
+            Command::Quit => Message::Quit.enqueue(),
+            Command::Register => Message::PaneShowTableRegisterer.enqueue(),
+            Command::Reload => Message::PaneReload.enqueue(),
+            Command::ScatterPlot => Message::PaneShowScatterPlotBuilder.enqueue(),
+            Command::Schema => Message::AppShowSchema.enqueue(),
+            Command::Select => Message::PaneShowInlineSelect.enqueue(),
+            Command::Show => Message::PaneUnhideColumns(Vec::new()).enqueue(),
+            Command::Sort => Message::PaneShowInlineOrder.enqueue(),
+            Command::ThemeSelector => Message::AppShowThemeSelector.enqueue(),
+            Command::Transpose => Message::PaneTranspose.enqueue(),
+            Command::ToggleAutoReload => Message::PaneToggleAutoReload.enqueue(),
+            Command::ToggleBorders => {
+                config().toggle_show_table_borders();
+                config().store().unwrap_or_enqueue_error();
+            }
+            Command::ToggleDtypeBadges => {
+                config().toggle_show_dtype_badges();
+                config().store().unwrap_or_enqueue_error();
+            }
+            Command::ToggleLocalTime => {
+                config().toggle_show_local_time();
+                config().store().unwrap_or_enqueue_error();
+            }
+            Command::ToggleNullMarker => {
+                config().toggle_show_null_marker();
+                config().store().unwrap_or_enqueue_error();
+            }
+            Command::ToggleRowNumbers => {
+                config().toggle_show_table_row_numbers();
+                config().store().unwrap_or_enqueue_error();
+            }
+            Command::ToggleStripes => {
+                config().toggle_striped_rows();
+                config().store().unwrap_or_enqueue_error();
+            }
+            Command::FuzzySearch => Message::PaneShowFuzzySearch.enqueue(),
+            Command::Search => Message::PaneShowSearch.enqueue(),
+            Command::Edit => Message::PaneEditInExternalEditor.enqueue(),
+            Command::ReloadConfig => Message::AppReloadConfig.enqueue(),
+        }
+    }
+
+    /// Keyword of commands that expect arguments typed after them in the palette.
+    pub fn keyword(&self) -> Option<&'static str> {
+        match self {
+            Command::Aggregate => Some("agg"),
+            Command::Bar => Some("bar"),
+            Command::Column => Some("col"),
+            Command::Concat => Some("concat"),
+            Command::CumMax => Some("cummax"),
+            Command::CumMin => Some("cummin"),
+            Command::CumSum => Some("cumsum"),
+            Command::Cut => Some("cut"),
+            Command::Diff => Some("diff"),
+            Command::Drop => Some("drop"),
+            Command::Dummies => Some("dummies"),
+            Command::Explode => Some("explode"),
+            Command::FillNull => Some("fill-null"),
+            Command::Footer => Some("footer"),
+            Command::Frequency => Some("freq"),
+            Command::GoTo => Some("goto"),
+            Command::Head => Some("head"),
+            Command::Join => Some("join"),
+            Command::Mutate => Some("mutate"),
+            Command::ParseDates => Some("parse-dates"),
+            Command::PercentRank => Some("pct-rank"),
+            Command::Plot => Some("plot"),
+            Command::Rank => Some("rank"),
+            Command::Rename => Some("rename"),
+            Command::RenameTab => Some("rename-tab"),
+            Command::Replace => Some("replace"),
+            Command::Rolling => Some("rolling"),
+            Command::Sample => Some("sample"),
+            Command::Slice => Some("slice"),
+            Command::Split => Some("split"),
+            Command::Substitute => Some("%s"),
+            Command::Tail => Some("tail"),
+            Command::Theme => Some("theme"),
+            Command::TimeZone => Some("tz"),
+            Command::Unnest => Some("unnest"),
+            Command::Width => Some("width"),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Command {
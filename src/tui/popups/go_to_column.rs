@@ -0,0 +1,59 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Flex, Layout},
+    widgets::{Clear, Widget},
+};
+
+use crate::{
+    handler::message::Message,
+    tui::{
+        component::Component,
+        widgets::{block::Block, input::Input},
+    },
+};
+
+/// Scrolls to the column best matching the typed name while typing.
+#[derive(Debug, Default)]
+pub struct GoToColumn {
+    input: Input,
+}
+
+impl Component for GoToColumn {
+    fn render(
+        &mut self,
+        _area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        focus_state: crate::tui::component::FocusState,
+    ) {
+        let [area, _] = Layout::horizontal([Constraint::Length(32), Constraint::Length(1)])
+            .flex(Flex::End)
+            .areas(buf.area);
+        let [_, area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(3)]).areas(area);
+        Clear.render(area, buf);
+        let area = {
+            let block = Block::default().title("Go to Column");
+            let inner = block.inner(area);
+            block.render(area, buf);
+            inner
+        };
+        self.input.render(area, buf, focus_state);
+    }
+
+    fn handle(&mut self, event: KeyEvent) -> bool {
+        if self.input.handle(event) {
+            if !self.input.value().is_empty() {
+                Message::PaneGoToColumn(self.input.value().to_owned()).enqueue();
+            }
+            true
+        } else {
+            match (event.code, event.modifiers) {
+                (KeyCode::Enter, KeyModifiers::NONE) | (KeyCode::Esc, KeyModifiers::NONE) => {
+                    Message::PaneDismissModal.enqueue();
+                    true
+                }
+                _ => false,
+            }
+        }
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
     handler::message::Message,
     tui::{
         component::Component,
+        table::Motion,
         widgets::{
             block::Block,
             input::{Input, InputType},
@@ -64,12 +65,20 @@ impl Component for GoToLine {
     }
     fn handle(&mut self, event: KeyEvent) -> bool {
         if self.input.handle(event) {
-            Message::PaneTableSelect(self.value().saturating_sub(1)).enqueue();
+            Message::PaneGoToLine(self.value()).enqueue();
+            true
+        } else if let Some(message) = counted(&event, self.value()) {
+            // The number typed is a count for the motion instead.
+            Message::PaneDismissModal.enqueue();
+            Message::PaneTableSelect(self.rollback).enqueue();
+            message.enqueue();
             true
         } else {
             match (event.code, event.modifiers) {
-                (KeyCode::Enter, KeyModifiers::NONE) => {
+                (KeyCode::Enter, KeyModifiers::NONE)
+                | (KeyCode::Char('G'), KeyModifiers::SHIFT) => {
                     Message::PaneDismissModal.enqueue();
+                    Message::PaneRecordJump(self.rollback).enqueue();
                     true
                 }
                 (KeyCode::Esc, KeyModifiers::NONE) => {
@@ -82,3 +91,20 @@ impl Component for GoToLine {
         }
     }
 }
+
+/// Message moving `count` times by the motion of the key, if it is one.
+fn counted(event: &KeyEvent, count: usize) -> Option<Message> {
+    if let Some(motion) = Motion::from_key(event) {
+        return Some(Message::PaneTableMove(motion, count));
+    }
+    let count = count.min(isize::MAX as usize) as isize;
+    match (event.code, event.modifiers) {
+        (KeyCode::Char('H'), KeyModifiers::SHIFT) | (KeyCode::Left, KeyModifiers::SHIFT) => {
+            Some(Message::TabsSelectBy(-count))
+        }
+        (KeyCode::Char('L'), KeyModifiers::SHIFT) | (KeyCode::Right, KeyModifiers::SHIFT) => {
+            Some(Message::TabsSelectBy(count))
+        }
+        _ => None,
+    }
+}
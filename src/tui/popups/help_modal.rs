@@ -1,33 +1,103 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::{Alignment, Constraint, Flex, Layout},
     text::{Line, Span},
-    widgets::{Clear, Paragraph, Widget, Wrap},
+    widgets::{Clear, Paragraph, Widget},
 };
+use strum::IntoEnumIterator;
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    misc::config::theme,
+    handler::message::Message,
+    misc::{
+        config::theme,
+        keybindings::{Context, keybindings},
+    },
     tui::{
         component::Component,
+        key_hints::KEY_GROUPS,
+        popups::command_palette::Command,
         tag_line::{Tag, TagLine},
+        utils::Scroll,
         widgets::block::Block,
     },
 };
 
-#[derive(Debug)]
-pub struct Help {}
+/// Width of the key column of the help.
+const KEY_WIDTH: usize = 24;
+
+/// Every key and palette command, generated from the key groups, the
+/// keybindings file, and the commands of the palette.
+#[derive(Debug, Default)]
+pub struct Help {
+    scroll: Scroll,
+}
 
 impl Help {
     pub fn new() -> Self {
-        Self {}
+        Self::default()
     }
-}
 
-impl Default for Help {
-    fn default() -> Self {
-        Self::new()
+    fn lines() -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::raw(""),
+            Line::styled("Tabiew Keyboard Shortcuts", theme().header(0)),
+            Line::raw(""),
+        ];
+        for group in KEY_GROUPS {
+            section(
+                &mut lines,
+                group.title,
+                group
+                    .keys
+                    .iter()
+                    .map(|(key, desc)| (key.to_string(), desc.to_string())),
+            );
+        }
+        for (title, context) in [
+            ("Keybindings File: Table", Context::Table),
+            ("Keybindings File: Schema", Context::Schema),
+            ("Keybindings File: Hex Viewer", Context::HexViewer),
+            ("Keybindings File: Leader", Context::Leader),
+        ] {
+            let keys = keybindings().list(context);
+            if !keys.is_empty() {
+                section(&mut lines, title, keys);
+            }
+        }
+        section(
+            &mut lines,
+            "Palette Commands (after pressing :)",
+            Command::iter().map(|command| {
+                (
+                    command.to_string(),
+                    command
+                        .keyword()
+                        .map(|keyword| format!("{keyword} \u{2026}"))
+                        .unwrap_or_default(),
+                )
+            }),
+        );
+        lines
     }
 }
 
+fn section(
+    lines: &mut Vec<Line<'static>>,
+    title: &str,
+    keys: impl IntoIterator<Item = (String, String)>,
+) {
+    lines.push(Line::styled(title.to_owned(), theme().header(1)));
+    lines.extend(keys.into_iter().map(|(key, desc)| {
+        let pad = KEY_WIDTH.saturating_sub(key.width()).max(1);
+        Line::from(vec![
+            Span::styled(format!("  {key}"), theme().text().bold()),
+            Span::raw(format!("{:pad$}{desc}", "")),
+        ])
+    }));
+    lines.push(Line::raw(""));
+}
+
 impl Component for Help {
     fn render(
         &mut self,
@@ -35,200 +105,63 @@ impl Component for Help {
         buf: &mut ratatui::prelude::Buffer,
         _focus_state: crate::tui::component::FocusState,
     ) {
+        let lines = Self::lines();
         let [area] = Layout::horizontal([Constraint::Length(90)])
             .flex(Flex::Center)
             .areas(buf.area);
-        let [_, area] =
-            Layout::vertical([Constraint::Length(2), Constraint::Length(50)]).areas(area);
+        let [_, area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Max(lines.len() as u16 + 2),
+        ])
+        .areas(area);
 
         Clear.render(area, buf);
 
-        let mut lines = vec![
-            Line::raw(""),
-            Line::from(vec![Span::styled(
-                "Tabiew Keyboard Shortcuts",
-                theme().header(0),
-            )]),
-            Line::raw(""),
-        ];
-
-        // Navigation
-        lines.push(Line::styled("Navigation", theme().header(1)));
-        lines.push(Line::from(vec![
-            Span::styled("  h j k l", theme().text().bold()),
-            Span::raw(" or "),
-            Span::styled("← ↓ ↑ →", theme().text().bold()),
-            Span::raw("     Navigate"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  b", theme().text().bold()),
-            Span::raw(" / "),
-            Span::styled("w", theme().text().bold()),
-            Span::raw("                 Previous / next column"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  Ctrl + u", theme().text().bold()),
-            Span::raw(" / "),
-            Span::styled("Ctrl + d", theme().text().bold()),
-            Span::raw("   Move half page up/down"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  Ctrl + b", theme().text().bold()),
-            Span::raw(" / "),
-            Span::styled("Ctrl + f", theme().text().bold()),
-            Span::raw("   Move full page up/down"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  Home", theme().text().bold()),
-            Span::raw(" or "),
-            Span::styled("g", theme().text().bold()),
-            Span::raw("             Move to first row"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  End", theme().text().bold()),
-            Span::raw(" or "),
-            Span::styled("G", theme().text().bold()),
-            Span::raw("              Move to last row"),
-        ]));
-        lines.push(Line::raw(""));
-
-        // View Actions
-        lines.push(Line::styled("View Actions", theme().header(1)));
-        lines.push(Line::from(vec![
-            Span::styled("  Enter", theme().text().bold()),
-            Span::raw("                  Open sheet (cell detail view)"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  e", theme().text().bold()),
-            Span::raw("                       Toggle Auto-Fit"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  f", theme().text().bold()),
-            Span::raw("                       Toggle Borders"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  I", theme().text().bold()),
-            Span::raw("                       Show DataFrame Info"),
-        ]));
-        lines.push(Line::raw(""));
-
-        // Search
-        lines.push(Line::styled("Search", theme().header(1)));
-        lines.push(Line::from(vec![
-            Span::styled("  /", theme().text().bold()),
-            Span::raw("                       Fuzzy Search"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  ?", theme().text().bold()),
-            Span::raw("                       Exact Search"),
-        ]));
-        lines.push(Line::raw(""));
-
-        // Commands
-        lines.push(Line::styled("Commands", theme().header(1)));
-        lines.push(Line::from(vec![
-            Span::styled("  :", theme().text().bold()),
-            Span::raw("                       Command Palette"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  Ctrl + r", theme().text().bold()),
-            Span::raw("              Reset data frame"),
-        ]));
-        lines.push(Line::raw(""));
-
-        // Tabs
-        lines.push(Line::styled("Tabs", theme().header(1)));
-        lines.push(Line::from(vec![
-            Span::styled("  H", theme().text().bold()),
-            Span::raw(" / "),
-            Span::styled("L", theme().text().bold()),
-            Span::raw(" or "),
-            Span::styled("Shift+←", theme().text().bold()),
-            Span::raw(" / "),
-            Span::styled("Shift+→", theme().text().bold()),
-            Span::raw("   Previous / next tab"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  t", theme().text().bold()),
-            Span::raw("                       Show tab panel"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  1-9", theme().text().bold()),
-            Span::raw("                     Go to tab number"),
-        ]));
-        lines.push(Line::raw(""));
-
-        // Quit
-        lines.push(Line::styled("Quit", theme().header(1)));
-        lines.push(Line::from(vec![
-            Span::styled("  q", theme().text().bold()),
-            Span::raw("                       Close current view/tab"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  Q", theme().text().bold()),
-            Span::raw("                       Quit Application"),
-        ]));
-        lines.push(Line::raw(""));
-
-        // Common Commands
-        lines.push(Line::styled(
-            "Common Commands (after pressing :)",
-            theme().header(1),
-        ));
-        lines.push(Line::from(vec![
-            Span::styled("  Q", theme().text().bold()),
-            Span::raw(" or "),
-            Span::styled("query", theme().text().bold()),
-            Span::raw("           Query data with SQL"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  S", theme().text().bold()),
-            Span::raw(" or "),
-            Span::styled("select", theme().text().bold()),
-            Span::raw("          Select columns"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  F", theme().text().bold()),
-            Span::raw(" or "),
-            Span::styled("filter", theme().text().bold()),
-            Span::raw("          Filter rows"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  O", theme().text().bold()),
-            Span::raw(" or "),
-            Span::styled("order", theme().text().bold()),
-            Span::raw("           Sort data"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  tabn", theme().text().bold()),
-            Span::raw("                  Create new tab with query"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  schema", theme().text().bold()),
-            Span::raw("                Show schema browser"),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("  reset", theme().text().bold()),
-            Span::raw("                 Reset table to original data"),
-        ]));
-
-        let pg = Paragraph::new(lines)
+        let block = Block::default()
+            .title("Help")
+            .bottom(
+                TagLine::new()
+                    .mono_color()
+                    .centered()
+                    .tag(Tag::new(" Scroll ", " j | k "))
+                    .tag(Tag::new(" Close ", " ESC | q ")),
+            )
+            .title_alignment(Alignment::Center);
+        self.scroll.adjust(lines.len(), block.inner(area).height);
+        Paragraph::new(lines)
             .style(theme().text())
             .alignment(Alignment::Left)
-            .wrap(Wrap { trim: true })
-            .block(
-                Block::default()
-                    .title("Help")
-                    .bottom(
-                        TagLine::new()
-                            .mono_color()
-                            .centered()
-                            .tag(Tag::new(" Close ", " ESC | q ")),
-                    )
-                    .title_alignment(Alignment::Center)
-                    .into_widget(),
-            );
+            .scroll((self.scroll.val_u16(), 0))
+            .block(block.into_widget())
+            .render(area, buf);
+    }
 
-        pg.render(area, buf);
+    fn handle(&mut self, event: KeyEvent) -> bool {
+        match (event.code, event.modifiers) {
+            (KeyCode::Down, KeyModifiers::NONE) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
+                self.scroll.down();
+                true
+            }
+            (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
+                self.scroll.up();
+                true
+            }
+            (KeyCode::PageDown, KeyModifiers::NONE)
+            | (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
+                (0..10).for_each(|_| self.scroll.down());
+                true
+            }
+            (KeyCode::PageUp, KeyModifiers::NONE) | (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+                (0..10).for_each(|_| self.scroll.up());
+                true
+            }
+            (KeyCode::Esc, KeyModifiers::NONE)
+            | (KeyCode::Char('q'), KeyModifiers::NONE)
+            | (KeyCode::F(1), KeyModifiers::NONE) => {
+                Message::AppDismissOverlay.enqueue();
+                true
+            }
+            _ => false,
+        }
     }
 }
@@ -0,0 +1,323 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Widget},
+};
+
+use crate::{
+    handler::message::Message,
+    misc::{
+        config::theme,
+        keybindings::{Context, keybindings},
+    },
+    tui::{
+        component::{Component, FocusState},
+        tag_line::{Tag, TagLine},
+        widgets::{block::Block, input::Input},
+    },
+};
+
+/// Bytes shown on each line of the viewer.
+const BYTES_PER_LINE: usize = 16;
+
+/// Hex dump of a binary value with byte offsets, an ASCII pane, and search.
+#[derive(Debug)]
+pub struct HexViewer {
+    title: String,
+    bytes: Vec<u8>,
+    /// First line in view.
+    offset: usize,
+    /// Lines fitting in the view on the last render.
+    height: usize,
+    search: Option<HexSearch>,
+}
+
+#[derive(Debug, Default)]
+struct HexSearch {
+    input: Input,
+    editing: bool,
+    /// Length of the searched byte sequence.
+    len: usize,
+    /// Byte offsets of the matches.
+    matches: Vec<usize>,
+    current: usize,
+}
+
+impl HexViewer {
+    pub fn new(title: String, bytes: Vec<u8>) -> Self {
+        Self {
+            title,
+            bytes,
+            offset: 0,
+            height: 0,
+            search: None,
+        }
+    }
+
+    fn line_count(&self) -> usize {
+        self.bytes.len().div_ceil(BYTES_PER_LINE).max(1)
+    }
+
+    fn scroll_to(&mut self, line: usize) {
+        self.offset = line.min(self.line_count().saturating_sub(self.height.max(1)));
+    }
+
+    fn scroll_by(&mut self, lines: isize) {
+        self.scroll_to(self.offset.saturating_add_signed(lines));
+    }
+
+    /// Finds the searched bytes, given as hex pairs (`0xdeadbeef`, `x:de ad`) or text.
+    fn find(&mut self) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        let needle = parse_needle(search.input.value());
+        search.len = needle.len();
+        search.current = 0;
+        search.matches = if needle.is_empty() {
+            Vec::new()
+        } else {
+            self.bytes
+                .windows(needle.len())
+                .enumerate()
+                .filter(|(_, window)| *window == needle.as_slice())
+                .map(|(idx, _)| idx)
+                .collect()
+        };
+        self.reveal_match();
+    }
+
+    fn select_match(&mut self, forward: bool) {
+        if let Some(search) = self.search.as_mut()
+            && !search.matches.is_empty()
+        {
+            let len = search.matches.len();
+            search.current = if forward {
+                (search.current + 1) % len
+            } else {
+                (search.current + len - 1) % len
+            };
+        }
+        self.reveal_match();
+    }
+
+    fn reveal_match(&mut self) {
+        let line = self
+            .search
+            .as_ref()
+            .and_then(|search| search.matches.get(search.current))
+            .map(|pos| pos / BYTES_PER_LINE);
+        if let Some(line) = line
+            && !(self.offset..self.offset + self.height.max(1)).contains(&line)
+        {
+            self.scroll_to(line.saturating_sub(self.height / 2));
+        }
+    }
+
+    /// Style of the byte at `pos`, emphasizing search matches.
+    fn byte_style(&self, pos: usize) -> Style {
+        let Some(search) = &self.search else {
+            return theme().text();
+        };
+        let start = search
+            .matches
+            .partition_point(|start| start + search.len <= pos);
+        match search.matches.get(start) {
+            Some(start) if *start <= pos => {
+                if search.matches.get(search.current) == Some(start) {
+                    theme().text_highlighted().add_modifier(Modifier::REVERSED)
+                } else {
+                    theme().text_highlighted()
+                }
+            }
+            _ => theme().text(),
+        }
+    }
+
+    fn line(&self, line: usize, offset_width: usize) -> Line<'_> {
+        let start = line * BYTES_PER_LINE;
+        let end = (start + BYTES_PER_LINE).min(self.bytes.len());
+        let mut spans = vec![Span::styled(
+            format!("{start:0offset_width$X}  "),
+            theme().subtext(),
+        )];
+        for pos in start..start + BYTES_PER_LINE {
+            let gap = if pos % BYTES_PER_LINE == BYTES_PER_LINE / 2 {
+                "  "
+            } else {
+                " "
+            };
+            spans.push(Span::raw(gap));
+            spans.push(match self.bytes.get(pos) {
+                Some(byte) => Span::styled(format!("{byte:02X}"), self.byte_style(pos)),
+                None => Span::raw("  "),
+            });
+        }
+        spans.push(Span::styled("  │", theme().subtext()));
+        spans.extend((start..end).map(|pos| {
+            let byte = self.bytes[pos];
+            let c = if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            };
+            Span::styled(c.to_string(), self.byte_style(pos))
+        }));
+        Line::from(spans)
+    }
+}
+
+/// Bytes of a search query: the hex pairs after a `0x` or `x:` prefix, its text
+/// otherwise. Hex that does not make whole bytes matches nothing.
+fn parse_needle(query: &str) -> Vec<u8> {
+    let Some(hex) = query
+        .strip_prefix("0x")
+        .or_else(|| query.strip_prefix("x:"))
+    else {
+        return query.as_bytes().to_vec();
+    };
+    let hex = hex.split_whitespace().collect::<String>();
+    if hex.len() % 2 == 0 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        (0..hex.len())
+            .step_by(2)
+            .filter_map(|idx| u8::from_str_radix(&hex[idx..idx + 2], 16).ok())
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+impl Component for HexViewer {
+    fn render(&mut self, _area: Rect, buf: &mut ratatui::prelude::Buffer, focus_state: FocusState) {
+        let offset_width = format!("{:X}", self.bytes.len()).len().max(8);
+        let width = offset_width as u16 + 2 + BYTES_PER_LINE as u16 * 3 + 4 + 16 + 2;
+        let [area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(buf.area);
+        let area = area.inner(Margin::new(0, 2));
+        Clear.render(area, buf);
+
+        let bottom = match &self.search {
+            Some(search) if !search.editing => {
+                TagLine::new().mono_color().centered().tag(Tag::new(
+                    format!(" /{} ", search.input.value()),
+                    if search.matches.is_empty() {
+                        " No Match ".to_owned()
+                    } else {
+                        format!(" {} / {} ", search.current + 1, search.matches.len())
+                    },
+                ))
+            }
+            _ => TagLine::new()
+                .mono_color()
+                .centered()
+                .tag(Tag::new(" Scroll ", " j | k "))
+                .tag(Tag::new(" Search ", " / "))
+                .tag(Tag::new(" Next / Previous ", " n | N "))
+                .tag(Tag::new(" Close ", " Esc | q ")),
+        };
+        let block = Block::default()
+            .title(self.title.as_str())
+            .bottom(bottom)
+            .into_widget();
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let inner = match self.search.as_mut().filter(|search| search.editing) {
+            Some(search) => {
+                let [input_area, inner] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner);
+                let [prompt_area, input_area] =
+                    Layout::horizontal([Constraint::Length(1), Constraint::Fill(1)])
+                        .areas(input_area);
+                Span::styled("/", theme().subtext()).render(prompt_area, buf);
+                search.input.render(input_area, buf, focus_state);
+                inner
+            }
+            None => inner,
+        };
+
+        self.height = inner.height as usize;
+        self.scroll_to(self.offset);
+        let lines = (self.offset..self.line_count())
+            .take(self.height)
+            .map(|line| self.line(line, offset_width))
+            .collect::<Vec<_>>();
+        Paragraph::new(lines)
+            .style(theme().text())
+            .render(inner, buf);
+    }
+
+    fn handle(&mut self, event: KeyEvent) -> bool {
+        if let Some(search) = self.search.as_mut().filter(|search| search.editing) {
+            match (event.code, event.modifiers) {
+                (KeyCode::Enter, KeyModifiers::NONE) => search.editing = false,
+                (KeyCode::Esc, KeyModifiers::NONE) => self.search = None,
+                _ => {
+                    if search.input.handle(event) {
+                        self.find();
+                    }
+                }
+            }
+            return true;
+        }
+        if let Some(action) = keybindings().get(Context::HexViewer, &event) {
+            action.run();
+            return true;
+        }
+        match (event.code, event.modifiers) {
+            (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
+                self.scroll_by(-1)
+            }
+            (KeyCode::Down, KeyModifiers::NONE) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
+                self.scroll_by(1)
+            }
+            (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+                self.scroll_by(-(self.height as isize / 2))
+            }
+            (KeyCode::Char('d'), KeyModifiers::CONTROL) => self.scroll_by(self.height as isize / 2),
+            (KeyCode::PageUp, KeyModifiers::NONE) | (KeyCode::Char('b'), KeyModifiers::CONTROL) => {
+                self.scroll_by(-(self.height as isize))
+            }
+            (KeyCode::PageDown, KeyModifiers::NONE)
+            | (KeyCode::Char('f'), KeyModifiers::CONTROL) => self.scroll_by(self.height as isize),
+            (KeyCode::Home, KeyModifiers::NONE) | (KeyCode::Char('g'), KeyModifiers::NONE) => {
+                self.scroll_to(0)
+            }
+            (KeyCode::End, KeyModifiers::NONE) | (KeyCode::Char('G'), KeyModifiers::SHIFT) => {
+                self.scroll_to(usize::MAX)
+            }
+            (KeyCode::Char('/'), KeyModifiers::NONE) => {
+                self.search = Some(HexSearch {
+                    editing: true,
+                    ..Default::default()
+                })
+            }
+            (KeyCode::Char('n'), KeyModifiers::NONE) => self.select_match(true),
+            (KeyCode::Char('N'), KeyModifiers::SHIFT) => self.select_match(false),
+            (KeyCode::Esc, KeyModifiers::NONE) if self.search.is_some() => self.search = None,
+            (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
+                Message::PaneDismissModal.enqueue()
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_needle() {
+        assert_eq!(parse_needle("0xdeadbeef"), [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(parse_needle("x:ff d8"), [0xff, 0xd8]);
+        assert_eq!(parse_needle("cafe"), b"cafe");
+        assert_eq!(parse_needle("0x"), b"");
+        assert_eq!(parse_needle("0xabc"), b"");
+        assert_eq!(parse_needle("x:zz"), b"");
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
     },
 };
 
-const DEFAULT_BUCKET_COUNT: &str = "24";
+pub const DEFAULT_BUCKET_COUNT: usize = 24;
 
 pub type HistogramBuilder = MultiStepOverlay<State>;
 
@@ -59,7 +59,7 @@ impl OverlayStep for State {
                             picker: TextPicker::default()
                                 .with_title("Buckets")
                                 .with_input_type(InputType::Numeric)
-                                .with_value(DEFAULT_BUCKET_COUNT.to_owned()),
+                                .with_value(DEFAULT_BUCKET_COUNT.to_string()),
                         }
                     }
                 } else {
@@ -5,7 +5,8 @@ use crate::{
     io::{DataSource, reader::ReaderSource},
     misc::{
         remote_load,
-        sql::{TableSource, sql},
+        sql::{TableReader, TableSource, sql},
+        type_inferer::TypeInferer,
     },
 };
 
@@ -52,8 +53,13 @@ fn dismiss_overlay_and_load_data_frame(source: DataSource, reader: impl remote_l
                 }
             };
             let count = frames.len();
-            for (name, df) in frames {
-                let name = sql().register(&name, df.clone(), TableSource::Stdin);
+            let reader: Arc<dyn remote_load::Reader> = Arc::new(reader);
+            for (frame, df) in frames {
+                let name = sql().register(&frame, df.clone(), TableSource::File(path_buf.clone()));
+                sql().set_reader(
+                    &name,
+                    TableReader::new(reader.clone(), frame, TypeInferer::default()),
+                );
                 Message::TabsAddNamePane(df, name).enqueue();
             }
             Message::AppShowToast(format!(
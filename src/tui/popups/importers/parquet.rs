@@ -23,7 +23,10 @@ impl OverlayStep for State {
         match self {
             State::PickSource { picker } => match picker.value() {
                 Some(ImportSource::Stdin) => {
-                    dismiss_overlay_and_load_data_frame(DataSource::Stdin, ParquetToDataFrame);
+                    dismiss_overlay_and_load_data_frame(
+                        DataSource::Stdin,
+                        ParquetToDataFrame::default(),
+                    );
                     State::PickSource { picker }
                 }
                 Some(ImportSource::File) => State::PickPath {
@@ -37,14 +40,14 @@ impl OverlayStep for State {
             State::PickPath { picker } => {
                 dismiss_overlay_and_load_data_frame(
                     DataSource::File(picker.path()),
-                    ParquetToDataFrame,
+                    ParquetToDataFrame::default(),
                 );
                 Default::default()
             }
             State::PickUrl { picker } => {
                 dismiss_overlay_and_load_data_frame(
                     DataSource::Url(picker.url()),
-                    ParquetToDataFrame,
+                    ParquetToDataFrame::default(),
                 );
                 Default::default()
             }
@@ -3,11 +3,9 @@ use polars::frame::DataFrame;
 
 use crate::{
     handler::message::Message,
-    misc::sql::sql,
     sql_completion::{self, SqlSuggestion},
     tui::{
         component::Component,
-        pane::TableDescription,
         pickers::text_picker_with_suggestion::{Provider, TextPickerWithSuggestion},
     },
 };
@@ -15,7 +13,6 @@ use crate::{
 #[derive(Debug)]
 pub struct InlineQueryPicker {
     picker: TextPickerWithSuggestion<InlineQueryProvider>,
-    dataframe: DataFrame,
     query_type: QueryType,
 }
 
@@ -23,65 +20,19 @@ impl InlineQueryPicker {
     pub fn new(dataframe: DataFrame, query_type: QueryType) -> Self {
         let all_columns = sql_completion::collect_all_columns(Some(&dataframe));
         let provider = InlineQueryProvider {
-            dataframe: dataframe.clone(),
+            dataframe,
             query_type,
             all_columns,
         };
         Self {
             picker: TextPickerWithSuggestion::new(query_type.title(), provider),
-            dataframe,
             query_type,
         }
     }
 
     fn submit(&self) {
-        let value = self.picker.value();
-        let result = match self.query_type {
-            QueryType::Select => {
-                sql().execute(&format!("SELECT {value} FROM _"), self.dataframe.clone())
-            }
-            QueryType::Filter => sql().execute(
-                &format!("SELECT * FROM _ where {value}"),
-                self.dataframe.clone(),
-            ),
-            QueryType::Order => sql().execute(
-                &format!("SELECT * FROM _ ORDER BY {value}"),
-                self.dataframe.clone(),
-            ),
-        };
-        match (result, self.query_type) {
-            (Ok(result_dataframe), QueryType::Select) => {
-                Message::PaneDismissModal.enqueue();
-                Message::PanePushDataFrame(
-                    result_dataframe,
-                    TableDescription::Select(value.to_owned()),
-                )
-                .enqueue();
-                Message::AppShowToast(format!("Column selection '{value}' occurred")).enqueue();
-            }
-            (Ok(result_dataframe), QueryType::Order) => {
-                Message::PaneDismissModal.enqueue();
-                Message::PanePushDataFrame(
-                    result_dataframe,
-                    TableDescription::Order(value.to_owned()),
-                )
-                .enqueue();
-                Message::AppShowToast(format!("Data frame ordered by '{value}'")).enqueue();
-            }
-            (Ok(result_dataframe), QueryType::Filter) => {
-                Message::PaneDismissModal.enqueue();
-                Message::PanePushDataFrame(
-                    result_dataframe,
-                    TableDescription::Filter(value.to_owned()),
-                )
-                .enqueue();
-                Message::AppShowToast(format!("Filter '{value}' applied")).enqueue();
-            }
-            (Err(error), _) => {
-                Message::PaneDismissModal.enqueue();
-                Message::AppShowError(error.to_string()).enqueue();
-            }
-        }
+        Message::PaneDismissModal.enqueue();
+        Message::PaneInlineQuery(self.query_type, self.picker.value().to_owned()).enqueue();
     }
 }
 
@@ -156,6 +107,15 @@ impl QueryType {
         }
     }
 
+    /// Query running the `value` typed into the prompt on the current frame.
+    pub fn query(&self, value: &str) -> String {
+        match self {
+            QueryType::Select => format!("SELECT {value} FROM _"),
+            QueryType::Filter => format!("SELECT * FROM _ where {value}"),
+            QueryType::Order => format!("SELECT * FROM _ ORDER BY {value}"),
+        }
+    }
+
     /// SQL fragment prepended to the user's input so that the tokenizer sees
     /// the full clause context (e.g. a comma after `SELECT col1,` is
     /// recognised as being inside a SELECT clause).
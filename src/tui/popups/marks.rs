@@ -0,0 +1,125 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout},
+    widgets::{Cell, Clear, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+use crate::{
+    handler::message::Message,
+    misc::config::theme,
+    tui::{
+        component::Component,
+        tag_line::{Tag, TagLine},
+        widgets::block::Block,
+    },
+};
+
+/// Marked rows of the table; typing a mark letter jumps to its row.
+#[derive(Debug)]
+pub struct Marks {
+    /// Mark letter, row, and a preview of the row.
+    marks: Vec<(char, usize, String)>,
+    list_state: TableState,
+}
+
+impl Marks {
+    pub fn new(marks: Vec<(char, usize, String)>) -> Self {
+        Self {
+            list_state: TableState::default().with_selected((!marks.is_empty()).then_some(0)),
+            marks,
+        }
+    }
+
+    fn jump(&self, row: usize) {
+        Message::PaneDismissModal.enqueue();
+        Message::PaneTableJump(row).enqueue();
+    }
+}
+
+impl Component for Marks {
+    fn render(
+        &mut self,
+        _area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        _focus_state: crate::tui::component::FocusState,
+    ) {
+        let [area] = Layout::horizontal([Constraint::Length(64)])
+            .flex(Flex::Center)
+            .areas(buf.area);
+        let [area] = Layout::vertical([Constraint::Length(self.marks.len().max(1) as u16 + 2)])
+            .flex(Flex::Center)
+            .areas(area);
+        Clear.render(area, buf);
+
+        let rows = self.marks.iter().map(|(mark, row, preview)| {
+            Row::new([
+                Cell::new(format!(" {mark}")).style(theme().text().bold()),
+                Cell::new((row + 1).to_string()).style(theme().subtext()),
+                Cell::new(preview.as_str()).style(theme().text()),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(2),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+            ],
+        )
+        .style(theme().text())
+        .row_highlight_style(theme().row_highlighted())
+        .column_spacing(1)
+        .block(
+            Block::default()
+                .title(if self.marks.is_empty() {
+                    "Marks (none, set with m{a-z})"
+                } else {
+                    "Marks"
+                })
+                .title_alignment(Alignment::Center)
+                .bottom(
+                    TagLine::new()
+                        .mono_color()
+                        .centered()
+                        .tag(Tag::new(" Jump ", " a-z | ↑↓ Enter ")),
+                )
+                .into_widget(),
+        );
+        StatefulWidget::render(table, area, buf, &mut self.list_state);
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        match (event.code, event.modifiers) {
+            (KeyCode::Char(c), KeyModifiers::NONE) if c.is_ascii_lowercase() => {
+                match self.marks.iter().find(|(mark, _, _)| *mark == c) {
+                    Some((_, row, _)) => self.jump(*row),
+                    None => Message::AppShowToast(format!("Mark '{c}' is not set")).enqueue(),
+                }
+                true
+            }
+            (KeyCode::Up, KeyModifiers::NONE) => {
+                self.list_state.select_previous();
+                true
+            }
+            (KeyCode::Down, KeyModifiers::NONE) => {
+                self.list_state.select_next();
+                true
+            }
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                if let Some((_, row, _)) = self
+                    .list_state
+                    .selected()
+                    .and_then(|idx| self.marks.get(idx))
+                {
+                    self.jump(*row);
+                }
+                true
+            }
+            (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('\''), KeyModifiers::NONE) => {
+                Message::PaneDismissModal.enqueue();
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -1,4 +1,8 @@
+pub mod cell_editor;
 pub mod column_caster;
+pub mod column_info;
+pub mod column_stats;
+pub mod column_visibility;
 pub mod command_palette;
 pub mod data_frame_info;
 pub mod download_notif;
@@ -6,16 +10,20 @@ pub mod export_target_picker;
 pub mod exporter;
 pub mod exporters;
 pub mod file_picker;
+pub mod go_to_column;
 pub mod go_to_line;
 pub mod help_modal;
+pub mod hex_viewer;
 pub mod histogram_builder;
 pub mod importer;
 pub mod importers;
 pub mod inline_query_picker;
+pub mod marks;
 pub mod multi_step_overlay;
 pub mod scatter_plot_builder;
 pub mod sql_query_picker;
 pub mod table_registerer;
 pub mod theme_selector;
+pub mod transform_preview;
 pub mod url_picker;
 pub mod yes_no_picker;
@@ -2,6 +2,7 @@ use polars::frame::DataFrame;
 
 use crate::{
     handler::message::Message,
+    misc::time_axis,
     tui::{
         pickers::search_picker::SearchPicker,
         popups::multi_step_overlay::{MultiStepOverlay, OverlayStep},
@@ -33,7 +34,9 @@ impl State {
         let items = df
             .columns()
             .iter()
-            .filter(|col| col.dtype().is_numeric())
+            .filter(|col| {
+                col.dtype().is_numeric() || time_axis::seconds_per_unit(col.dtype()).is_some()
+            })
             .map(|col| col.name().to_string())
             .collect();
         Self::PickX {
@@ -51,7 +54,12 @@ impl OverlayStep for State {
                     let items = picker
                         .into_items()
                         .into_iter()
-                        .filter(|item| item != &x)
+                        .filter(|item| {
+                            item != &x
+                                && df
+                                    .column(item)
+                                    .is_ok_and(|column| column.dtype().is_numeric())
+                        })
                         .collect();
                     State::PickY {
                         df,
@@ -76,7 +84,7 @@ impl OverlayStep for State {
                                             let dtype = col.dtype();
                                             dtype.is_string()
                                                 || dtype.is_bool()
-                                                || dtype.is_integer()
+                                                || dtype.is_numeric()
                                         })
                                         .map(|col| col.name().to_string()),
                                 )
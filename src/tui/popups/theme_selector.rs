@@ -1,4 +1,4 @@
-use std::ops::Deref;
+use std::{fmt::Display, ops::Deref};
 
 use crossterm::event::KeyCode;
 use strum::IntoEnumIterator;
@@ -12,32 +12,88 @@ use crate::{
     tui::{
         component::Component,
         pickers::search_picker::SearchPicker,
-        themes::theme::{LoadedTheme, Theme},
+        themes::theme::{LoadedTheme, Theme, theme_files},
     },
 };
 
+/// Theme listed by the selector: a built-in one or a file of the themes directory.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThemeEntry {
+    BuiltIn(Theme),
+    File(String),
+}
+
+impl ThemeEntry {
+    /// Name selecting the theme, as taken by `--theme`.
+    fn name(&self) -> &str {
+        match self {
+            ThemeEntry::BuiltIn(theme) => theme.id(),
+            ThemeEntry::File(name) => name,
+        }
+    }
+}
+
+impl Display for ThemeEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeEntry::BuiltIn(theme) => write!(f, "{theme}"),
+            ThemeEntry::File(name) => write!(f, "{name} (file)"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ThemeSelector {
-    search_picker: SearchPicker<Theme>,
+    search_picker: SearchPicker<ThemeEntry>,
     rollback: LoadedTheme,
+    /// Entry the table is restyled with, loaded once per selection.
+    previewed: Option<ThemeEntry>,
 }
 
 impl ThemeSelector {
+    /// Opens the selector on the themes best matching `query`, previewing the first.
+    pub fn with_query(query: &str) -> Self {
+        let mut selector = Self::default();
+        selector.search_picker.set_text(query);
+        selector.search_picker.select(0);
+        selector
+    }
+
     pub fn into_rollback_theme(self) -> LoadedTheme {
         self.rollback
     }
 
-    pub fn selected(&self) -> Option<Theme> {
-        self.search_picker.selected_item().cloned()
+    pub fn selected(&self) -> Option<&ThemeEntry> {
+        self.search_picker.selected_item()
     }
 
-    pub fn search_picker(&self) -> &SearchPicker<Theme> {
+    pub fn search_picker(&self) -> &SearchPicker<ThemeEntry> {
         &self.search_picker
     }
 
-    pub fn search_picker_mut(&mut self) -> &mut SearchPicker<Theme> {
+    pub fn search_picker_mut(&mut self) -> &mut SearchPicker<ThemeEntry> {
         &mut self.search_picker
     }
+
+    fn preview(&mut self) {
+        let Some(entry) = self.search_picker.selected_item() else {
+            return;
+        };
+        if self.previewed.as_ref() == Some(entry) {
+            return;
+        }
+        match entry {
+            ThemeEntry::BuiltIn(t) if t != theme().app_theme() => config().set_theme(*t),
+            ThemeEntry::BuiltIn(_) => (),
+            // a file failing to load keeps the previous theme, its error shows on Enter
+            ThemeEntry::File(name) => {
+                if let Ok(loaded) = name.parse::<LoadedTheme>() {
+                    config().set_theme(loaded);
+                }
+            }
+        }
+        self.previewed = Some(entry.clone());
+    }
 }
 
 impl Component for ThemeSelector {
@@ -48,11 +104,7 @@ impl Component for ThemeSelector {
         focus_state: crate::tui::component::FocusState,
     ) {
         self.search_picker.render(area, buf, focus_state);
-        if let Some(t) = self.search_picker.selected_item()
-            && t != theme().app_theme()
-        {
-            config().set_theme(*t);
-        }
+        self.preview();
     }
 
     fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
@@ -65,6 +117,11 @@ impl Component for ThemeSelector {
                 }
                 KeyCode::Enter => {
                     Message::AppDismissOverlay.enqueue();
+                    if let Some(entry) = self.selected() {
+                        config()
+                            .select_theme(entry.name())
+                            .unwrap_or_enqueue_error();
+                    }
                     config().store().unwrap_or_enqueue_error();
                     true
                 }
@@ -75,17 +132,26 @@ impl Component for ThemeSelector {
 
 impl Default for ThemeSelector {
     fn default() -> Self {
-        let mut search_picker = SearchPicker::new(Theme::iter().collect());
+        let entries = Theme::iter()
+            .map(ThemeEntry::BuiltIn)
+            .chain(theme_files().into_iter().map(ThemeEntry::File))
+            .collect::<Vec<_>>();
         let rollback = config().theme().deref().clone();
-        let idx = Theme::iter()
-            .enumerate()
-            .find_map(|(i, t)| rollback.app_theme().eq(&t).then_some(i))
+        let current = match rollback.file() {
+            Some(file) => ThemeEntry::File(file.to_owned()),
+            None => ThemeEntry::BuiltIn(*rollback.app_theme()),
+        };
+        let idx = entries
+            .iter()
+            .position(|entry| *entry == current)
             .unwrap_or_default();
+        let mut search_picker = SearchPicker::new(entries);
         search_picker.select(Some(idx));
 
         Self {
             search_picker,
             rollback,
+            previewed: None,
         }
     }
 }
@@ -0,0 +1,55 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use polars::frame::DataFrame;
+
+use crate::{
+    handler::message::Message,
+    tui::{component::Component, pane::TableDescription, popups::yes_no_picker::YesNoPicker},
+};
+
+/// Asks for confirmation before pushing an already computed data frame.
+#[derive(Debug)]
+pub struct TransformPreview {
+    df: DataFrame,
+    description: TableDescription,
+    picker: YesNoPicker,
+}
+
+impl TransformPreview {
+    pub fn new(df: DataFrame, description: TableDescription, title: impl Into<String>) -> Self {
+        Self {
+            df,
+            description,
+            picker: YesNoPicker::default().with_title(title),
+        }
+    }
+}
+
+impl Component for TransformPreview {
+    fn render(
+        &mut self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        focus_state: crate::tui::component::FocusState,
+    ) {
+        self.picker.render(area, buf, focus_state);
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        self.picker.handle(event)
+            || match (event.code, event.modifiers) {
+                (KeyCode::Enter, KeyModifiers::NONE) => {
+                    if self.picker.value() == Some(true) {
+                        Message::PanePushDataFrame(self.df.clone(), self.description.clone())
+                            .enqueue();
+                    }
+                    Message::PaneDismissModal.enqueue();
+                    true
+                }
+                (KeyCode::Esc, KeyModifiers::NONE) => {
+                    Message::PaneDismissModal.enqueue();
+                    true
+                }
+                _ => false,
+            }
+    }
+}
@@ -4,7 +4,7 @@ use ratatui::widgets::Widget;
 
 use crate::{
     handler::message::Message,
-    misc::search::{self, Contain, Skim},
+    misc::search::{self, Contain, Highlight, Skim},
     tui::{component::Component, widgets::block::Block},
 };
 
@@ -42,25 +42,23 @@ impl SearchBar {
         self.rollback_df
     }
 
+    pub fn highlight(&self) -> Option<Highlight> {
+        match self.searcher {
+            Searcher::Fuzzy(_) => Highlight::fuzzy(&self.rollback_df, self.input.value()),
+            Searcher::Exact(_) => Highlight::exact(&self.rollback_df, self.input.value()),
+        }
+    }
+
     pub fn value(&self) -> &str {
         self.input.value()
     }
 
     fn update_search(&mut self) {
         if self.input.value() != self.searcher.pattern() {
-            match self.searcher {
-                Searcher::Fuzzy(_) => {
-                    self.searcher = Searcher::Fuzzy(search::Search::new(
-                        self.rollback_df.clone(),
-                        self.input.value().to_owned(),
-                    ))
-                }
-                Searcher::Exact(_) => {
-                    self.searcher = Searcher::Exact(search::Search::new(
-                        self.rollback_df.clone(),
-                        self.input.value().to_owned(),
-                    ))
-                }
+            let pat = self.input.value().to_owned();
+            self.searcher = match &self.searcher {
+                Searcher::Fuzzy(search) => Searcher::Fuzzy(search.refine(pat)),
+                Searcher::Exact(search) => Searcher::Exact(search.refine(pat)),
             }
         }
     }
@@ -121,7 +119,7 @@ impl Searcher {
         }
     }
 
-    pub fn latest(&self) -> Option<DataFrame> {
+    pub fn latest(&self) -> Option<(DataFrame, Vec<u32>)> {
         match self {
             Searcher::Fuzzy(search) => search.latest(),
             Searcher::Exact(search) => search.latest(),
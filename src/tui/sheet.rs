@@ -1,38 +1,221 @@
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::{KeyCode, KeyModifiers, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::Alignment,
-    text::Line,
+    layout::{Alignment, Constraint, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{Clear, Paragraph, Widget, Wrap},
 };
+use regex::{Regex, RegexBuilder};
 
 use crate::{
     handler::message::Message,
-    misc::{config::theme, osc52::CopyToClipboardOsc52},
+    misc::{
+        config::theme,
+        graphics::{self, ImageFormat},
+        json::{self, JsonToken},
+        osc52::CopyToClipboardOsc52,
+    },
     tui::{
-        component::Component,
+        component::{Component, FocusState},
         tag_line::{Tag, TagLine},
         utils::Scroll,
-        widgets::block::Block,
+        widgets::{block::Block, input::Input},
     },
 };
 
 #[derive(Debug)]
 pub struct SheetSection {
+    /// Column the section shows the value of.
+    name: String,
     header: String,
     content: String,
+    /// Indented form of the content when it holds a JSON object or array.
+    json: Option<String>,
+    /// Nested value of a List or Struct column, with its lines at the current depth.
+    tree: Option<(ValueNode, String)>,
+    /// Binary value holding an image the terminal can draw.
+    image: Option<(ImageFormat, Vec<u8>)>,
+    /// Style of the content, from the column style rules of the config.
+    style: Style,
 }
 
 impl SheetSection {
-    pub fn new(header: String, content: String) -> Self {
-        Self { header, content }
+    pub fn new(name: String, header: String, content: String) -> Self {
+        Self {
+            name,
+            header,
+            json: json::pretty(&content),
+            content,
+            tree: None,
+            image: None,
+            style: Style::default(),
+        }
+    }
+
+    pub fn with_style(self, style: Style) -> Self {
+        Self { style, ..self }
+    }
+
+    /// Keeps `bytes` for a thumbnail when they hold an image the terminal can draw.
+    pub fn with_image(self, bytes: Option<Vec<u8>>) -> Self {
+        Self {
+            image: bytes.and_then(|bytes| {
+                ImageFormat::detect(&bytes)
+                    .filter(|format| graphics::supports(*format))
+                    .map(|format| (format, bytes))
+            }),
+            ..self
+        }
+    }
+
+    pub fn with_tree(self, tree: Option<ValueNode>) -> Self {
+        Self {
+            tree: tree.map(|node| {
+                let lines = node.lines(usize::MAX);
+                (node, lines)
+            }),
+            ..self
+        }
     }
 }
 
+/// Nested List or Struct value, drawn as an indented tree in the sheet.
+#[derive(Debug)]
+pub struct ValueNode {
+    key: String,
+    /// Value of a leaf, or the item count of a branch.
+    value: String,
+    children: Option<Vec<ValueNode>>,
+}
+
+impl ValueNode {
+    pub fn leaf(key: String, value: String) -> Self {
+        Self {
+            key,
+            value,
+            children: None,
+        }
+    }
+
+    pub fn branch(key: String, value: String, children: Vec<ValueNode>) -> Self {
+        Self {
+            key,
+            value,
+            children: Some(children),
+        }
+    }
+
+    /// Number of branch levels, counting this node.
+    fn depth(&self) -> usize {
+        self.children.as_ref().map_or(0, |children| {
+            children
+                .iter()
+                .map(ValueNode::depth)
+                .max()
+                .unwrap_or_default()
+                + 1
+        })
+    }
+
+    /// Lines of the tree with the branches below `depth` levels collapsed.
+    fn lines(&self, depth: usize) -> String {
+        let mut lines = Vec::new();
+        self.push_lines(depth, 0, &mut lines);
+        lines.join("\n")
+    }
+
+    fn push_lines(&self, depth: usize, indent: usize, lines: &mut Vec<String>) {
+        let key = if self.key.is_empty() {
+            String::new()
+        } else {
+            format!("{}: ", self.key)
+        };
+        match &self.children {
+            Some(children) if depth > 0 => {
+                lines.push(format!("{:indent$}\u{25be} {key}{}", "", self.value));
+                for child in children {
+                    child.push_lines(depth - 1, indent + 2, lines);
+                }
+            }
+            Some(_) => lines.push(format!("{:indent$}\u{25b8} {key}{}", "", self.value)),
+            None => lines.push(format!("{:indent$}  {key}{}", "", self.value)),
+        }
+    }
+}
+
+/// Rows of the thumbnail drawn above the sheet for an image value.
+const THUMBNAIL_HEIGHT: u16 = 14;
+
+/// Kind of a line of the sheet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SheetLine {
+    /// Header of the section at the index.
+    Header(usize),
+    /// Content line of the section at the index.
+    Content(usize),
+    /// Indented JSON line of the section at the index.
+    Json(usize),
+}
+
 #[derive(Debug)]
 pub struct Sheet {
     scroll: Scroll,
     row: usize,
     sections: Vec<SheetSection>,
+    search: Option<SheetSearch>,
+    /// Section to scroll to on the next render.
+    jump_section: Option<usize>,
+    editor: Option<RecordEditor>,
+    /// Whether JSON values are shown as stored instead of indented.
+    raw_json: bool,
+    /// Levels of nested values shown expanded; all of them when `None`.
+    tree_depth: Option<usize>,
+    /// Area the sheet was last rendered in.
+    area: Rect,
+}
+
+/// Inputs editing the values of the record, one per section.
+#[derive(Debug)]
+struct RecordEditor {
+    inputs: Vec<Input>,
+    active: usize,
+    offset: usize,
+}
+
+impl RecordEditor {
+    fn select(&mut self, forward: bool) {
+        let len = self.inputs.len().max(1);
+        self.active = if forward {
+            (self.active + 1) % len
+        } else {
+            (self.active + len - 1) % len
+        };
+    }
+}
+
+/// Case-insensitive text search within the sections of a sheet.
+#[derive(Debug, Default)]
+struct SheetSearch {
+    input: Input,
+    editing: bool,
+    /// Indices of the sheet lines containing a match.
+    matches: Vec<usize>,
+    current: usize,
+    /// Whether the next render scrolls to the current match.
+    jump: bool,
+}
+
+impl SheetSearch {
+    fn regex(&self) -> Option<Regex> {
+        (!self.input.value().is_empty())
+            .then(|| {
+                RegexBuilder::new(&regex::escape(self.input.value()))
+                    .case_insensitive(true)
+                    .build()
+                    .ok()
+            })
+            .flatten()
+    }
 }
 
 impl Sheet {
@@ -41,6 +224,20 @@ impl Sheet {
             scroll: Default::default(),
             row,
             sections,
+            search: None,
+            jump_section: None,
+            editor: None,
+            raw_json: false,
+            tree_depth: None,
+            area: Rect::default(),
+        }
+    }
+
+    /// Scrolls to the section at `idx`, e.g. the column of the active cell.
+    pub fn with_section(self, idx: impl Into<Option<usize>>) -> Self {
+        Self {
+            jump_section: idx.into(),
+            ..self
         }
     }
 
@@ -59,6 +256,200 @@ impl Sheet {
     pub fn set(&mut self, row: usize, sections: Vec<SheetSection>) {
         self.row = row;
         self.sections = sections;
+        self.editor = None;
+        self.set_tree_depth(self.tree_depth);
+    }
+
+    fn max_tree_depth(&self) -> Option<usize> {
+        self.sections
+            .iter()
+            .filter_map(|section| section.tree.as_ref())
+            .map(|(node, _)| node.depth())
+            .max()
+    }
+
+    /// Expands nested values to `depth` levels, or all of them.
+    fn set_tree_depth(&mut self, depth: Option<usize>) {
+        self.tree_depth = depth;
+        for (node, lines) in self.sections.iter_mut().filter_map(|s| s.tree.as_mut()) {
+            *lines = node.lines(depth.unwrap_or(usize::MAX));
+        }
+        self.find();
+    }
+
+    /// Starts editing the values of the record, from the section at the top.
+    fn edit(&mut self) {
+        self.editor = Some(RecordEditor {
+            inputs: self
+                .sections
+                .iter()
+                .map(|section| Input::default().with_value(section.content.clone()))
+                .collect(),
+            active: 0,
+            offset: 0,
+        });
+    }
+
+    /// Sends the edited values of the record to the pane, which writes them
+    /// into the frame and refreshes the sheet.
+    fn commit(&self) {
+        if let Some(editor) = &self.editor {
+            let values = self
+                .sections
+                .iter()
+                .zip(&editor.inputs)
+                .filter(|(section, input)| section.content != input.value())
+                .map(|(section, input)| (section.name.clone(), input.value().to_owned()))
+                .collect::<Vec<_>>();
+            if values.is_empty() {
+                Message::AppShowToast("Nothing to save".to_owned()).enqueue();
+            } else {
+                Message::PaneEditRecord(self.row, values).enqueue();
+            }
+        }
+    }
+
+    /// Finds the lines matching the search and moves to the first of them.
+    fn find(&mut self) {
+        let Some(regex) = self.search.as_ref().and_then(SheetSearch::regex) else {
+            if let Some(search) = self.search.as_mut() {
+                search.matches.clear();
+            }
+            return;
+        };
+        let matches = sheet_lines(&self.sections, self.raw_json)
+            .enumerate()
+            .filter(|(_, (_, line))| regex.is_match(line))
+            .map(|(idx, _)| idx)
+            .collect();
+        if let Some(search) = self.search.as_mut() {
+            search.matches = matches;
+            search.current = 0;
+            search.jump = true;
+        }
+    }
+
+    fn select_match(&mut self, forward: bool) {
+        if let Some(search) = self.search.as_mut()
+            && !search.matches.is_empty()
+        {
+            let len = search.matches.len();
+            search.current = if forward {
+                (search.current + 1) % len
+            } else {
+                (search.current + len - 1) % len
+            };
+            search.jump = true;
+        }
+    }
+}
+
+/// Lines of the sheet: each section's header and content lines, indented
+/// when they hold JSON unless `raw_json`, followed by a blank line.
+fn sheet_lines(
+    sections: &[SheetSection],
+    raw_json: bool,
+) -> impl Iterator<Item = (SheetLine, &str)> {
+    sections.iter().enumerate().flat_map(move |(idx, section)| {
+        let (kind, content) = match (&section.tree, &section.json) {
+            (Some((_, lines)), _) => (SheetLine::Content(idx), lines),
+            (None, Some(json)) if !raw_json => (SheetLine::Json(idx), json),
+            _ => (SheetLine::Content(idx), &section.content),
+        };
+        std::iter::once((SheetLine::Header(idx), section.header.as_str()))
+            .chain(content.lines().map(move |line| (kind, line)))
+            .chain(std::iter::once((SheetLine::Content(idx), "\n")))
+    })
+}
+
+/// Colors the tokens of a line of indented JSON.
+fn json_line(line: &str) -> Line<'_> {
+    Line::from(
+        json::tokens(line)
+            .into_iter()
+            .map(|(token, text)| match token {
+                JsonToken::Key => Span::styled(text, theme().header(0)),
+                JsonToken::String => Span::styled(text, theme().header(2)),
+                JsonToken::Number => Span::styled(text, theme().header(3)),
+                JsonToken::Literal => Span::styled(text, theme().header(4)),
+                JsonToken::Punctuation => Span::styled(text, theme().subtext()),
+                JsonToken::Space => Span::raw(text),
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Splits `line` into spans, emphasizing the matches of `regex`.
+fn highlighted_line<'a>(line: &'a str, regex: Option<&Regex>) -> Line<'a> {
+    let Some(regex) = regex else {
+        return Line::raw(line);
+    };
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for found in regex.find_iter(line) {
+        spans.push(Span::raw(&line[last..found.start()]));
+        spans.push(Span::styled(
+            found.as_str(),
+            theme()
+                .text_highlighted()
+                .add_modifier(Modifier::UNDERLINED),
+        ));
+        last = found.end();
+    }
+    spans.push(Span::raw(&line[last..]));
+    Line::from(spans)
+}
+
+/// Draws the header of every section above an input with its value, keeping
+/// the active input in view.
+fn render_editor(
+    sections: &[SheetSection],
+    editor: &mut RecordEditor,
+    area: Rect,
+    buf: &mut ratatui::prelude::Buffer,
+) {
+    let block = Block::default()
+        .bottom(
+            TagLine::new()
+                .mono_color()
+                .centered()
+                .tag(Tag::new(" Next Field ", " Tab "))
+                .tag(Tag::new(" Previous Field ", " Shift+Tab "))
+                .tag(Tag::new(" Save ", " Enter "))
+                .tag(Tag::new(" Cancel ", " Esc ")),
+        )
+        .into_widget();
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let fields = usize::from(inner.height / 2).max(1);
+    editor.offset = editor
+        .offset
+        .clamp(editor.active.saturating_sub(fields - 1), editor.active);
+    for (idx, (section, input)) in sections
+        .iter()
+        .zip(editor.inputs.iter_mut())
+        .enumerate()
+        .skip(editor.offset)
+        .take(fields)
+    {
+        let y = inner.y + ((idx - editor.offset) * 2) as u16;
+        buf.set_line(
+            inner.x,
+            y,
+            &Line::styled(section.header.as_str(), theme().header(idx)),
+            inner.width,
+        );
+        let value_area = Rect::new(inner.x, y + 1, inner.width, 1).intersection(inner);
+        input.render(
+            value_area,
+            buf,
+            if idx == editor.active {
+                FocusState::Focused
+            } else {
+                FocusState::NotFocused
+            },
+        );
     }
 }
 
@@ -69,48 +460,219 @@ impl Component for Sheet {
         buf: &mut ratatui::prelude::Buffer,
         _focus_state: super::component::FocusState,
     ) {
+        self.area = area;
         Clear.render(area, buf);
 
-        let pg = Paragraph::new(
-            self.sections
-                .iter()
-                .enumerate()
-                .flat_map(|(idx, SheetSection { header, content })| {
-                    std::iter::once(Line::raw(header).style(theme().header(idx)))
-                        .chain(
-                            content
-                                .lines()
-                                .map(|line| Line::raw(line).style(theme().text())),
-                        )
-                        .chain(std::iter::once(Line::raw("\n")))
-                })
-                .collect::<Vec<_>>(),
-        )
-        .style(theme().text())
-        .alignment(Alignment::Left)
-        .wrap(Wrap { trim: true })
-        .block(
-            Block::default()
-                .bottom(
-                    TagLine::new()
-                        .mono_color()
-                        .centered()
-                        .tag(Tag::new(" Scroll Up ", " Shift+K | Shift+\u{2191} "))
-                        .tag(Tag::new(" Scroll Down ", " Shift+J | Shift+\u{2193} "))
-                        .tag(Tag::new(" Copy ", " C ")),
-                )
-                .title_alignment(Alignment::Center)
-                .into_widget(),
-        );
+        if let Some(editor) = &mut self.editor {
+            render_editor(&self.sections, editor, area, buf);
+            return;
+        }
+
+        let area = match self
+            .sections
+            .iter()
+            .find_map(|section| Some((section, section.image.as_ref()?)))
+        {
+            Some((section, (format, bytes))) if area.height > THUMBNAIL_HEIGHT * 2 => {
+                let [image_area, area] =
+                    Layout::vertical([Constraint::Length(THUMBNAIL_HEIGHT), Constraint::Fill(1)])
+                        .areas(area);
+                let block = Block::default()
+                    .title(format!(
+                        "{} ({}, {} bytes)",
+                        section.name,
+                        format.name(),
+                        bytes.len()
+                    ))
+                    .into_widget();
+                let inner = block.inner(image_area);
+                block.render(image_area, buf);
+                graphics::show_image(inner, bytes);
+                area
+            }
+            _ => area,
+        };
+
+        let regex = self.search.as_ref().and_then(SheetSearch::regex);
+        let current = self
+            .search
+            .as_ref()
+            .and_then(|search| search.matches.get(search.current).copied());
+        let lines = sheet_lines(&self.sections, self.raw_json)
+            .enumerate()
+            .map(|(line_idx, (kind, line))| {
+                let style = match kind {
+                    SheetLine::Header(idx) => theme().header(idx),
+                    SheetLine::Content(idx) | SheetLine::Json(idx) => {
+                        theme().text().patch(self.sections[idx].style)
+                    }
+                };
+                let style = if current == Some(line_idx) {
+                    style.add_modifier(Modifier::REVERSED)
+                } else {
+                    style
+                };
+                match kind {
+                    SheetLine::Json(_) if regex.is_none() => json_line(line).style(style),
+                    _ => highlighted_line(line, regex.as_ref()).style(style),
+                }
+            })
+            .collect::<Vec<_>>();
+        let has_json = self.sections.iter().any(|section| section.json.is_some());
+        let has_tree = self.sections.iter().any(|section| section.tree.is_some());
+
+        let bottom = match &self.search {
+            Some(search) => TagLine::new().mono_color().centered().tag(Tag::new(
+                format!(" /{} ", search.input.value()),
+                if search.matches.is_empty() {
+                    " No Match ".to_owned()
+                } else {
+                    format!(" {} / {} ", search.current + 1, search.matches.len())
+                },
+            )),
+            None => {
+                let tags = TagLine::new()
+                    .mono_color()
+                    .centered()
+                    .tag(Tag::new(" Scroll Up ", " Shift+K | Shift+\u{2191} "))
+                    .tag(Tag::new(" Scroll Down ", " Shift+J | Shift+\u{2193} "))
+                    .tag(Tag::new(" Search ", " / "))
+                    .tag(Tag::new(" Edit ", " E "))
+                    .tag(Tag::new(" Copy ", " C "));
+                let tags = if has_tree {
+                    tags.tag(Tag::new(" Collapse / Expand ", " - | + "))
+                } else {
+                    tags
+                };
+                match (has_json, self.raw_json) {
+                    (true, false) => tags.tag(Tag::new(" Raw JSON ", " R ")),
+                    (true, true) => tags.tag(Tag::new(" Pretty JSON ", " R ")),
+                    (false, _) => tags,
+                }
+            }
+        };
+
+        let jump = self
+            .search
+            .as_mut()
+            .is_some_and(|search| std::mem::take(&mut search.jump));
+        let section_line = self.jump_section.take().and_then(|section| {
+            sheet_lines(&self.sections, self.raw_json)
+                .position(|(kind, _)| kind == SheetLine::Header(section))
+        });
+        let offset = current.filter(|_| jump).or(section_line).map(|line_idx| {
+            Paragraph::new(lines[..line_idx].to_vec())
+                .wrap(Wrap { trim: true })
+                .line_count(area.width.saturating_sub(2))
+        });
+
+        let pg = Paragraph::new(lines)
+            .style(theme().text())
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .bottom(bottom)
+                    .title_alignment(Alignment::Center)
+                    .into_widget(),
+            );
 
         self.scroll
             .adjust(pg.line_count(area.width), area.height.saturating_sub(2));
+        if let Some(offset) = offset {
+            self.scroll.set(offset);
+        }
 
         pg.scroll((self.scroll.val_u16(), 0)).render(area, buf);
     }
 
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if !self.area.contains(Position::new(event.column, event.row)) {
+            return false;
+        }
+        match event.kind {
+            MouseEventKind::ScrollUp => self.scroll.up(),
+            MouseEventKind::ScrollDown => self.scroll.down(),
+            _ => (),
+        }
+        true
+    }
+
     fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        if let Some(editor) = self.editor.as_mut() {
+            match (event.code, event.modifiers) {
+                (KeyCode::Tab, KeyModifiers::NONE) | (KeyCode::Down, KeyModifiers::NONE) => {
+                    editor.select(true)
+                }
+                (KeyCode::BackTab, _) | (KeyCode::Up, KeyModifiers::NONE) => editor.select(false),
+                (KeyCode::Enter, KeyModifiers::NONE) => self.commit(),
+                (KeyCode::Esc, KeyModifiers::NONE) => self.editor = None,
+                _ => {
+                    if let Some(input) = editor.inputs.get_mut(editor.active) {
+                        input.handle(event);
+                    }
+                }
+            }
+            return true;
+        }
+        if let Some(search) = self.search.as_mut().filter(|search| search.editing) {
+            match (event.code, event.modifiers) {
+                (KeyCode::Enter, KeyModifiers::NONE) => search.editing = false,
+                (KeyCode::Esc, KeyModifiers::NONE) => self.search = None,
+                _ => {
+                    if search.input.handle(event) {
+                        self.find();
+                    }
+                }
+            }
+            return true;
+        }
         match (event.code, event.modifiers) {
+            (KeyCode::Char('e'), KeyModifiers::NONE) => {
+                self.edit();
+                true
+            }
+            (KeyCode::Char('/'), KeyModifiers::NONE) => {
+                self.search = Some(SheetSearch {
+                    editing: true,
+                    ..Default::default()
+                });
+                true
+            }
+            (KeyCode::Char('n'), KeyModifiers::NONE) if self.search.is_some() => {
+                self.select_match(true);
+                true
+            }
+            (KeyCode::Char('N'), KeyModifiers::SHIFT) if self.search.is_some() => {
+                self.select_match(false);
+                true
+            }
+            (KeyCode::Esc, KeyModifiers::NONE) if self.search.is_some() => {
+                self.search = None;
+                true
+            }
+            (KeyCode::Char('r'), KeyModifiers::NONE)
+                if self.sections.iter().any(|section| section.json.is_some()) =>
+            {
+                self.raw_json = !self.raw_json;
+                self.find();
+                true
+            }
+            (KeyCode::Char('-'), _) if self.max_tree_depth().is_some() => {
+                let max = self.max_tree_depth().unwrap_or_default();
+                let depth = self.tree_depth.unwrap_or(max).min(max);
+                self.set_tree_depth(Some(depth.saturating_sub(1)));
+                true
+            }
+            (KeyCode::Char('+'), _) if self.max_tree_depth().is_some() => {
+                let max = self.max_tree_depth().unwrap_or_default();
+                self.set_tree_depth(
+                    self.tree_depth
+                        .filter(|depth| depth + 1 < max)
+                        .map(|depth| depth + 1),
+                );
+                true
+            }
             (KeyCode::Char('K'), KeyModifiers::SHIFT) | (KeyCode::Up, KeyModifiers::SHIFT) => {
                 self.scroll.up();
                 true
@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use polars::frame::DataFrame;
+use ratatui::{
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use regex::Regex;
+
+use crate::{
+    AppResult,
+    misc::{config::theme, search::Highlight, transform, type_ext::UnwrapOrEnqueueError},
+    tui::{
+        component::Component,
+        tag_line::{Tag, TagLine},
+        widgets::block::Block,
+    },
+};
+
+/// Walks through the cells matching a `%s` substitution, asking whether to
+/// replace each one.
+#[derive(Debug)]
+pub struct SubstituteBar {
+    df: DataFrame,
+    pattern: Regex,
+    replacement: String,
+    /// Matching `(row, column index)` cells in reading order.
+    cells: Vec<(usize, usize)>,
+    position: usize,
+    replaced: usize,
+    updated: bool,
+}
+
+impl SubstituteBar {
+    pub fn new(
+        df: DataFrame,
+        pattern: Regex,
+        replacement: String,
+        cells: Vec<(usize, usize)>,
+    ) -> Self {
+        Self {
+            df,
+            pattern,
+            replacement,
+            cells,
+            position: 0,
+            replaced: 0,
+            updated: false,
+        }
+    }
+
+    /// Row and column name of the match awaiting an answer.
+    pub fn current(&self) -> Option<(usize, &str)> {
+        let (row, col) = self.cells.get(self.position)?;
+        Some((*row, self.df.columns().get(*col)?.name().as_str()))
+    }
+
+    pub fn highlight(&self) -> Option<Highlight> {
+        self.current()
+            .map(|(_, name)| Highlight::regex(name.to_owned(), self.pattern.clone()))
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.position >= self.cells.len()
+    }
+
+    pub fn replaced(&self) -> usize {
+        self.replaced
+    }
+
+    pub fn total(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The frame with the replacements made since the last call, if any.
+    pub fn take_update(&mut self) -> Option<DataFrame> {
+        std::mem::take(&mut self.updated).then(|| self.df.clone())
+    }
+
+    fn current_value(&self) -> Option<&str> {
+        let (row, name) = self.current()?;
+        self.df.column(name).ok()?.str().ok()?.get(row)
+    }
+
+    fn accept(&mut self) -> AppResult<()> {
+        self.replace(self.position + 1)
+    }
+
+    fn accept_all(&mut self) -> AppResult<()> {
+        self.replace(self.cells.len())
+    }
+
+    fn skip(&mut self) {
+        self.position += 1;
+    }
+
+    fn quit(&mut self) {
+        self.position = self.cells.len();
+    }
+
+    /// Replaces the matches from the current one up to `end`, column by column.
+    fn replace(&mut self, end: usize) -> AppResult<()> {
+        let mut rows = BTreeMap::<usize, Vec<usize>>::new();
+        for (row, col) in &self.cells[self.position..end] {
+            rows.entry(*col).or_default().push(*row);
+        }
+        for (col, rows) in rows {
+            let name = self.df.columns()[col].name().to_string();
+            self.df =
+                transform::replace_cells(&self.df, &name, &rows, &self.pattern, &self.replacement)?;
+            self.replaced += rows.len();
+        }
+        self.position = end;
+        self.updated = true;
+        Ok(())
+    }
+}
+
+impl Component for SubstituteBar {
+    fn render(
+        &mut self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        _focus_state: super::component::FocusState,
+    ) {
+        let before = self.current_value().unwrap_or_default();
+        let after = self.pattern.replace_all(before, self.replacement.as_str());
+        let title = match self.current() {
+            Some((row, name)) => format!(
+                "Substitute {} / {} in '{name}' row {}",
+                self.position + 1,
+                self.cells.len(),
+                row + 1
+            ),
+            None => "Substitute".to_owned(),
+        };
+        Paragraph::new(vec![
+            Line::from(vec![
+                Span::styled("- ", theme().text()),
+                Span::styled(before, theme().text()),
+            ]),
+            Line::from(vec![
+                Span::styled("+ ", theme().text_highlighted()),
+                Span::styled(after, theme().text_highlighted()),
+            ]),
+        ])
+        .style(theme().text())
+        .block(
+            Block::default()
+                .title(title)
+                .bottom(
+                    TagLine::new()
+                        .mono_color()
+                        .centered()
+                        .tag(Tag::new(" Replace ", " y "))
+                        .tag(Tag::new(" Skip ", " n "))
+                        .tag(Tag::new(" All ", " a "))
+                        .tag(Tag::new(" Quit ", " q ")),
+                )
+                .into_widget(),
+        )
+        .render(area, buf);
+    }
+
+    fn handle(&mut self, event: KeyEvent) -> bool {
+        match (event.code, event.modifiers) {
+            (KeyCode::Char('y'), KeyModifiers::NONE) => {
+                self.accept().unwrap_or_enqueue_error();
+                true
+            }
+            (KeyCode::Char('n'), KeyModifiers::NONE) => {
+                self.skip();
+                true
+            }
+            (KeyCode::Char('a'), KeyModifiers::NONE) => {
+                self.accept_all().unwrap_or_enqueue_error();
+                true
+            }
+            (KeyCode::Char('q'), KeyModifiers::NONE) | (KeyCode::Esc, KeyModifiers::NONE) => {
+                self.quit();
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use std::ops::Div;
 
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+use itertools::Itertools;
 use ratatui::{
-    layout::{Constraint, Rect},
+    layout::{Constraint, Layout, Position, Rect},
     widgets::{Cell, Clear, Row, StatefulWidget, Table, TableState, Widget},
 };
 use unicode_width::UnicodeWidthStr;
@@ -10,33 +12,69 @@ use unicode_width::UnicodeWidthStr;
 use crate::{
     handler::message::Message,
     misc::config::theme,
-    tui::{component::Component, widgets::block::Block},
+    tui::{
+        component::Component,
+        pickers::text_picker::TextPicker,
+        widgets::{block::Block, input::Input},
+    },
 };
 
+/// Tab as listed in the switcher.
+#[derive(Debug, Clone)]
+pub struct TabEntry {
+    pub title: String,
+    /// File or URL the tab was read from.
+    pub path: Option<String>,
+    /// Rows and columns of the frame shown in the tab.
+    pub shape: (usize, usize),
+}
+
 #[derive(Debug)]
 pub struct TabSwitcher {
-    tabs: Vec<String>,
+    tabs: Vec<TabEntry>,
     title: String,
     list_state: TableState,
     rollback: usize,
+    /// Input of the new name of the selected tab.
+    renamer: Option<TextPicker>,
+    /// Fuzzy filter over the titles and paths of the tabs.
+    filter: Option<Input>,
+    /// Indices of the listed tabs, best matches first while filtering.
+    visible: Vec<usize>,
+    /// Areas of the panel and of its list when last rendered.
+    area: Rect,
+    list_area: Rect,
 }
 
 impl TabSwitcher {
-    pub fn new(title: impl Into<String>, tabs: Vec<String>, idx: usize) -> TabSwitcher {
+    pub fn new(title: impl Into<String>, tabs: Vec<TabEntry>, idx: usize) -> TabSwitcher {
         Self {
             list_state: TableState::default().with_selected(idx),
+            visible: (0..tabs.len()).collect(),
             tabs,
             title: title.into(),
             rollback: idx,
+            renamer: None,
+            filter: None,
+            area: Rect::default(),
+            list_area: Rect::default(),
         }
     }
 
+    /// Index of the selected tab.
     pub fn selected(&self) -> Option<usize> {
-        self.list_state.selected()
+        self.list_state
+            .selected()
+            .and_then(|pos| self.visible.get(pos))
+            .copied()
     }
 
+    /// Selects the tab at `idx`, when it is listed.
     pub fn select(&mut self, idx: impl Into<Option<usize>>) {
-        self.list_state.select(idx.into());
+        self.list_state.select(
+            idx.into()
+                .and_then(|idx| self.visible.iter().position(|tab| *tab == idx)),
+        );
     }
 
     pub fn select_prev(&mut self) {
@@ -49,16 +87,102 @@ impl TabSwitcher {
             .selected()
             .unwrap_or_default()
             .saturating_add(1)
-            .min(self.tabs.len().saturating_sub(1));
+            .min(self.visible.len().saturating_sub(1));
         self.list_state.select(Some(idx));
     }
 
+    fn show_renamer(&mut self) {
+        if let Some(tab) = self.selected().and_then(|idx| self.tabs.get(idx)) {
+            self.renamer = Some(
+                TextPicker::default()
+                    .with_title("Rename Tab")
+                    .with_value(tab.title.to_owned()),
+            );
+        }
+    }
+
     pub fn select_first(&mut self) {
         self.list_state.select(Some(0));
     }
     pub fn select_last(&mut self) {
         self.list_state
-            .select(Some(self.tabs.len().saturating_sub(1)));
+            .select(Some(self.visible.len().saturating_sub(1)));
+    }
+
+    /// Lists the tabs matching the filter, best first, selecting the best one.
+    fn refilter(&mut self) {
+        let query = self.filter.as_ref().map(Input::value).unwrap_or_default();
+        if query.is_empty() {
+            let selected = self.selected();
+            self.visible = (0..self.tabs.len()).collect();
+            self.select(selected);
+            return;
+        }
+        let matcher = SkimMatcherV2::default();
+        self.visible = self
+            .tabs
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, tab)| {
+                let title = matcher.fuzzy_match(&tab.title, query);
+                let path = tab
+                    .path
+                    .as_deref()
+                    .and_then(|path| matcher.fuzzy_match(path, query));
+                title.max(path).map(|score| (score, idx))
+            })
+            .sorted_by_key(|(score, idx)| (-score, *idx))
+            .map(|(_, idx)| idx)
+            .collect();
+        self.list_state
+            .select((!self.visible.is_empty()).then_some(0));
+        self.enqueue_selected();
+    }
+
+    fn enqueue_selected(&self) {
+        if let Some(select) = self.selected() {
+            Message::TabsSelect(select).enqueue();
+        }
+    }
+
+    /// Rows, columns, and path of the selected tab.
+    fn preview(&self) -> Option<String> {
+        let tab = self.tabs.get(self.selected()?)?;
+        let shape = format!(" {} \u{d7} {} ", tab.shape.0, tab.shape.1);
+        Some(match &tab.path {
+            Some(path) => format!("{shape}\u{b7} {path} "),
+            None => shape,
+        })
+    }
+
+    fn handle_filter(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        match (event.code, event.modifiers) {
+            (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                self.select_prev();
+                self.enqueue_selected();
+            }
+            (KeyCode::Down, KeyModifiers::NONE) | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+                self.select_next();
+                self.enqueue_selected();
+            }
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                Message::TabsDismissSwitcher.enqueue();
+            }
+            (KeyCode::Esc, KeyModifiers::NONE) => {
+                self.filter = None;
+                self.refilter();
+            }
+            _ => {
+                if let Some(filter) = self.filter.as_mut()
+                    && filter.handle(event)
+                {
+                    self.refilter();
+                } else {
+                    return false;
+                }
+            }
+        }
+        true
     }
 }
 
@@ -73,7 +197,7 @@ impl Component for TabSwitcher {
         let text_width = self
             .tabs
             .iter()
-            .map(|s| s.width() as u16)
+            .map(|tab| tab.title.width() as u16)
             .max()
             .unwrap_or_default()
             .max(34)
@@ -86,15 +210,32 @@ impl Component for TabSwitcher {
             area.height,
         );
 
+        self.area = area;
         Widget::render(Clear, area, buf);
 
-        let rows = self.tabs.iter().enumerate().map(|(i, s)| {
+        let rows = self.visible.iter().map(|idx| {
             Row::new([
-                Cell::new(format!(" {:>width$}", i + 1, width = num_width as usize))
+                Cell::new(format!(" {:>width$}", idx + 1, width = num_width as usize))
                     .style(theme().subtext()),
-                Cell::new(s.as_str()).style(theme().text()),
+                Cell::new(self.tabs[*idx].title.as_str()).style(theme().text()),
             ])
         });
+        let mut block = Block::default().title(self.title.as_str());
+        if let Some(preview) = self.preview() {
+            block = block.bottom(preview);
+        }
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let table_area = match self.filter.as_mut() {
+            Some(filter) => {
+                let [filter_area, table_area] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner);
+                filter.render(filter_area, buf, focus_state);
+                table_area
+            }
+            None => inner,
+        };
+        self.list_area = table_area;
         let table = Table::default()
             .rows(rows)
             .style(theme().text())
@@ -103,53 +244,106 @@ impl Component for TabSwitcher {
                 Constraint::Length(num_width + 1),
                 Constraint::Length(text_width),
             ])
-            .column_spacing(1)
-            .block(Block::default().title(self.title.as_str()).into_widget());
+            .column_spacing(1);
         if focus_state.is_focused() {
-            StatefulWidget::render(table, area, buf, &mut self.list_state);
+            StatefulWidget::render(table, table_area, buf, &mut self.list_state);
         } else {
-            StatefulWidget::render(table, area, buf, &mut self.list_state.with_selected(None));
+            StatefulWidget::render(
+                table,
+                table_area,
+                buf,
+                &mut self.list_state.with_selected(None),
+            );
+        }
+        if let Some(renamer) = self.renamer.as_mut() {
+            renamer.render(area, buf, focus_state);
+        }
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        let position = Position::new(event.column, event.row);
+        if self.renamer.is_some() || !self.area.contains(position) {
+            return false;
+        }
+        match event.kind {
+            MouseEventKind::ScrollUp => self.select_prev(),
+            MouseEventKind::ScrollDown => self.select_next(),
+            MouseEventKind::Down(MouseButton::Left) if self.list_area.contains(position) => {
+                let pos = self.list_state.offset() + usize::from(event.row - self.list_area.y);
+                if pos < self.visible.len() {
+                    self.list_state.select(Some(pos));
+                }
+            }
+            _ => return true,
         }
+        self.enqueue_selected();
+        true
     }
 
     fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        if let Some(renamer) = self.renamer.as_mut() {
+            return renamer.handle(event)
+                || match (event.code, event.modifiers) {
+                    (KeyCode::Enter, KeyModifiers::NONE) => {
+                        Message::TabsRename(renamer.value().to_owned()).enqueue();
+                        self.renamer = None;
+                        true
+                    }
+                    (KeyCode::Esc, KeyModifiers::NONE) => {
+                        self.renamer = None;
+                        true
+                    }
+                    _ => false,
+                };
+        }
+        if self.filter.is_some() {
+            return self.handle_filter(event);
+        }
         match (event.code, event.modifiers) {
             (KeyCode::Up, KeyModifiers::NONE)
             | (KeyCode::Char('k'), KeyModifiers::NONE)
             | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
                 self.select_prev();
-                if let Some(select) = self.list_state.selected() {
-                    Message::TabsSelect(select).enqueue();
-                }
+                self.enqueue_selected();
                 true
             }
             (KeyCode::Down, KeyModifiers::NONE)
             | (KeyCode::Char('j'), KeyModifiers::NONE)
             | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
                 self.select_next();
-                if let Some(select) = self.list_state.selected() {
-                    Message::TabsSelect(select).enqueue();
-                }
+                self.enqueue_selected();
                 true
             }
             (KeyCode::Home, KeyModifiers::NONE) | (KeyCode::Char('g'), KeyModifiers::NONE) => {
                 self.select_first();
-                if let Some(select) = self.list_state.selected() {
-                    Message::TabsSelect(select).enqueue();
-                }
+                self.enqueue_selected();
                 true
             }
             (KeyCode::End, KeyModifiers::NONE) | (KeyCode::Char('G'), KeyModifiers::SHIFT) => {
                 self.select_last();
-                if let Some(select) = self.list_state.selected() {
-                    Message::TabsSelect(select).enqueue();
-                }
+                self.enqueue_selected();
                 true
             }
             (KeyCode::Enter, KeyModifiers::NONE) => {
                 Message::TabsDismissSwitcher.enqueue();
                 true
             }
+            (KeyCode::Char('/'), KeyModifiers::NONE) => {
+                self.filter = Some(Input::default().with_hint("Filter tabs".to_owned()));
+                true
+            }
+            (KeyCode::Char('K'), KeyModifiers::SHIFT) | (KeyCode::Up, KeyModifiers::SHIFT) => {
+                Message::TabsMoveSelected(-1).enqueue();
+                true
+            }
+            (KeyCode::Char('J'), KeyModifiers::SHIFT) | (KeyCode::Down, KeyModifiers::SHIFT) => {
+                Message::TabsMoveSelected(1).enqueue();
+                true
+            }
+            (KeyCode::Char('r'), KeyModifiers::NONE) => {
+                self.show_renamer();
+                true
+            }
             (KeyCode::Esc, KeyModifiers::NONE)
             | (KeyCode::Char('q'), KeyModifiers::NONE)
             | (KeyCode::Char('t'), KeyModifiers::NONE) => {
@@ -1,21 +1,37 @@
-use std::ops::{Add, Div};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::{Add, Div, Range, RangeInclusive},
+    sync::Arc,
+};
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use itertools::Itertools;
-use polars::frame::DataFrame;
+use polars::{
+    frame::DataFrame,
+    prelude::{AnyValue, ChunkAgg, Column, DataType},
+};
 use ratatui::{
-    layout::{Constraint, Layout, Position, Rect},
-    text::Text,
+    layout::{Constraint, Flex, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
     widgets::{Cell, List, ListItem, ListState, Row, StatefulWidget, TableState},
 };
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
 use tui_scrollview::{ScrollView, ScrollViewState, ScrollbarVisibility};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::{
+    handler::message::Message,
     misc::{
-        config::theme,
+        config::{config, theme},
         iter_ext::ZipItersExt,
-        polars_ext::{AnyValueExt, DataFrameExt},
+        jump_list::JumpList,
+        polars_ext::{AnyValueExt, SeriesExt},
+        search::Highlight,
+        transform::{self, Footer},
         type_ext::ConstraintExt,
+        value_format::ValueFormat,
     },
     tui::component::Component,
 };
@@ -24,6 +40,9 @@ use crate::{
 pub struct Table {
     df: DataFrame,
     col_widths: Vec<Constraint>,
+    /// Whether each width was measured from the values rather than estimated
+    /// from the column name.
+    measured: Vec<bool>,
     col_offsets: Vec<u16>,
     col_space: u16,
     striped: bool,
@@ -32,58 +51,141 @@ pub struct Table {
     offset: usize,
     rendered_rows: usize,
     rendered_width: u16,
+    /// Area of the rendered rows, with the gutter, and the height of each row.
+    rows_area: Rect,
+    row_heights: Vec<usize>,
+    /// Rendered columns and the terminal columns their headers span.
+    header_spans: Vec<(usize, Range<u16>)>,
+    /// Column this frame was sorted by when pushed, and whether descending.
+    sort: Option<(String, bool)>,
     column_mode: ColumnMode,
     gutter_mode: GutterMode,
+    /// Row numbers of the frame this one was derived from, by row.
+    row_numbers: Option<Arc<[usize]>>,
+    /// Whether the gutter shows `row_numbers` rather than the row positions.
+    original_numbers: bool,
+    highlight: Option<Highlight>,
+    selected_column: Option<usize>,
+    visual_anchor: Option<usize>,
+    /// Names of the columns left out of rendering.
+    hidden: HashSet<String>,
+    /// Indices of the rendered columns; `col_offsets` are laid out over these.
+    visible: Vec<usize>,
+    /// Column widths set by hand, overriding the computed ones.
+    width_overrides: HashMap<String, u16>,
+    overflow: Overflow,
+    footer: Option<Footer>,
+    /// Footer value of every column, computed on the next render.
+    footer_values: Option<Vec<String>>,
+    heatmap: bool,
+    /// Min and max of every numeric column, computed on the next render.
+    heat_ranges: Option<Vec<Option<(f64, f64)>>>,
+    /// Whether the widths were computed for headers with dtype badges.
+    dtype_badges: bool,
+    /// Rows marked with `m{a-z}`.
+    marks: BTreeMap<char, usize>,
+    /// Rows jumped away from, walked with `Ctrl + o` / `Ctrl + i`.
+    jumps: JumpList<usize>,
 }
 
+/// Frames wider than this start with column widths estimated from the names,
+/// each measured once its column scrolls into view.
+const MEASURED_COLUMNS: usize = 256;
+
+/// Number of rows kept in the jump list.
+const JUMP_LIMIT: usize = 100;
+
+/// Rows moved by a step of the mouse wheel.
+const WHEEL_ROWS: usize = 3;
+
+/// Background of the smallest and the largest values of a heatmap column.
+const HEAT_LOW: (u8, u8, u8) = (32, 64, 112);
+const HEAT_HIGH: (u8, u8, u8) = (144, 48, 48);
+
 impl Table {
     pub fn new(df: DataFrame) -> Self {
         let col_space = 1;
-        let col_widths = df
-            .widths()
-            .into_iter()
-            .map(|u| Constraint::Length(u as u16))
-            .collect_vec();
+        let dtype_badges = config().show_dtype_badges();
+        let (col_widths, measured) = column_widths(&df, &HashMap::new(), dtype_badges);
         let col_offsets = col_offsets(&col_widths, col_space);
         let gutter_width = df.height().to_string().len() as u16;
         Self {
             col_widths,
+            measured,
             selected: None,
             col_offsets,
             offset: 0,
             rendered_rows: 0,
             rendered_width: 0,
+            rows_area: Rect::default(),
+            row_heights: Vec::new(),
+            header_spans: Vec::new(),
+            sort: None,
             column_mode: ColumnMode::Compact,
             striped: false,
             show_header: false,
             gutter_mode: GutterMode::Visible(gutter_width),
+            row_numbers: None,
+            original_numbers: false,
+            highlight: None,
+            selected_column: None,
+            visual_anchor: None,
+            hidden: HashSet::new(),
+            visible: (0..df.width()).collect(),
+            width_overrides: HashMap::new(),
+            overflow: Overflow::default(),
+            footer: None,
+            footer_values: None,
+            heatmap: false,
+            heat_ranges: None,
+            dtype_badges,
+            marks: BTreeMap::new(),
+            jumps: JumpList::new(JUMP_LIMIT),
             df,
             col_space,
         }
     }
 
     pub fn clone_with_data_frame(&self, df: DataFrame) -> Self {
-        let col_widths = df
-            .widths()
-            .into_iter()
-            .map(|u| Constraint::Length(u as u16))
-            .collect_vec();
-        let col_offsets = col_offsets(&col_widths, self.col_space);
+        let (col_widths, measured) = column_widths(&df, &self.width_overrides, self.dtype_badges);
         let gutter_width = df.height().to_string().len() as u16;
-        Self {
+        let mut table = Self {
             df,
             col_widths,
-            col_offsets,
+            measured,
+            col_offsets: Vec::new(),
             offset: 0,
             selected: self.selected,
             rendered_rows: self.rendered_rows,
             rendered_width: self.rendered_width,
+            rows_area: self.rows_area,
+            row_heights: Vec::new(),
+            header_spans: Vec::new(),
+            sort: None,
             column_mode: self.column_mode,
             striped: self.striped,
             show_header: self.show_header,
             gutter_mode: GutterMode::Visible(gutter_width),
+            row_numbers: None,
+            original_numbers: self.original_numbers,
+            highlight: None,
+            selected_column: self.selected_column,
+            visual_anchor: None,
+            hidden: self.hidden.clone(),
+            visible: Vec::new(),
+            width_overrides: self.width_overrides.clone(),
+            overflow: self.overflow,
+            footer: self.footer,
+            footer_values: None,
+            heatmap: self.heatmap,
+            heat_ranges: None,
+            dtype_badges: self.dtype_badges,
+            marks: BTreeMap::new(),
+            jumps: JumpList::new(JUMP_LIMIT),
             col_space: self.col_space,
-        }
+        };
+        table.refresh_columns();
+        table
     }
 
     pub fn striped(self) -> Self {
@@ -110,7 +212,7 @@ impl Table {
     pub fn with_col_space(self, col_space: u16) -> Self {
         Self {
             col_space,
-            col_offsets: col_offsets(&self.col_widths, col_space),
+            col_offsets: col_offsets(&visible_widths(&self.col_widths, &self.visible), col_space),
             ..self
         }
     }
@@ -149,16 +251,75 @@ impl Table {
 
     pub fn set_data_frame(&mut self, df: DataFrame) {
         self.df = df;
+        self.footer_values = None;
+        self.heat_ranges = None;
+    }
+
+    /// Replaces the frame by one holding the same rows, e.g. with a cell
+    /// edited, keeping their numbers when the height is unchanged.
+    pub fn replace_data_frame(&mut self, df: DataFrame) {
+        (self.col_widths, self.measured) =
+            column_widths(&df, &self.width_overrides, self.dtype_badges);
+        if df.height() != self.df.height() {
+            self.row_numbers = None;
+        }
+        self.df = df;
+        self.refresh_columns();
+        self.visual_anchor = None;
+        self.footer_values = None;
+        self.heat_ranges = None;
+        self.select(self.selected);
+    }
+
+    pub fn set_highlight(&mut self, highlight: Option<Highlight>) {
+        self.highlight = highlight;
     }
 
     pub fn set_gutter_visibility(&mut self, value: bool) {
         if value {
-            self.gutter_mode = GutterMode::Visible(self.df.height().to_string().len() as u16)
+            let last = match self.row_numbers.as_deref() {
+                Some(numbers) if self.original_numbers => {
+                    numbers.iter().max().copied().unwrap_or_default()
+                }
+                _ => self.df.height(),
+            };
+            self.gutter_mode = GutterMode::Visible(last.to_string().len() as u16)
         } else {
             self.gutter_mode = GutterMode::Hidden
         }
     }
 
+    /// Shows the numbers of the rows in the frame first loaded in the gutter,
+    /// rather than their positions.
+    pub fn set_original_numbers(&mut self, value: bool) {
+        self.original_numbers = value;
+    }
+
+    pub fn row_numbers(&self) -> Option<&[usize]> {
+        self.row_numbers.as_deref()
+    }
+
+    pub fn set_row_numbers(&mut self, numbers: Option<Arc<[usize]>>) {
+        self.row_numbers = numbers.filter(|numbers| numbers.len() == self.df.height());
+    }
+
+    /// Number of the row at `idx` in the frame first loaded, counted from one.
+    pub fn row_number(&self, idx: usize) -> usize {
+        self.row_numbers
+            .as_deref()
+            .and_then(|numbers| numbers.get(idx).copied())
+            .unwrap_or(idx + 1)
+    }
+
+    /// Position of the row numbered `number` in the frame first loaded, or
+    /// `None` when it was filtered out.
+    pub fn row_position(&self, number: usize) -> Option<usize> {
+        match self.row_numbers.as_deref() {
+            Some(numbers) => numbers.iter().position(|other| *other == number),
+            None => number.checked_sub(1).filter(|idx| *idx < self.df.height()),
+        }
+    }
+
     pub fn selected(&self) -> Option<usize> {
         self.selected
     }
@@ -170,6 +331,220 @@ impl Table {
         }
     }
 
+    /// Selects `idx`, recording the selected row in the jump list.
+    pub fn jump(&mut self, idx: usize) {
+        if let Some(selected) = self.selected.filter(|selected| *selected != idx) {
+            self.jumps.record(selected);
+        }
+        self.select(idx);
+    }
+
+    pub fn record_jump(&mut self, from: usize) {
+        if self.selected != Some(from) {
+            self.jumps.record(from);
+        }
+    }
+
+    /// Selects the row before the latest jump; `false` when there is none.
+    pub fn jump_back(&mut self) -> bool {
+        match self.jumps.back(self.selected.unwrap_or_default()) {
+            Some(row) => {
+                self.select(row);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Selects the row a jump back came from; `false` when there is none.
+    pub fn jump_forward(&mut self) -> bool {
+        match self.jumps.forward() {
+            Some(row) => {
+                self.select(row);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Marks the selected row with `mark`.
+    pub fn set_mark(&mut self, mark: char) -> Option<usize> {
+        let row = self.selected?;
+        self.marks.insert(mark, row);
+        Some(row)
+    }
+
+    pub fn marks(&self) -> &BTreeMap<char, usize> {
+        &self.marks
+    }
+
+    pub fn is_column_hidden(&self, name: &str) -> bool {
+        self.hidden.contains(name)
+    }
+
+    /// Hides or shows a column without touching the data frame.
+    pub fn set_column_hidden(&mut self, name: &str, hidden: bool) {
+        if hidden {
+            self.hidden.insert(name.to_owned());
+        } else {
+            self.hidden.remove(name);
+        }
+        self.refresh_columns();
+        if let Some(col) = self.selected_column {
+            self.select_column(col);
+        }
+    }
+
+    pub fn show_all_columns(&mut self) {
+        self.hidden.clear();
+        self.refresh_columns();
+    }
+
+    /// Overrides the computed width of a column, or restores it with `None`.
+    pub fn set_column_width(&mut self, name: &str, width: Option<u16>) {
+        match width {
+            Some(width) => {
+                self.width_overrides.insert(name.to_owned(), width.max(1));
+                if let Some(idx) = self.df.get_column_index(name) {
+                    self.col_widths[idx] = Constraint::Length(width.max(1));
+                }
+            }
+            None => {
+                self.width_overrides.remove(name);
+                (self.col_widths, self.measured) =
+                    column_widths(&self.df, &self.width_overrides, self.dtype_badges);
+            }
+        }
+        self.refresh_columns();
+    }
+
+    /// Widens or narrows the selected column by `delta` cells.
+    fn resize_selected_column(&mut self, delta: i16) {
+        if let Some(col) = self.selected_column
+            && let Some(column) = self.df.columns().get(col)
+        {
+            let width = self.col_widths[col].value().saturating_add_signed(delta);
+            self.set_column_width(&column.name().to_string(), Some(width));
+        }
+    }
+
+    /// Switches to the next way of rendering values longer than their column.
+    pub fn cycle_overflow(&mut self) -> Overflow {
+        self.overflow = match self.overflow {
+            Overflow::Truncate => Overflow::Ellipsis,
+            Overflow::Ellipsis => Overflow::Wrap,
+            Overflow::Wrap => Overflow::Truncate,
+        };
+        self.overflow
+    }
+
+    pub fn footer(&self) -> Option<Footer> {
+        self.footer
+    }
+
+    pub fn set_footer(&mut self, footer: Option<Footer>) {
+        self.footer = footer;
+        self.footer_values = None;
+    }
+
+    /// Turns background shading of numeric cells by their value on or off.
+    pub fn toggle_heatmap(&mut self) -> bool {
+        self.heatmap = !self.heatmap;
+        self.heatmap
+    }
+
+    /// Recomputes the rendered columns and their offsets.
+    fn refresh_columns(&mut self) {
+        self.visible = self
+            .df
+            .columns()
+            .iter()
+            .enumerate()
+            .filter(|(_, col)| !self.hidden.contains(col.name().as_str()))
+            .map(|(idx, _)| idx)
+            .collect();
+        self.col_offsets = col_offsets(
+            &visible_widths(&self.col_widths, &self.visible),
+            self.col_space,
+        );
+    }
+
+    /// Measures the columns at `positions` among the rendered ones whose width
+    /// is still estimated; returns whether any width changed.
+    fn measure_columns(&mut self, positions: RangeInclusive<usize>) -> bool {
+        let columns = self
+            .visible
+            .get(positions)
+            .unwrap_or_default()
+            .iter()
+            .copied()
+            .filter(|idx| !self.measured[*idx])
+            .collect_vec();
+        let mut changed = false;
+        for idx in columns {
+            let column = &self.df.columns()[idx];
+            let width = Constraint::Length(
+                column
+                    .as_materialized_series()
+                    .display_width()
+                    .max(header_text(column, self.dtype_badges).width()) as u16,
+            );
+            changed |= width != self.col_widths[idx];
+            self.col_widths[idx] = width;
+            self.measured[idx] = true;
+        }
+        if changed {
+            self.col_offsets = col_offsets(
+                &visible_widths(&self.col_widths, &self.visible),
+                self.col_space,
+            );
+        }
+        changed
+    }
+
+    /// Highlighted column while in column selection mode.
+    pub fn selected_column(&self) -> Option<usize> {
+        self.selected_column
+    }
+
+    /// Starts a visual row selection at the selected row, or ends it.
+    pub fn toggle_visual_selection(&mut self) {
+        self.visual_anchor = match self.visual_anchor {
+            Some(_) => None,
+            None => self.selected,
+        };
+    }
+
+    /// Rows between the visual selection anchor and the selected row.
+    pub fn visual_selection(&self) -> Option<RangeInclusive<usize>> {
+        let (anchor, selected) = self.visual_anchor.zip(self.selected)?;
+        Some(anchor.min(selected)..=anchor.max(selected))
+    }
+
+    /// Active `(row, column)` cell, when a column is selected.
+    pub fn selected_cell(&self) -> Option<(usize, usize)> {
+        self.selected.zip(self.selected_column)
+    }
+
+    /// Selects the column at `idx`, or the next visible one when it is hidden.
+    pub fn select_column(&mut self, idx: usize) {
+        self.selected_column = match self.visible.binary_search(&idx) {
+            Ok(pos) | Err(pos) => self.visible.get(pos).or(self.visible.last()).copied(),
+        };
+    }
+
+    /// Enters column selection mode on the leftmost visible column, or leaves it.
+    pub fn toggle_column_selection(&mut self) {
+        self.selected_column = match (self.selected_column, self.column_mode) {
+            (Some(_), _) => None,
+            (None, ColumnMode::Compact) => self.visible.first().copied(),
+            (None, ColumnMode::Expanded(offset)) => self
+                .visible
+                .get(column_index(&self.col_offsets, &offset))
+                .copied(),
+        };
+    }
+
     pub fn fits_in_page(&self) -> bool {
         self.minimum_compact_width() <= self.rendered_width
     }
@@ -193,6 +568,31 @@ impl Table {
         }
     }
 
+    /// Applies `motion` `count` times; counts past the size of the frame
+    /// change nothing more.
+    pub fn move_by(&mut self, motion: Motion, count: usize) {
+        let count = count.clamp(1, self.df.height().max(self.df.width()).max(1));
+        let column_mode = self.selected_column.is_some();
+        for _ in 0..count {
+            match motion {
+                Motion::Left | Motion::PrevColumn if column_mode => self.select_prev_column(),
+                Motion::Right | Motion::NextColumn if column_mode => self.select_next_column(),
+                Motion::Up => self.select_up(),
+                Motion::Down => self.select_down(),
+                Motion::Left => self.scroll_left(),
+                Motion::Right => self.scroll_right(),
+                Motion::PrevColumn => self.scroll_to_left_column(),
+                Motion::NextColumn => self.scroll_to_right_column(),
+                Motion::PageUp => self.page_up(),
+                Motion::PageDown => self.page_down(),
+                Motion::HalfPageUp => self.half_page_up(),
+                Motion::HalfPageDown => self.half_page_down(),
+                Motion::PrevColumns => self.page_columns(false),
+                Motion::NextColumns => self.page_columns(true),
+            }
+        }
+    }
+
     fn select_up(&mut self) {
         if let Some(selected) = self.selected {
             self.select(selected.saturating_sub(1));
@@ -210,11 +610,11 @@ impl Table {
     }
 
     fn select_first(&mut self) {
-        self.select(0);
+        self.jump(0);
     }
 
     fn select_last(&mut self) {
-        self.select(self.df.height().saturating_sub(1));
+        self.jump(self.df.height().saturating_sub(1));
     }
 
     fn page_up(&mut self) {
@@ -229,6 +629,54 @@ impl Table {
         }
     }
 
+    fn select_prev_column(&mut self) {
+        if let Some(col) = self.selected_column
+            && let Some(prev) = self.visible.iter().rev().find(|idx| **idx < col)
+        {
+            self.selected_column = Some(*prev);
+        }
+    }
+
+    fn select_next_column(&mut self) {
+        if let Some(col) = self.selected_column
+            && let Some(next) = self.visible.iter().find(|idx| **idx > col)
+        {
+            self.selected_column = Some(*next);
+        }
+    }
+
+    /// Swaps the selected column with its visible neighbour, reordering the
+    /// columns of the data frame.
+    fn move_selected_column(&mut self, right: bool) {
+        let Some(col) = self.selected_column else {
+            return;
+        };
+        let other = if right {
+            self.visible.iter().find(|idx| **idx > col)
+        } else {
+            self.visible.iter().rev().find(|idx| **idx < col)
+        };
+        let Some(other) = other.copied() else {
+            return;
+        };
+        let mut names = self
+            .df
+            .get_column_names()
+            .into_iter()
+            .cloned()
+            .collect_vec();
+        names.swap(col, other);
+        if let Ok(df) = self.df.select(names) {
+            self.df = df;
+            self.footer_values = None;
+            self.heat_ranges = None;
+            self.col_widths.swap(col, other);
+            self.measured.swap(col, other);
+            self.refresh_columns();
+            self.selected_column = Some(other);
+        }
+    }
+
     fn scroll_left(&mut self) {
         if let ColumnMode::Expanded(st) = &mut self.column_mode {
             *st = st.saturating_sub(1)
@@ -265,6 +713,37 @@ impl Table {
         }
     }
 
+    /// Scrolls a screenful of columns left or right, starting the view at a
+    /// column and carrying the selected column along.
+    fn page_columns(&mut self, right: bool) {
+        if let ColumnMode::Expanded(offset) = &mut self.column_mode {
+            let target = if right {
+                offset.saturating_add(self.rendered_width)
+            } else {
+                offset.saturating_sub(self.rendered_width)
+            };
+            let pos = column_index(&self.col_offsets, &target);
+            *offset = self.col_offsets.get(pos).copied().unwrap_or_default();
+            if self.selected_column.is_some() {
+                self.selected_column = self.visible.get(pos).copied();
+            }
+        }
+    }
+
+    /// Scrolls horizontally so the column at `idx` sits in the middle of the view.
+    pub fn scroll_to_column(&mut self, idx: usize) {
+        if let ColumnMode::Expanded(offset) = &mut self.column_mode
+            && let Ok(pos) = self.visible.binary_search(&idx)
+            && let (Some(start), Some(end)) =
+                (self.col_offsets.get(pos), self.col_offsets.get(pos + 1))
+        {
+            *offset = start
+                .add(end)
+                .div(2)
+                .saturating_sub(self.rendered_width.div(2));
+        }
+    }
+
     fn half_page_up(&mut self) {
         if let Some(selected) = self.selected {
             self.select(selected.saturating_sub(self.rendered_rows.div(2)));
@@ -277,10 +756,16 @@ impl Table {
         }
     }
 
-    fn gutter_item(&self, idx: usize) -> ListItem<'_> {
+    fn gutter_item(&self, idx: usize, height: usize) -> ListItem<'_> {
+        let number = if self.original_numbers {
+            self.row_number(idx)
+        } else {
+            idx + 1
+        };
         ListItem::new(Text::raw(format!(
-            "  {:>w$}  ",
-            idx + 1,
+            "  {:>w$}  {}",
+            number,
+            "\n".repeat(height.saturating_sub(1)),
             w = self.gutter_mode.width().into()
         )))
         .style(theme().gutter(idx))
@@ -304,8 +789,30 @@ impl Table {
         }
     }
 
+    pub fn sort(&self) -> Option<(&str, bool)> {
+        self.sort
+            .as_ref()
+            .map(|(column, descending)| (column.as_str(), *descending))
+    }
+
+    pub fn set_sort(&mut self, column: String, descending: bool) {
+        self.sort = Some((column, descending));
+    }
+
+    /// Row drawn at the terminal line `y`.
+    fn row_at(&self, y: u16) -> Option<usize> {
+        let mut top = self.rows_area.y as usize;
+        self.row_heights
+            .iter()
+            .enumerate()
+            .find_map(|(idx, height)| {
+                top += height;
+                (usize::from(y) < top).then_some(self.offset + idx)
+            })
+    }
+
     fn minimum_compact_width(&self) -> u16 {
-        let col_count = self.df.width() as u16;
+        let col_count = self.visible.len() as u16;
         col_count + (col_count.saturating_sub(1) * self.col_space)
     }
 }
@@ -317,34 +824,176 @@ impl Component for Table {
         buf: &mut ratatui::prelude::Buffer,
         focus_state: super::component::FocusState,
     ) {
-        let height = if self.show_header {
-            area.height.saturating_sub(1)
-        } else {
-            area.height
-        } as usize;
+        let height = area
+            .height
+            .saturating_sub(self.show_header.into())
+            .saturating_sub(self.footer.is_some().into()) as usize;
         self.rendered_rows = height;
         self.rendered_width = area.width;
 
+        if self.dtype_badges != config().show_dtype_badges() {
+            self.dtype_badges = !self.dtype_badges;
+            (self.col_widths, self.measured) =
+                column_widths(&self.df, &self.width_overrides, self.dtype_badges);
+            self.refresh_columns();
+        }
+
         self.selected = self.selected.map(|selected| selected.min(self.df.height()));
+        self.selected_column = self
+            .selected_column
+            .map(|col| col.min(self.df.width().saturating_sub(1)));
+        // position of the selected column among the rendered ones
+        let selected_position = self
+            .selected_column
+            .and_then(|col| self.visible.binary_search(&col).ok());
+
+        let (gutter_area, table_area) = self.gutter_table_area(area);
+        let selection = self.visual_selection();
+
+        if table_area.width < self.minimum_compact_width()
+            && matches!(self.column_mode, ColumnMode::Compact)
+        {
+            self.column_mode = ColumnMode::Expanded(0);
+        }
+
+        // measure the columns coming into view until the slice settles
+        let (col_start, col_end) = loop {
+            let (col_start, col_end) = match &mut self.column_mode {
+                ColumnMode::Compact => (0, self.visible.len().saturating_sub(1)),
+                ColumnMode::Expanded(x) => {
+                    let total_width = self
+                        .col_offsets
+                        .last()
+                        .copied()
+                        .unwrap_or(0)
+                        .max(table_area.width);
+                    // keep the selected column in view
+                    if let Some(col) = selected_position
+                        && let (Some(start), Some(end)) =
+                            (self.col_offsets.get(col), self.col_offsets.get(col + 1))
+                    {
+                        *x = (*x).min(*start).max(end.saturating_sub(table_area.width));
+                    }
+                    *x = (*x).min(total_width.saturating_sub(table_area.width));
+                    (
+                        column_index(&self.col_offsets, x),
+                        column_index(&self.col_offsets, &x.add(table_area.width)),
+                    )
+                }
+            };
+            if !self.measure_columns(col_start..=col_end) {
+                break (col_start, col_end);
+            }
+        };
+        let columns = self.visible.get(col_start..=col_end).unwrap_or_default();
+        let col_widths = visible_widths(&self.col_widths, columns);
+        self.header_spans = match self.column_mode {
+            ColumnMode::Compact => Layout::horizontal(col_widths.iter().copied())
+                .flex(Flex::Start)
+                .spacing(self.col_space)
+                .split(table_area)
+                .iter()
+                .zip(columns)
+                .map(|(rect, col)| (*col, rect.x..rect.right()))
+                .collect(),
+            ColumnMode::Expanded(x) => columns
+                .iter()
+                .zip(self.col_offsets.get(col_start..).unwrap_or_default())
+                .zip(&col_widths)
+                .map(|((col, start), width)| {
+                    let left = (table_area.x + start).saturating_sub(x);
+                    let right = (table_area.x + start + width.value()).saturating_sub(x);
+                    (*col, left.max(table_area.x)..right.min(table_area.right()))
+                })
+                .collect(),
+        };
 
         if let Some(selected) = self.selected {
             self.offset = self
                 .offset
                 .clamp(selected.saturating_sub(height.saturating_sub(1)), selected)
                 .min(self.df.height().saturating_sub(height));
+            if self.overflow == Overflow::Wrap {
+                // scroll further down until the wrapped rows up to the selected one fit
+                let mut used = (self.offset..=selected)
+                    .map(|row| row_height(&self.df, columns, &col_widths, row))
+                    .sum::<usize>();
+                while used > height && self.offset < selected {
+                    used -= row_height(&self.df, columns, &col_widths, self.offset);
+                    self.offset += 1;
+                }
+            }
         } else {
             self.offset = self.offset.min(self.df.height().saturating_sub(height))
         }
 
-        let (gutter_area, table_area) = self.gutter_table_area(area);
+        let row_heights = match self.overflow {
+            Overflow::Wrap => (self.offset..self.df.height())
+                .map(|row| row_height(&self.df, columns, &col_widths, row))
+                .scan(0, |used, row_height| {
+                    (*used < height).then(|| {
+                        *used += row_height;
+                        row_height
+                    })
+                })
+                .collect_vec(),
+            Overflow::Truncate | Overflow::Ellipsis => {
+                vec![1; height.min(self.df.height().saturating_sub(self.offset))]
+            }
+        };
+        if self.overflow == Overflow::Wrap {
+            self.rendered_rows = row_heights.len();
+        }
+        self.rows_area = Rect {
+            y: area.y + u16::from(self.show_header),
+            height: height as u16,
+            ..area
+        };
+        self.row_heights = row_heights.clone();
+
+        if let Some(footer) = self.footer
+            && self.footer_values.is_none()
+        {
+            self.footer_values = Some(footer_values(&self.df, footer));
+        }
+        let footer = self
+            .footer_values
+            .as_ref()
+            .map(|values| columns.iter().map(|idx| values[*idx].clone()).collect_vec());
+
+        if self.heatmap && self.heat_ranges.is_none() {
+            self.heat_ranges = Some(heat_ranges(&self.df));
+        }
+        let heat = self
+            .heat_ranges
+            .as_ref()
+            .filter(|_| self.heatmap)
+            .map(|ranges| columns.iter().map(|idx| ranges[*idx]).collect_vec());
 
         if let Some(gutter_area) = gutter_area {
+            let gutter_area = match self.footer {
+                Some(footer) => {
+                    let [gutter_area, label_area] =
+                        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)])
+                            .areas(gutter_area);
+                    buf.set_line(
+                        label_area.x,
+                        label_area.y,
+                        &Line::raw(format!("  {footer}")).style(theme().table_header()),
+                        label_area.width,
+                    );
+                    gutter_area
+                }
+                None => gutter_area,
+            };
             List::default()
                 .items(
-                    (self.offset..(self.offset + height).min(self.df.height()))
-                        .map(|idx| self.gutter_item(idx)),
+                    row_heights
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, row_height)| self.gutter_item(self.offset + idx, *row_height)),
                 )
-                .highlight_style(theme().row_highlighted())
+                .highlight_style(config().rows_config().highlight_style())
                 .render(
                     gutter_area,
                     buf,
@@ -356,82 +1005,55 @@ impl Component for Table {
                 );
         }
 
-        if table_area.width < self.minimum_compact_width()
-            && matches!(self.column_mode, ColumnMode::Compact)
-        {
-            self.column_mode = ColumnMode::Expanded(0);
-        }
+        let df = select_columns(&self.df, columns).slice(self.offset as i64, row_heights.len());
+        let table = build_table(
+            &df,
+            &col_widths,
+            self.col_space,
+            self.show_header,
+            self.striped,
+            self.offset,
+            col_start,
+            self.highlight.as_ref(),
+            selection.as_ref(),
+            self.overflow,
+            footer,
+            heat.as_deref(),
+            self.sort(),
+        );
+        let mut state = TableState::default()
+            .with_selected(if focus_state.is_focused() {
+                self.selected.map(|s| s.saturating_sub(self.offset))
+            } else {
+                None
+            })
+            .with_selected_column(
+                selected_position
+                    .filter(|col| (col_start..=col_end).contains(col))
+                    .map(|col| col - col_start),
+            );
 
-        match &mut self.column_mode {
-            ColumnMode::Compact => {
-                let df = self.df.slice(self.offset as i64, height);
-                let table = build_table(
-                    &df,
-                    &self.col_widths,
-                    self.col_space,
-                    self.show_header,
-                    self.striped,
-                    self.offset,
-                    0,
-                );
-                table.render(
-                    table_area,
-                    buf,
-                    &mut TableState::default().with_selected(if focus_state.is_focused() {
-                        self.selected.map(|s| s.saturating_sub(self.offset))
-                    } else {
-                        None
-                    }),
-                );
-            }
+        match self.column_mode {
+            ColumnMode::Compact => table.render(table_area, buf, &mut state),
             ColumnMode::Expanded(x) => {
-                let total_width = self
+                let start = self.col_offsets.get(col_start).copied().unwrap_or_default();
+                let end = self
                     .col_offsets
-                    .last()
+                    .get(col_end + 1)
                     .copied()
-                    .unwrap_or(0)
-                    .max(table_area.width);
-                *x = (*x).min(total_width.saturating_sub(table_area.width));
-                let col_start = column_index(&self.col_offsets, x);
-                let col_end = column_index(&self.col_offsets, &x.add(table_area.width));
-                let df = self
-                    .df
-                    .select(&self.df.get_column_names()[col_start..=col_end])
-                    .unwrap()
-                    .slice(self.offset as i64, height);
-                let table = build_table(
-                    &df,
-                    &self.col_widths[col_start..=col_end],
-                    self.col_space,
-                    self.show_header,
-                    self.striped,
-                    self.offset,
-                    col_start,
-                );
-                let width = (self.col_offsets[col_end + 1] - self.col_offsets[col_start])
-                    .max(table_area.width);
+                    .unwrap_or_default();
                 let size = ratatui::layout::Size {
-                    width,
+                    width: end.saturating_sub(start).max(table_area.width),
                     height: table_area.height,
                 };
                 let mut scroll_area =
                     ScrollView::new(size).scrollbars_visibility(ScrollbarVisibility::Never);
-                scroll_area.render_stateful_widget(
-                    table,
-                    scroll_area.area(),
-                    &mut TableState::default().with_selected(if focus_state.is_focused() {
-                        self.selected.map(|s| s.saturating_sub(self.offset))
-                    } else {
-                        None
-                    }),
-                );
+                scroll_area.render_stateful_widget(table, scroll_area.area(), &mut state);
                 scroll_area.render(
                     table_area,
                     buf,
                     &mut ScrollViewState::with_offset(Position {
-                        x: x.saturating_sub(
-                            self.col_offsets.get(col_start).copied().unwrap_or_default(),
-                        ),
+                        x: x.saturating_sub(start),
                         y: 0,
                     }),
                 );
@@ -440,27 +1062,57 @@ impl Component for Table {
     }
 
     fn handle(&mut self, event: KeyEvent) -> bool {
-        match (event.code, event.modifiers) {
-            (KeyCode::Up, KeyModifiers::NONE)
-            | (KeyCode::Char('k'), KeyModifiers::NONE)
-            | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
-                self.select_up();
-                true
-            }
-            (KeyCode::Down, KeyModifiers::NONE)
-            | (KeyCode::Char('j'), KeyModifiers::NONE)
-            | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
-                self.select_down();
-                true
-            }
-            (KeyCode::Left, KeyModifiers::NONE) | (KeyCode::Char('h'), KeyModifiers::NONE) => {
-                self.scroll_left();
-                true
-            }
-            (KeyCode::Right, KeyModifiers::NONE) | (KeyCode::Char('l'), KeyModifiers::NONE) => {
-                self.scroll_right();
-                true
+        if self.visual_anchor.is_some() && event.code == KeyCode::Esc {
+            self.visual_anchor = None;
+            return true;
+        }
+        if let Some(motion) = Motion::from_key(&event) {
+            self.move_by(motion, 1);
+            return true;
+        }
+        if self.selected_column.is_some() {
+            match (event.code, event.modifiers) {
+                (KeyCode::Char('_'), _) => {
+                    self.select_column(0);
+                    return true;
+                }
+                (KeyCode::Char('+'), _) => {
+                    self.resize_selected_column(1);
+                    return true;
+                }
+                (KeyCode::Char('-'), _) => {
+                    self.resize_selected_column(-1);
+                    return true;
+                }
+                (KeyCode::Char('='), _) => {
+                    if let Some(column) = self
+                        .selected_column
+                        .and_then(|col| self.df.columns().get(col))
+                    {
+                        self.set_column_width(&column.name().to_string(), None);
+                    }
+                    return true;
+                }
+                (KeyCode::Char('<'), _) => {
+                    self.move_selected_column(false);
+                    return true;
+                }
+                (KeyCode::Char('>'), _) => {
+                    self.move_selected_column(true);
+                    return true;
+                }
+                (KeyCode::Char('$'), _) => {
+                    self.select_column(self.df.width().saturating_sub(1));
+                    return true;
+                }
+                (KeyCode::Esc, KeyModifiers::NONE) => {
+                    self.selected_column = None;
+                    return true;
+                }
+                _ => (),
             }
+        }
+        match (event.code, event.modifiers) {
             (KeyCode::Home, KeyModifiers::NONE) | (KeyCode::Char('g'), KeyModifiers::NONE) => {
                 self.select_first();
                 true
@@ -469,31 +1121,6 @@ impl Component for Table {
                 self.select_last();
                 true
             }
-            (KeyCode::PageUp, KeyModifiers::NONE) | (KeyCode::Char('b'), KeyModifiers::CONTROL) => {
-                self.page_up();
-                true
-            }
-            (KeyCode::PageDown, KeyModifiers::NONE)
-            | (KeyCode::Char('f'), KeyModifiers::CONTROL) => {
-                self.page_down();
-                true
-            }
-            (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
-                self.half_page_up();
-                true
-            }
-            (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
-                self.half_page_down();
-                true
-            }
-            (KeyCode::Char('w'), KeyModifiers::NONE) => {
-                self.scroll_to_right_column();
-                true
-            }
-            (KeyCode::Char('b'), KeyModifiers::NONE) => {
-                self.scroll_to_left_column();
-                true
-            }
             (KeyCode::Char('_'), _) => {
                 self.scroll_to_first_column();
                 true
@@ -505,6 +1132,109 @@ impl Component for Table {
             _ => false,
         }
     }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        let area = self.rows_area;
+        if event.column < area.x || event.column >= area.right() {
+            return false;
+        }
+        match event.kind {
+            MouseEventKind::ScrollUp => (0..WHEEL_ROWS).for_each(|_| self.select_up()),
+            MouseEventKind::ScrollDown => (0..WHEEL_ROWS).for_each(|_| self.select_down()),
+            MouseEventKind::ScrollLeft => self.scroll_left(),
+            MouseEventKind::ScrollRight => self.scroll_right(),
+            MouseEventKind::Down(MouseButton::Left)
+                if self.show_header && event.row + 1 == area.y =>
+            {
+                match self
+                    .header_spans
+                    .iter()
+                    .find(|(_, span)| span.contains(&event.column))
+                    .and_then(|(col, _)| self.df.columns().get(*col))
+                {
+                    Some(column) => Message::PaneCycleSort(column.name().to_string()).enqueue(),
+                    None => return false,
+                }
+            }
+            MouseEventKind::Down(MouseButton::Left) => match self.row_at(event.row) {
+                Some(row) if area.contains(Position::new(event.column, event.row)) => {
+                    self.select(row)
+                }
+                _ => return false,
+            },
+            // dragging past the top or bottom edge scrolls
+            MouseEventKind::Drag(MouseButton::Left) => match self.row_at(event.row) {
+                _ if event.row < area.y => self.select_up(),
+                Some(row) => self.select(row),
+                None => self.select_down(),
+            },
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Movement of the table bound to a key, which a count typed before the key
+/// repeats, e.g. `15j`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// `b`: the column to the left, or the previous column in column selection mode.
+    PrevColumn,
+    /// `w`: the column to the right, or the next column in column selection mode.
+    NextColumn,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    /// `[`: a screenful of columns to the left.
+    PrevColumns,
+    /// `]`: a screenful of columns to the right.
+    NextColumns,
+}
+
+impl Motion {
+    pub fn from_key(event: &KeyEvent) -> Option<Motion> {
+        Some(match (event.code, event.modifiers) {
+            (KeyCode::Up, KeyModifiers::NONE)
+            | (KeyCode::Char('k'), KeyModifiers::NONE)
+            | (KeyCode::Char('p'), KeyModifiers::CONTROL) => Motion::Up,
+            (KeyCode::Down, KeyModifiers::NONE)
+            | (KeyCode::Char('j'), KeyModifiers::NONE)
+            | (KeyCode::Char('n'), KeyModifiers::CONTROL) => Motion::Down,
+            (KeyCode::Left, KeyModifiers::NONE) | (KeyCode::Char('h'), KeyModifiers::NONE) => {
+                Motion::Left
+            }
+            (KeyCode::Right, KeyModifiers::NONE) | (KeyCode::Char('l'), KeyModifiers::NONE) => {
+                Motion::Right
+            }
+            (KeyCode::Char('b'), KeyModifiers::NONE) => Motion::PrevColumn,
+            (KeyCode::Char('w'), KeyModifiers::NONE) => Motion::NextColumn,
+            (KeyCode::PageUp, KeyModifiers::NONE) | (KeyCode::Char('b'), KeyModifiers::CONTROL) => {
+                Motion::PageUp
+            }
+            (KeyCode::PageDown, KeyModifiers::NONE)
+            | (KeyCode::Char('f'), KeyModifiers::CONTROL) => Motion::PageDown,
+            (KeyCode::Char('u'), KeyModifiers::CONTROL) => Motion::HalfPageUp,
+            (KeyCode::Char('d'), KeyModifiers::CONTROL) => Motion::HalfPageDown,
+            (KeyCode::Char('['), _) => Motion::PrevColumns,
+            (KeyCode::Char(']'), _) => Motion::NextColumns,
+            _ => return None,
+        })
+    }
+}
+
+/// How cell values longer than their column render.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display)]
+pub enum Overflow {
+    #[default]
+    Truncate,
+    Ellipsis,
+    /// Wraps values onto more lines, making their rows taller.
+    Wrap,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -541,14 +1271,130 @@ fn col_offsets(col_widths: &[Constraint], col_space: u16) -> Vec<u16> {
                         c.value()
                     }
                 })
-                .scan(0, |s, u| {
-                    *s += u;
+                .scan(0_u16, |s, u| {
+                    *s = s.saturating_add(u);
                     Some(*s)
                 }),
         )
         .collect_vec()
 }
 
+/// Footer value of every column, blank for the columns without one.
+fn footer_values(df: &DataFrame, footer: Footer) -> Vec<String> {
+    let totals = transform::footer(df, footer).unwrap_or_default();
+    df.get_column_names()
+        .into_iter()
+        .map(|name| {
+            totals
+                .column(name)
+                .ok()
+                .and_then(|column| column.get(0).ok())
+                .map(|value| value.into_single_line())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Min and max of every numeric column, `None` for the other columns.
+fn heat_ranges(df: &DataFrame) -> Vec<Option<(f64, f64)>> {
+    df.columns()
+        .iter()
+        .map(|column| {
+            column
+                .dtype()
+                .is_primitive_numeric()
+                .then(|| column.cast(&DataType::Float64).ok())
+                .flatten()
+                .and_then(|column| column.f64().ok().and_then(|values| values.min_max()))
+        })
+        .collect()
+}
+
+/// Background shading `value` by where it falls between `min` and `max`.
+fn heat_style(value: f64, (min, max): (f64, f64)) -> Style {
+    let ratio = if max > min {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    } else {
+        0.5
+    };
+    Style::new().bg(heat_color(ratio))
+}
+
+/// Color of the heatmap gradient at `ratio`, from 0 (blue) to 1 (red).
+pub fn heat_color(ratio: f64) -> Color {
+    let ratio = ratio.clamp(0.0, 1.0);
+    let blend = |low: u8, high: u8| (low as f64 + (high as f64 - low as f64) * ratio).round() as u8;
+    Color::Rgb(
+        blend(HEAT_LOW.0, HEAT_HIGH.0),
+        blend(HEAT_LOW.1, HEAT_HIGH.1),
+        blend(HEAT_LOW.2, HEAT_HIGH.2),
+    )
+}
+
+/// Width of every column and whether it was measured; past `MEASURED_COLUMNS`
+/// columns, widths not set by hand are estimated from the headers.
+fn column_widths(
+    df: &DataFrame,
+    overrides: &HashMap<String, u16>,
+    dtype_badges: bool,
+) -> (Vec<Constraint>, Vec<bool>) {
+    let estimate = df.width() > MEASURED_COLUMNS;
+    df.columns()
+        .iter()
+        .map(|col| {
+            let header = header_text(col, dtype_badges).width();
+            let format = config().value_format(col.name());
+            match overrides.get(col.name().as_str()) {
+                Some(width) => (Constraint::Length(*width), true),
+                None if estimate => (Constraint::Length(header as u16), false),
+                None if format != ValueFormat::default() => (
+                    Constraint::Length(
+                        col.as_materialized_series()
+                            .iter()
+                            .map(|value| formatted(&format, value).width())
+                            .max()
+                            .unwrap_or_default()
+                            .max(header) as u16,
+                    ),
+                    true,
+                ),
+                None => (
+                    Constraint::Length(
+                        col.as_materialized_series().display_width().max(header) as u16
+                    ),
+                    true,
+                ),
+            }
+        })
+        .unzip()
+}
+
+/// Text of a value in the table, with the display options of its column.
+fn formatted(format: &ValueFormat, value: AnyValue) -> String {
+    format
+        .format(&value)
+        .unwrap_or_else(|| value.into_single_line())
+}
+
+/// Header of a column, followed by a badge with its data type when `dtype_badge` is set.
+fn header_text(column: &Column, dtype_badge: bool) -> String {
+    if dtype_badge {
+        format!("{} \u{27e8}{}\u{27e9}", column.name(), column.dtype())
+    } else {
+        column.name().to_string()
+    }
+}
+
+fn visible_widths(col_widths: &[Constraint], visible: &[usize]) -> Vec<Constraint> {
+    visible.iter().map(|idx| col_widths[*idx]).collect_vec()
+}
+
+fn select_columns(df: &DataFrame, columns: &[usize]) -> DataFrame {
+    let names = df.get_column_names();
+    df.select(columns.iter().map(|idx| names[*idx]).collect_vec())
+        .unwrap()
+}
+
 fn column_index(col_offsets: &[u16], offset: &u16) -> usize {
     // col_offsets index: 0    1    2    3    4
     // col_offsets      : 0---10---20---30---40
@@ -573,6 +1419,161 @@ fn next_column_offset(col_offsets: &[u16], offset: &u16) -> u16 {
         .unwrap_or_default()
 }
 
+/// Lines of a cell value laid out in `width` columns, each paired with the
+/// index of its first character in `text`.
+fn fitted_lines(text: &str, width: usize, overflow: Overflow) -> Vec<(usize, String)> {
+    match overflow {
+        Overflow::Truncate => vec![(0, text.lines().next().unwrap_or_default().to_owned())],
+        Overflow::Ellipsis => vec![(0, ellipsize(text, width))],
+        Overflow::Wrap => wrap(text, width),
+    }
+}
+
+/// First line of `text`, ending in `…` when anything is cut off.
+fn ellipsize(text: &str, width: usize) -> String {
+    let first = text.lines().next().unwrap_or_default();
+    if first.len() == text.len() && first.width() <= width {
+        return first.to_owned();
+    }
+    let mut used = 0;
+    first
+        .chars()
+        .take_while(|c| {
+            used += c.width().unwrap_or(0);
+            used < width
+        })
+        .chain(['…'])
+        .collect()
+}
+
+/// Breaks `text` at newlines and wherever it exceeds `width` columns.
+fn wrap(text: &str, width: usize) -> Vec<(usize, String)> {
+    let mut lines = vec![(0, String::new())];
+    let mut used = 0;
+    for (idx, c) in text.chars().enumerate() {
+        let char_width = c.width().unwrap_or(0);
+        if c == '\n' {
+            lines.push((idx + 1, String::new()));
+            used = 0;
+            continue;
+        }
+        if used + char_width > width && used > 0 {
+            lines.push((idx, String::new()));
+            used = 0;
+        }
+        if let Some((_, line)) = lines.last_mut() {
+            line.push(c);
+        }
+        used += char_width;
+    }
+    lines
+}
+
+/// Number of lines the wrapped values of `row` take in the given columns.
+fn row_height(df: &DataFrame, columns: &[usize], col_widths: &[Constraint], row: usize) -> usize {
+    df.get(row)
+        .and_then(|values| {
+            columns
+                .iter()
+                .zip(col_widths)
+                .map(|(idx, width)| {
+                    let format = config().value_format(df.get_column_names()[*idx]);
+                    let text = formatted(&format, values[*idx].clone());
+                    wrap(&text, width.value().into()).len()
+                })
+                .max()
+        })
+        .unwrap_or(1)
+}
+
+/// Renders `line`, emphasizing the characters whose index (counted from
+/// `start`) is in `indices`.
+fn highlighted_line(line: String, indices: &[usize], start: usize) -> Line<'static> {
+    if indices.is_empty() {
+        return Line::raw(line);
+    }
+    let mut style = theme()
+        .text_highlighted()
+        .add_modifier(Modifier::UNDERLINED);
+    style.bg = None;
+    Line::from(
+        line.chars()
+            .enumerate()
+            .chunk_by(|(idx, _)| indices.binary_search(&(start + idx)).is_ok())
+            .into_iter()
+            .map(|(matched, chars)| {
+                let fragment = chars.map(|(_, c)| c).collect::<String>();
+                if matched {
+                    Span::styled(fragment, style)
+                } else {
+                    Span::raw(fragment)
+                }
+            })
+            .collect_vec(),
+    )
+}
+
+/// Striping and highlight of the table rows, from the `[rows]` section of the config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RowsConfig {
+    /// Whether the rows alternate their background.
+    pub striped: bool,
+    /// Number of consecutive rows sharing a background.
+    pub stripe_period: usize,
+    /// Backgrounds to alternate instead of the ones of the theme.
+    pub stripe_colors: Vec<Color>,
+    /// Strength of the selected row highlight, as a percentage.
+    pub highlight_intensity: u8,
+}
+
+impl Default for RowsConfig {
+    fn default() -> Self {
+        Self {
+            striped: true,
+            stripe_period: 1,
+            stripe_colors: Vec::new(),
+            highlight_intensity: 100,
+        }
+    }
+}
+
+impl RowsConfig {
+    fn row_style(&self, row: usize, striped: bool) -> Style {
+        let stripe = if striped && self.striped {
+            row / self.stripe_period.max(1)
+        } else {
+            0
+        };
+        match self.stripe_colors.as_slice() {
+            [] => theme().row(stripe),
+            colors => theme().row(0).bg(colors[stripe % colors.len()]),
+        }
+    }
+
+    /// Highlight of the theme blended into the row background below 100%,
+    /// keeping the row text color below 50%.
+    fn highlight_style(&self) -> Style {
+        let highlight = theme().row_highlighted();
+        let row = self.row_style(0, false);
+        let intensity = self.highlight_intensity.min(100) as u16;
+        match (highlight.bg, row.bg) {
+            (Some(Color::Rgb(r1, g1, b1)), Some(Color::Rgb(r0, g0, b0))) if intensity < 100 => {
+                let mix = |from: u8, to: u8| {
+                    ((from as u16 * (100 - intensity) + to as u16 * intensity) / 100) as u8
+                };
+                let style = highlight.bg(Color::Rgb(mix(r0, r1), mix(g0, g1), mix(b0, b1)));
+                match row.fg {
+                    Some(fg) if intensity < 50 => style.fg(fg),
+                    _ => style,
+                }
+            }
+            _ => highlight,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn build_table<'a>(
     df: &'a DataFrame,
     col_widths: &[Constraint],
@@ -581,11 +1582,42 @@ fn build_table<'a>(
     striped: bool,
     offset_row: usize,
     offset_col: usize,
+    highlight: Option<&Highlight>,
+    selection: Option<&RangeInclusive<usize>>,
+    overflow: Overflow,
+    footer: Option<Vec<String>>,
+    heat: Option<&[Option<(f64, f64)>]>,
+    sort: Option<(&str, bool)>,
 ) -> ratatui::widgets::Table<'a> {
+    let diff = df
+        .column(transform::DIFF)
+        .ok()
+        .and_then(|column| column.str().ok());
+    let null_marker = config()
+        .show_null_marker()
+        .then(|| config().null_marker().clone());
+    let dtype_badges = config().show_dtype_badges();
+    let rows_config = config().rows_config().clone();
+    let column_styles = df
+        .columns()
+        .iter()
+        .map(|col| config().column_style(col.name(), col.dtype()))
+        .collect_vec();
+    let formats = df
+        .columns()
+        .iter()
+        .map(|col| config().value_format(col.name()))
+        .collect_vec();
     let mut table = ratatui::widgets::Table::default()
         .widths(col_widths)
         .style(theme().text())
-        .row_highlight_style(theme().row_highlighted())
+        .row_highlight_style(rows_config.highlight_style())
+        .column_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .cell_highlight_style(
+            theme()
+                .text_highlighted()
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        )
         .column_spacing(col_space)
         .rows(
             df.columns()
@@ -594,25 +1626,89 @@ fn build_table<'a>(
                 .zip_iters()
                 .enumerate()
                 .map(|(idx, vals)| {
-                    let cells = vals
+                    let lines = vals
                         .into_iter()
-                        .map(|val| Cell::new(val.into_single_line()));
-                    Row::new(cells).style(if striped {
-                        theme().row(offset_row + idx)
-                    } else {
-                        theme().row(0)
-                    })
+                        .zip(df.columns())
+                        .zip(col_widths)
+                        .enumerate()
+                        .map(|(col_idx, ((val, col), width))| {
+                            if let Some(marker) = null_marker.as_ref().filter(|_| val.is_null()) {
+                                return (
+                                    vec![Line::styled(
+                                        marker.clone(),
+                                        Style::new().add_modifier(Modifier::DIM),
+                                    )],
+                                    Style::default(),
+                                );
+                            }
+                            let style = column_styles[col_idx].patch(
+                                heat.and_then(|ranges| ranges[col_idx])
+                                    .zip(val.extract::<f64>())
+                                    .map(|(range, value)| heat_style(value, range))
+                                    .unwrap_or_default(),
+                            );
+                            let text = formatted(&formats[col_idx], val);
+                            let indices = highlight
+                                .filter(|highlight| highlight.applies_to(col.name()))
+                                .map(|highlight| highlight.indices(&text))
+                                .unwrap_or_default();
+                            let lines = fitted_lines(&text, width.value().into(), overflow)
+                                .into_iter()
+                                .map(|(start, line)| highlighted_line(line, &indices, start))
+                                .collect_vec();
+                            (lines, style)
+                        })
+                        .collect_vec();
+                    let height = lines
+                        .iter()
+                        .map(|(lines, _)| lines.len())
+                        .max()
+                        .unwrap_or(1);
+                    Row::new(
+                        lines
+                            .into_iter()
+                            .map(|(lines, style)| Cell::new(Text::from(lines)).style(style)),
+                    )
+                    .height(height as u16)
+                    .style(
+                        if selection.is_some_and(|rows| rows.contains(&(offset_row + idx))) {
+                            theme().text_highlighted()
+                        } else if let Some(status) = diff.and_then(|diff| diff.get(idx)) {
+                            rows_config
+                                .row_style(offset_row + idx, striped)
+                                .fg(match status {
+                                    "added" => Color::Green,
+                                    "removed" => Color::Red,
+                                    _ => Color::Yellow,
+                                })
+                        } else {
+                            rows_config.row_style(offset_row + idx, striped)
+                        },
+                    )
                 }),
         );
 
     if show_header {
-        table =
-            table.header(
-                Row::new(df.columns().iter().enumerate().map(|(i, c)| {
-                    Cell::new(c.name().as_str()).style(theme().header(offset_col + i))
-                }))
-                .style(theme().table_header()),
-            )
+        table = table.header(
+            Row::new(df.columns().iter().enumerate().map(|(i, c)| {
+                let text = header_text(c, dtype_badges);
+                // the arrow goes first so narrow columns keep it
+                let text = match sort {
+                    Some((column, descending)) if column == c.name().as_str() => {
+                        format!(
+                            "{} {text}",
+                            if descending { '\u{25bc}' } else { '\u{25b2}' }
+                        )
+                    }
+                    _ => text,
+                };
+                Cell::new(text).style(theme().header(offset_col + i))
+            }))
+            .style(theme().table_header()),
+        )
+    }
+    if let Some(footer) = footer {
+        table = table.footer(Row::new(footer).style(theme().table_header()));
     }
     table
 }
@@ -1,13 +1,21 @@
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::{KeyCode, KeyModifiers, MouseEvent, MouseEventKind};
+use itertools::Itertools;
 
 use ratatui::{
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Position, Rect},
     widgets::{Borders, Widget},
 };
+use strum_macros::Display;
 
 use crate::{
+    AppResult,
     handler::message::Message,
-    misc::config::config,
+    misc::{
+        config::config,
+        sql::{TableSource, sql},
+        transform::{self, JoinHow},
+        type_ext::UnwrapOrEnqueueError,
+    },
     tui::{
         component::{Component, FocusState},
         pane::TableDescription,
@@ -15,13 +23,34 @@ use crate::{
     },
 };
 
-use super::{pane::Pane, tab_switcher::TabSwitcher};
+use super::{
+    pane::Pane,
+    tab_switcher::{TabEntry, TabSwitcher},
+};
 
 #[derive(Debug)]
 pub struct Tabs {
     panes: Vec<Pane>,
     switcher: Option<TabSwitcher>,
     idx: usize,
+    split: Option<Split>,
+    /// Tabs last rendered and their areas, for the mouse.
+    areas: Vec<(usize, Rect)>,
+}
+
+/// Second tab shown next to the selected one.
+#[derive(Debug, Clone, Copy)]
+struct Split {
+    other: usize,
+    direction: SplitDirection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+enum SplitDirection {
+    #[strum(to_string = "side by side")]
+    Vertical,
+    #[strum(to_string = "stacked")]
+    Horizontal,
 }
 
 impl Tabs {
@@ -29,6 +58,132 @@ impl Tabs {
         self.panes.get(self.idx)
     }
 
+    /// Whether keys go to the table of the current tab.
+    pub fn is_browsing(&self) -> bool {
+        self.switcher.is_none() && self.selected().is_some_and(Pane::is_browsing)
+    }
+
+    pub fn titles(&self) -> Vec<String> {
+        self.panes
+            .iter()
+            .map(|pane| pane.title().to_owned())
+            .collect()
+    }
+
+    fn find_by_title(&self, title: &str) -> AppResult<&Pane> {
+        self.panes
+            .iter()
+            .find(|pane| pane.title() == title)
+            .ok_or(anyhow::anyhow!("Tab '{title}' not found"))
+    }
+
+    fn join(&self, other: &str, keys: &[(String, String)], how: JoinHow) -> AppResult<()> {
+        let left = self
+            .selected()
+            .ok_or(anyhow::anyhow!("No tab is selected"))?
+            .table()
+            .data_frame();
+        let right = self.find_by_title(other)?.table().data_frame();
+        let df = transform::join(left, right, keys, how)?;
+        let on = keys
+            .iter()
+            .map(|(l, r)| {
+                if l == r {
+                    l.to_owned()
+                } else {
+                    format!("{l}:{r}")
+                }
+            })
+            .join(", ");
+        Message::PanePushDataFrame(df, TableDescription::Join(format!("{how} {other} on {on}")))
+            .enqueue();
+        Ok(())
+    }
+
+    fn concat(&mut self, names: &[String]) -> AppResult<()> {
+        let mut frames = names
+            .iter()
+            .map(|name| Ok(self.find_by_title(name)?.table().data_frame()))
+            .collect::<AppResult<Vec<_>>>()?;
+        let mut names = names.to_vec();
+        if names.len() == 1
+            && let Some(pane) = self.selected()
+        {
+            frames.insert(0, pane.table().data_frame());
+            names.insert(0, pane.title().to_owned());
+        }
+        let df = transform::concat(&frames)?;
+        self.add(Pane::new(
+            df,
+            TableDescription::Concat(names.iter().join(" + ")),
+        ));
+        Ok(())
+    }
+
+    fn diff(&mut self, left: Option<&str>, right: &str, keys: &[String]) -> AppResult<()> {
+        let left = match left {
+            Some(name) => self.find_by_title(name)?,
+            None => self
+                .selected()
+                .ok_or(anyhow::anyhow!("No tab is selected"))?,
+        };
+        let title = format!("{} vs {right}", left.title());
+        let df = transform::diff(
+            left.table().data_frame(),
+            self.find_by_title(right)?.table().data_frame(),
+            keys,
+        )?;
+        Message::AppShowToast(format!("{} row(s) differ", df.height())).enqueue();
+        self.add(Pane::new(df, TableDescription::Diff(title)));
+        Ok(())
+    }
+
+    fn rename_selected(&mut self, name: &str) -> AppResult<()> {
+        self.panes
+            .get_mut(self.idx)
+            .ok_or(anyhow::anyhow!("No tab is selected"))?
+            .rename(name)?;
+        if self.switcher.is_some() {
+            self.show_tab_switcher();
+        }
+        Ok(())
+    }
+
+    /// Moves the selected tab `offset` places, keeping it selected and the
+    /// split showing the same tabs.
+    fn move_selected(&mut self, offset: isize) {
+        let target = self
+            .idx
+            .saturating_add_signed(offset)
+            .min(self.len().saturating_sub(1));
+        if target == self.idx {
+            return;
+        }
+        let pane = self.panes.remove(self.idx);
+        self.panes.insert(target, pane);
+        if let Some(split) = self.split.as_mut() {
+            if split.other > self.idx && split.other <= target {
+                split.other -= 1;
+            } else if split.other < self.idx && split.other >= target {
+                split.other += 1;
+            }
+        }
+        self.select(target);
+        if self.switcher.is_some() {
+            self.show_tab_switcher();
+        }
+    }
+
+    fn duplicate_selected(&mut self) -> AppResult<()> {
+        let pane = self
+            .selected()
+            .ok_or(anyhow::anyhow!("No tab is selected"))?
+            .duplicate();
+        self.add(pane);
+        Message::AppShowToast("Tab duplicated".to_owned()).enqueue();
+        Ok(())
+    }
+
     fn add(&mut self, tabular: Pane) {
         self.panes.push(tabular);
         self.idx = self.panes.len().saturating_sub(1);
@@ -46,10 +201,17 @@ impl Tabs {
         self.idx
     }
 
+    /// Selects the tab at `idx`; selecting the other tab of the split moves the
+    /// focus to its side instead of closing the split.
     fn select(&mut self, idx: usize) {
         if let Some(switcher) = self.switcher.as_mut() {
             switcher.select(idx);
         }
+        if let Some(split) = self.split.as_mut()
+            && split.other == idx
+        {
+            split.other = self.idx;
+        }
         self.idx = idx;
     }
 
@@ -57,31 +219,94 @@ impl Tabs {
         if self.idx < self.panes.len() {
             self.panes.remove(self.idx);
         }
+        self.idx = self.idx.min(self.len().saturating_sub(1));
+        self.split = None;
         if self.switcher.is_some() {
             self.show_tab_switcher();
         }
     }
 
-    fn select_prev(&mut self) {
-        self.select(self.idx().saturating_sub(1));
+    /// Closes the tabs to the right of the selected one and, unless
+    /// `right_only`, the ones to its left.
+    fn close_others(&mut self, right_only: bool) {
+        if self.is_empty() {
+            return;
+        }
+        let idx = self.idx.min(self.len() - 1);
+        let closed = self.len() - if right_only { idx + 1 } else { 1 };
+        self.panes.truncate(idx + 1);
+        if !right_only {
+            self.panes.drain(..idx);
+            self.idx = 0;
+        }
+        self.split = None;
+        if self.switcher.is_some() {
+            self.show_tab_switcher();
+        }
+        Message::AppShowToast(format!("{closed} tab(s) closed")).enqueue();
     }
 
-    fn select_next(&mut self) {
+    fn select_by(&mut self, offset: isize) {
         self.select(
             self.idx()
-                .saturating_add(1)
+                .saturating_add_signed(offset)
                 .min(self.len().saturating_sub(1)),
         );
     }
+    /// Splits the view with the next tab side by side, then stacked, then
+    /// goes back to a single tab.
+    fn cycle_split(&mut self) {
+        self.split = match self.split {
+            None if self.len() < 2 => {
+                Message::AppShowToast("Open another tab to split the view".to_owned()).enqueue();
+                return;
+            }
+            None => Some(Split {
+                other: (self.idx + 1) % self.len(),
+                direction: SplitDirection::Vertical,
+            }),
+            Some(Split {
+                other,
+                direction: SplitDirection::Vertical,
+            }) => Some(Split {
+                other,
+                direction: SplitDirection::Horizontal,
+            }),
+            Some(Split {
+                direction: SplitDirection::Horizontal,
+                ..
+            }) => None,
+        };
+        Message::AppShowToast(match self.split {
+            Some(split) => format!("Split view: {}", split.direction),
+            None => "Split view closed".to_owned(),
+        })
+        .enqueue();
+    }
+
+    /// Moves the focus to the other tab of the split.
+    fn switch_split_focus(&mut self) {
+        if let Some(split) = self.split.as_mut() {
+            std::mem::swap(&mut split.other, &mut self.idx);
+        }
+    }
+
     fn show_tab_switcher(&mut self) {
-        self.switcher = Some(TabSwitcher::new(
-            "Tabs",
-            self.panes
-                .iter()
-                .map(|pane| pane.title().to_owned())
-                .collect(),
-            self.idx,
-        ));
+        let tabs = self
+            .panes
+            .iter()
+            .map(|pane| TabEntry {
+                title: pane.title().to_owned(),
+                path: sql()
+                    .schema()
+                    .get(pane.title())
+                    .map(|info| info.source())
+                    .filter(|source| matches!(source, TableSource::File(_) | TableSource::Url(_)))
+                    .map(|source| source.display_path().into_owned()),
+                shape: pane.table().data_frame().shape(),
+            })
+            .collect();
+        self.switcher = Some(TabSwitcher::new("Tabs", tabs, self.idx));
     }
 
     fn dismiss_tab_switcher(&mut self) {
@@ -97,6 +322,7 @@ impl Component for Tabs {
         focus_state: super::component::FocusState,
     ) {
         self.idx = self.idx().min(self.len().saturating_sub(1));
+        self.areas.clear();
 
         let area = {
             if config().show_table_borders() {
@@ -129,21 +355,88 @@ impl Component for Tabs {
         };
 
         // render tabular
-        match (self.switcher.as_mut(), self.panes.get_mut(self.idx)) {
-            (Some(switcher), Some(pane)) => {
-                pane.render(area, buf, FocusState::NotFocused);
-                switcher.render(area, buf, focus_state);
-            }
-            (Some(switcher), None) => {
-                switcher.render(area, buf, focus_state);
+        let pane_focus = if self.switcher.is_some() {
+            FocusState::NotFocused
+        } else {
+            focus_state
+        };
+        match self
+            .split
+            .filter(|split| split.other != self.idx && split.other < self.len())
+        {
+            Some(split) => {
+                let [first_area, second_area] = match split.direction {
+                    SplitDirection::Vertical => {
+                        Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area)
+                    }
+                    SplitDirection::Horizontal => {
+                        Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(area)
+                    }
+                };
+                let second_area = {
+                    let blk = Block::default().borders(match split.direction {
+                        SplitDirection::Vertical => Borders::LEFT,
+                        SplitDirection::Horizontal => Borders::TOP,
+                    });
+                    let inner = blk.inner(second_area);
+                    blk.render(second_area, buf);
+                    inner
+                };
+                let (first, second) = if self.idx < split.other {
+                    (self.idx, split.other)
+                } else {
+                    (split.other, self.idx)
+                };
+                for (idx, area) in [(first, first_area), (second, second_area)] {
+                    self.areas.push((idx, area));
+                    if let Some(pane) = self.panes.get_mut(idx) {
+                        pane.render(
+                            area,
+                            buf,
+                            if idx == self.idx {
+                                pane_focus
+                            } else {
+                                FocusState::NotFocused
+                            },
+                        );
+                    }
+                }
             }
-            (None, Some(pane)) => {
-                pane.render(area, buf, focus_state);
+            None => {
+                self.split = None;
+                self.areas.push((self.idx, area));
+                if let Some(pane) = self.panes.get_mut(self.idx) {
+                    pane.render(area, buf, pane_focus);
+                }
             }
-            (None, None) => (),
+        }
+        if let Some(switcher) = self.switcher.as_mut() {
+            switcher.render(area, buf, focus_state);
         }
     }
 
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if let Some(switcher) = self.switcher.as_mut() {
+            return switcher.handle_mouse(event);
+        }
+        let Some(idx) = self
+            .areas
+            .iter()
+            .find(|(_, area)| area.contains(Position::new(event.column, event.row)))
+            .map(|(idx, _)| *idx)
+        else {
+            return false;
+        };
+        // clicking the other side of the split moves the focus to it
+        if idx != self.idx && matches!(event.kind, MouseEventKind::Down(_)) {
+            self.switch_split_focus();
+        }
+        self.panes
+            .get_mut(idx)
+            .map(|pane| pane.handle_mouse(event))
+            .unwrap_or_default()
+    }
+
     fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
         if let Some(switcher) = self.switcher.as_mut() {
             switcher.handle(event)
@@ -153,7 +446,7 @@ impl Component for Tabs {
                 .map(|pane| pane.handle(event))
                 .unwrap_or_default()
                 || match (event.code, event.modifiers) {
-                    (KeyCode::Char('q'), KeyModifiers::NONE) => {
+                    (KeyCode::Char('x'), KeyModifiers::NONE) => {
                         self.remove_selected();
                         if self.is_empty() {
                             Message::Quit.enqueue();
@@ -164,14 +457,34 @@ impl Component for Tabs {
                         self.show_tab_switcher();
                         true
                     }
+                    (KeyCode::Char('W'), KeyModifiers::SHIFT) => {
+                        self.cycle_split();
+                        true
+                    }
+                    (KeyCode::Char('w'), KeyModifiers::CONTROL) if self.split.is_some() => {
+                        self.switch_split_focus();
+                        true
+                    }
+                    (KeyCode::Char('H'), m) | (KeyCode::Left, m)
+                        if m == KeyModifiers::ALT | KeyModifiers::SHIFT =>
+                    {
+                        self.move_selected(-1);
+                        true
+                    }
+                    (KeyCode::Char('L'), m) | (KeyCode::Right, m)
+                        if m == KeyModifiers::ALT | KeyModifiers::SHIFT =>
+                    {
+                        self.move_selected(1);
+                        true
+                    }
                     (KeyCode::Char('H'), KeyModifiers::SHIFT)
                     | (KeyCode::Left, KeyModifiers::SHIFT) => {
-                        self.select_prev();
+                        Message::TabsSelectBy(-1).enqueue();
                         true
                     }
                     (KeyCode::Char('L'), KeyModifiers::SHIFT)
                     | (KeyCode::Right, KeyModifiers::SHIFT) => {
-                        self.select_next();
+                        Message::TabsSelectBy(1).enqueue();
                         true
                     }
                     _ => false,
@@ -193,7 +506,31 @@ impl Component for Tabs {
                     TableDescription::Query(query.to_owned()),
                 ));
             }
+            Message::TabsAddPane(df, description) => {
+                self.add(Pane::new(df.clone(), description.clone()));
+            }
+            Message::TabsJoin(other, keys, how) if focus_state.is_focused() => {
+                self.join(other, keys, *how).unwrap_or_enqueue_error()
+            }
+            Message::TabsConcat(names) if focus_state.is_focused() => {
+                self.concat(names).unwrap_or_enqueue_error()
+            }
+            Message::TabsDiff(left, right, keys) if focus_state.is_focused() => self
+                .diff(left.as_deref(), right, keys)
+                .unwrap_or_enqueue_error(),
+            Message::TabsRename(name) if focus_state.is_focused() => {
+                self.rename_selected(name).unwrap_or_enqueue_error()
+            }
+            Message::TabsCloseOthers if focus_state.is_focused() => self.close_others(false),
+            Message::TabsCloseRight if focus_state.is_focused() => self.close_others(true),
+            Message::TabsDuplicate if focus_state.is_focused() => {
+                self.duplicate_selected().unwrap_or_enqueue_error()
+            }
+            Message::TabsMoveSelected(offset) if focus_state.is_focused() => {
+                self.move_selected(*offset)
+            }
             Message::TabsSelect(idx) if focus_state.is_focused() => self.select(*idx),
+            Message::TabsSelectBy(offset) if focus_state.is_focused() => self.select_by(*offset),
             Message::TabsDismissSwitcher if focus_state.is_focused() => self.dismiss_tab_switcher(),
             _ => (),
         }
@@ -232,6 +569,8 @@ impl FromIterator<Pane> for Tabs {
             panes: iter.into_iter().collect(),
             idx: 0,
             switcher: None,
+            split: None,
+            areas: Vec::new(),
         }
     }
 }
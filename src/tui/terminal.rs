@@ -1,8 +1,12 @@
 use crate::AppResult;
 use crate::app::App;
+use crate::misc::color_mode;
+use crate::misc::config::config;
+use crate::misc::graphics::flush_images;
 use crate::misc::type_ext::UnwrapOrGracefulShutdown;
 use crate::tui::component::Component;
 use crate::tui::component::FocusState;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::prelude::CrosstermBackend;
 use std::io;
@@ -19,7 +23,7 @@ static INVALIDATE_TUI: AtomicBool = AtomicBool::new(false);
 
 pub fn start_tui() -> AppResult<()> {
     terminal::enable_raw_mode()?;
-    crossterm::execute!(io::stdout(), EnterAlternateScreen)?;
+    crossterm::execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
     let panic_hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic| {
         let _ = stop_tui();
@@ -33,20 +37,25 @@ pub fn start_tui() -> AppResult<()> {
 
 pub fn stop_tui() -> AppResult<()> {
     terminal::disable_raw_mode()?;
-    crossterm::execute!(io::stdout(), LeaveAlternateScreen)?;
+    crossterm::execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
     terminal().show_cursor()?;
     Ok(())
 }
 
 pub fn draw(app: &mut App) -> AppResult<()> {
-    if INVALIDATE_TUI.swap(false, Ordering::Relaxed) {
+    let cleared = INVALIDATE_TUI.swap(false, Ordering::Relaxed);
+    if cleared {
         terminal().clear().unwrap_or_graceful_shutdown();
     }
     terminal().draw(|frame| {
         let area = frame.area();
         let buf = frame.buffer_mut();
         app.render(area, buf, FocusState::Focused);
+        color_mode::degrade(buf, config().color_mode());
     })?;
+    if flush_images(cleared) {
+        invalidate_tui();
+    }
     Ok(())
 }
 
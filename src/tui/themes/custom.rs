@@ -1,4 +1,6 @@
-use std::fs;
+use std::{fs, path::Path};
+
+use anyhow::anyhow;
 
 use ratatui::style::{Color, Style};
 use serde::{Deserialize, Serialize};
@@ -25,9 +27,27 @@ pub struct Custom {
 
 impl Custom {
     fn load() -> AppResult<Self> {
-        let path = theme_path()?;
-        let contents = fs::read_to_string(path)?;
-        Ok(toml::from_str(&contents)?)
+        Self::from_file(theme_path()?)
+    }
+
+    /// Reads a theme definition file, with the defaults for missing styles.
+    pub fn from_file(path: impl AsRef<Path>) -> AppResult<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|err| anyhow!("Failed to read theme {}: {err}", path.display()))?;
+        let theme: Custom = toml::from_str(&contents)
+            .map_err(|err| anyhow!("Invalid theme {}: {err}", path.display()))?;
+        for (name, styles) in [
+            ("table_headers", &theme.table_headers),
+            ("rows", &theme.rows),
+            ("table_tags", &theme.table_tags),
+            ("chart", &theme.chart),
+        ] {
+            if styles.is_empty() {
+                return Err(anyhow!("Theme {} has no {name} styles", path.display()));
+            }
+        }
+        Ok(theme)
     }
 
     fn store(&self) -> AppResult<()> {
@@ -1,13 +1,18 @@
 use std::{
     fmt::{Debug, Display},
+    fs,
     ops::Deref,
+    path::PathBuf,
     str::FromStr,
     sync::Arc,
 };
 
+use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
 use strum_macros::{AsRefStr, EnumIter, EnumString};
 
+use crate::misc::paths::themes_dir;
 use crate::tui::themes::{
     aardvark_blue::AardvarkBlue, abernathy::Abernathy, adventure::Adventure,
     adventure_time::AdventureTime, adwaita::Adwaita, adwaita_dark::AdwaitaDark,
@@ -145,6 +150,7 @@ use crate::tui::themes::{
 pub struct LoadedTheme {
     app_theme: Theme,
     styler: Arc<dyn Styler + Send + Sync>,
+    file: Option<String>,
 }
 
 impl LoadedTheme {
@@ -152,14 +158,71 @@ impl LoadedTheme {
         &self.app_theme
     }
 
+    /// Name or path of the theme file the theme was loaded from.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
     fn new<S: Styler + Send + Sync + 'static>(app_theme: Theme, theme: S) -> Self {
         LoadedTheme {
             app_theme,
             styler: Arc::new(theme),
+            file: None,
         }
     }
 }
 
+impl FromStr for LoadedTheme {
+    type Err = anyhow::Error;
+
+    /// Loads a built-in theme by name, a theme file by its name in the themes
+    /// directory, or a theme file by its path.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(theme) = built_in(s) {
+            return Ok(theme.into());
+        }
+        let path = theme_file(s).ok_or(anyhow!("Theme not found: {s}"))?;
+        Ok(LoadedTheme {
+            file: Some(s.to_owned()),
+            ..LoadedTheme::new(Theme::Custom, Custom::from_file(path)?)
+        })
+    }
+}
+
+impl LoadedTheme {
+    /// Whether `name` is a built-in theme or a theme file.
+    pub fn exists(name: &str) -> bool {
+        built_in(name).is_some() || theme_file(name).is_some()
+    }
+}
+
+/// Names of the theme files in the themes directory, sorted.
+pub fn theme_files() -> Vec<String> {
+    let Some(entries) = themes_dir().ok().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut names = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "toml").then_some(path.file_stem()?.to_str()?.to_owned())
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+fn built_in(name: &str) -> Option<Theme> {
+    Theme::iter().find(|theme| theme.id().eq_ignore_ascii_case(name))
+}
+
+fn theme_file(name: &str) -> Option<PathBuf> {
+    themes_dir()
+        .ok()
+        .map(|dir| dir.join(format!("{name}.toml")))
+        .filter(|path| path.is_file())
+        .or_else(|| Some(PathBuf::from(name)).filter(|path| path.is_file()))
+}
+
 impl Default for LoadedTheme {
     fn default() -> Self {
         Self::new(Theme::Monokai, Monokai)
@@ -597,7 +660,7 @@ impl Serialize for LoadedTheme {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(self.app_theme.id())
+        serializer.serialize_str(self.file.as_deref().unwrap_or(self.app_theme.id()))
     }
 }
 
@@ -607,9 +670,7 @@ impl<'de> Deserialize<'de> for LoadedTheme {
         D: serde::Deserializer<'de>,
     {
         let theme_str = String::deserialize(deserializer)?;
-        let app_theme =
-            Theme::from_str(&theme_str).map_err(|e| serde::de::Error::custom(e.to_string()))?;
-        Ok(LoadedTheme::from(app_theme))
+        LoadedTheme::from_str(&theme_str).map_err(|e| serde::de::Error::custom(e.to_string()))
     }
 }
 
@@ -13,6 +13,10 @@ impl Scroll {
         self.val = self.val.saturating_add(1).min(self.max);
     }
 
+    pub fn set(&mut self, val: usize) {
+        self.val = val.min(self.max);
+    }
+
     pub fn adjust(&mut self, lines: usize, height: u16) {
         self.max = lines.saturating_sub(height.into());
         self.val = self.val.min(self.max);
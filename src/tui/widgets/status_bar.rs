@@ -63,8 +63,16 @@ impl<'a> Widget for StatusBar<'a> {
             ),
             3,
         );
-        let [history_area, tab_area, row_area, shp_area] = Layout::horizontal([
+        let match_tag = self.pane.match_status().map(|(pos, total)| {
+            Tag::new(
+                "Match",
+                format!("{pos:>width$} / {total}", width = total.to_string().len()),
+                4,
+            )
+        });
+        let [history_area, match_area, tab_area, row_area, shp_area] = Layout::horizontal([
             Constraint::Fill(3),
+            Constraint::Length(match_tag.as_ref().map(Tag::width).unwrap_or_default()),
             Constraint::Length(tab_tag.width()),
             Constraint::Length(row_tag.width()),
             Constraint::Length(shp_tag.width()),
@@ -72,6 +80,9 @@ impl<'a> Widget for StatusBar<'a> {
         .spacing(1)
         .areas(area);
 
+        if let Some(match_tag) = match_tag {
+            match_tag.line().render(match_area, buf);
+        }
         tab_tag.line().render(tab_area, buf);
         row_tag.line().render(row_area, buf);
         shp_tag.line().render(shp_area, buf);